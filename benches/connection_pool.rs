@@ -0,0 +1,96 @@
+//! Time sequential requests with and without connection pooling, the case the
+//! [`HttpOptions`] docs recommend pooling for.
+//!
+//! Runs against a local keep-alive server by default, which only measures the TCP
+//! handshake. Set `MYUST_BENCH_URL`, e.g. to `https://api.mystb.in`, to include
+//! TLS and the network:
+//!
+//! ```sh
+//! MYUST_BENCH_URL=https://api.mystb.in cargo bench --bench connection_pool
+//! ```
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+use myust::{Client, HttpOptions};
+
+const REQUESTS: u32 = 200;
+
+/// Answer every request with an empty object, keeping connections open.
+fn serve_keep_alive() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                loop {
+                    let mut content_length = 0;
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                            return;
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap();
+                            }
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    if reader.read_exact(&mut body).is_err()
+                        || write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{{}}")
+                            .is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    url
+}
+
+async fn time_requests(url: &str, options: HttpOptions) -> Duration {
+    let client = Client::new().base_url(url).http_options(options);
+    // the first request connects in both cases
+    client.ping().await.unwrap();
+    let started = Instant::now();
+    for _ in 0..REQUESTS {
+        client.ping().await.unwrap();
+    }
+    started.elapsed() / REQUESTS
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let url = std::env::var("MYUST_BENCH_URL").unwrap_or_else(|_| serve_keep_alive());
+    let pooled = time_requests(
+        &url,
+        HttpOptions {
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            pool_max_idle_per_host: Some(32),
+            ..Default::default()
+        },
+    )
+    .await;
+    let unpooled = time_requests(
+        &url,
+        HttpOptions {
+            pool_max_idle_per_host: Some(0),
+            ..Default::default()
+        },
+    )
+    .await;
+    println!("{} requests to {}", REQUESTS, url);
+    println!("pooled:   {:?} per request", pooled);
+    println!("unpooled: {:?} per request", unpooled);
+}
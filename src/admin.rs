@@ -0,0 +1,152 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "admin")))]
+
+//! Operations reserved to the administrators of an instance, e.g. a self-hosted
+//! mystb.in, reached with [`Client::admin`].
+//!
+//! ```rust,no_run
+//! use myust::Client;
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new()
+//!     .base_url("https://api.paste.internal.example")
+//!     .auth("ADMIN_TOKEN")
+//!     .await;
+//! let admin = client.admin();
+//! println!("{:?}", admin.stats().await?);
+//! admin.delete_paste("EquipmentMovingExpensive").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The API rejects these requests with a 403 status when the token isn't an
+//! administrator's.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    parse::{from_field, json_body},
+    Client, Error, MystbinError,
+};
+
+/// A user of the instance, as listed by [`Admin::users`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct InstanceUser {
+    /// The user's ID.
+    pub id: u64,
+    /// The user's name.
+    pub username: String,
+    /// Whether the user is an administrator.
+    pub admin: bool,
+    /// Whether the user is a subscriber.
+    pub subscriber: bool,
+    /// Whether the user is banned.
+    pub banned: bool,
+}
+
+/// The usage of the instance, see [`Admin::stats`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct InstanceStats {
+    /// The number of stored pastes.
+    pub pastes: u64,
+    /// The number of registered users.
+    pub users: u64,
+    /// The other figures sent by the API, which vary between versions.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, Value>,
+}
+
+/// The admin operations of a [`Client`], see the [module documentation](self).
+#[derive(Clone, Copy, Debug)]
+pub struct Admin<'a> {
+    client: &'a Client,
+}
+
+impl Client {
+    /// The operations reserved to administrators, for tokens with the rights.
+    pub fn admin(&self) -> Admin<'_> {
+        Admin { client: self }
+    }
+}
+
+impl Admin<'_> {
+    /// Delete any paste, whoever owns it, by ID or URL, see
+    /// [`IntoPasteId`](crate::IntoPasteId).
+    pub async fn delete_paste(&self, paste_id: impl crate::IntoPasteId) -> Result<(), Error> {
+        let paste_id = paste_id.into_paste_id()?;
+        let routes = self.client.routes();
+        let response = self
+            .client
+            .request(
+                &routes.admin_delete_paste,
+                Some(paste_id.as_str()),
+                "",
+                json!({}),
+            )
+            .await?;
+        match response.status_code {
+            200 | 204 => Ok(()),
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+
+    /// A page of the instance's users, starting at 1.
+    pub async fn users(&self, page: u32) -> Result<Vec<InstanceUser>, Error> {
+        let routes = self.client.routes();
+        let query = format!("?page={}", page);
+        let response = self
+            .client
+            .request(&routes.admin_users, None, &query, json!({}))
+            .await?;
+        match response.status_code {
+            200 => {
+                let mut data = json_body(response.json)?;
+                from_field(&mut data, "users")
+            }
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+
+    /// Ban a user, who can't use the API anymore.
+    pub async fn ban_user(&self, user_id: u64) -> Result<(), Error> {
+        self.set_ban(user_id, true).await
+    }
+
+    /// Lift the ban of a user.
+    pub async fn unban_user(&self, user_id: u64) -> Result<(), Error> {
+        self.set_ban(user_id, false).await
+    }
+
+    async fn set_ban(&self, user_id: u64, banned: bool) -> Result<(), Error> {
+        let routes = self.client.routes();
+        let endpoint = match banned {
+            true => &routes.admin_ban_user,
+            false => &routes.admin_unban_user,
+        };
+        let id = user_id.to_string();
+        let response = self
+            .client
+            .request(endpoint, Some(&id), "", json!({}))
+            .await?;
+        match response.status_code {
+            200 | 204 => Ok(()),
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+
+    /// The usage of the instance.
+    pub async fn stats(&self) -> Result<InstanceStats, Error> {
+        let routes = self.client.routes();
+        let response = self
+            .client
+            .request(&routes.admin_stats, None, "", json!({}))
+            .await?;
+        match response.status_code {
+            200 => serde_json::from_value(json_body(response.json)?)
+                .map_err(|err| Error::InvalidResponse(err.to_string())),
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+}
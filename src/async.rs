@@ -1,347 +1,1239 @@
-use std::{collections::HashMap, ops::FnOnce};
+use std::{
+    borrow::Cow,
+    fmt, io,
+    mem::take,
+    ops::FnOnce,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
 
 use crate::{
     builders::*,
+    cache::*,
+    hooks::*,
+    limiter::RequestLimiter,
+    options::*,
+    parse::{array_field, json_body, str_field, Lenient},
+    paste_id::{IntoPasteId, PasteId},
+    provider::PasteProvider,
+    routes::{ApiVersion, Endpoint, Routes},
+    stream::{StreamedBody, StreamedFile},
     structs::{response::MyustResponse, *},
+    tokens::{TokenIndex, TokenPool, TokenRotation},
     traits::*,
     utils::*,
 };
 
 use async_trait::async_trait;
-use reqwest::Method;
-use serde_json::{json, Map, Value};
+use futures_util::{stream, Stream, StreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+    StatusCode, Url,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
 
 /// A client to interact with the API.
 ///
 /// Use this if you're not doing anything users-related endpoints.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Client {
     inner: reqwest::Client,
     token: Option<String>,
+    base_url: String,
+    web_url: String,
+    default_expiry: Option<Expiry>,
+    journal: Option<Arc<crate::journal::Journal>>,
+    deduplicate: bool,
+    metrics: Option<Arc<dyn Metrics>>,
+    parse_warnings: Option<Arc<dyn ParseWarnings>>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    observers: Vec<Arc<dyn Observer>>,
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    last_url: Arc<Mutex<Option<Url>>>,
+    limiter: Option<RequestLimiter>,
+    tokens: Option<Arc<TokenPool>>,
+    api_version: ApiVersion,
+    validators: Option<Validators>,
+    cache: Option<Arc<dyn PasteCache>>,
+    #[cfg(feature = "encryption")]
+    encryption: Option<Arc<crate::encryption::Encryption>>,
+    #[cfg(feature = "signing")]
+    signing_key: Option<Arc<crate::signing::SigningKey>>,
+    http: HttpOptions,
+    #[cfg(feature = "middleware")]
+    middleware: Option<reqwest_middleware::ClientWithMiddleware>,
 }
 
 impl Client {
-    async fn check_token(client: reqwest::Client, token: String) -> u16 {
-        client
-            .get(SELF_ENDPOINT)
+    async fn check_token(&self, token: &str) -> Result<MyustResponse, Error> {
+        let request = self
+            .inner
+            .get(self.routes().current_user.url(&self.base_url, None, "")?)
             .header("Authorization", format!("Bearer {}", token))
-            .send()
-            .await
-            .unwrap()
-            .status()
-            .as_u16()
+            .build()?;
+        let response = self.execute(request).await?;
+        let request_id = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        Ok(self.read_response(response, request_id).await)
+    }
+
+    async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response, Error> {
+        let _permit = match &self.limiter {
+            Some(limiter) => Some(limiter.acquire().await),
+            None => None,
+        };
+        #[cfg(feature = "middleware")]
+        if let Some(middleware) = &self.middleware {
+            return middleware.execute(request).await.map_err(middleware_error);
+        }
+        Ok(self.inner.execute(request).await?)
+    }
+
+    fn has_middleware(&self) -> bool {
+        #[cfg(feature = "middleware")]
+        return self.middleware.is_some();
+        #[cfg(not(feature = "middleware"))]
+        false
     }
 
     /// Instantiate a new Client.
+    ///
+    /// Like `reqwest`'s own clients, this panics if the TLS backend can't be
+    /// initialized.
+    #[allow(clippy::expect_used)] // documented, nothing can be done about it
     pub fn new() -> Self {
+        Self::with_pool(
+            apply_http_options!(&HttpOptions::default(), reqwest::Client::builder())
+                .build()
+                .expect("the TLS backend can't be initialized"),
+        )
+    }
+
+    /// A new client with the default settings that shares this client's connection
+    /// pool and [`HttpOptions`].
+    ///
+    /// This is cheap, so use it to talk to several accounts or instances at once,
+    /// e.g. with `auth` or `with_config`.
+    pub fn share_pool(&self) -> Self {
         Client {
-            inner: reqwest::Client::new(),
-            ..Default::default()
+            http: self.http.clone(),
+            #[cfg(feature = "middleware")]
+            middleware: self.middleware.clone(),
+            ..Self::with_pool(self.inner.clone())
+        }
+    }
+
+    fn with_pool(inner: reqwest::Client) -> Self {
+        Client {
+            inner,
+            token: None,
+            base_url: BASE_URL.to_string(),
+            web_url: WEB_URL.to_string(),
+            default_expiry: None,
+            journal: None,
+            deduplicate: false,
+            metrics: None,
+            parse_warnings: None,
+            interceptors: vec![],
+            observers: vec![],
+            rate_limit: Arc::default(),
+            last_url: Arc::default(),
+            limiter: None,
+            tokens: None,
+            api_version: ApiVersion::default(),
+            validators: None,
+            cache: None,
+            #[cfg(feature = "encryption")]
+            encryption: None,
+            #[cfg(feature = "signing")]
+            signing_key: None,
+            http: HttpOptions::default(),
+            #[cfg(feature = "middleware")]
+            middleware: None,
         }
     }
 
+    /// Instantiate a new Client that sends its requests through a
+    /// [`reqwest_middleware`] stack, so existing middleware (tracing, retries,
+    /// caching, ...) composes with myust.
+    #[cfg(feature = "middleware")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "middleware")))]
+    pub fn with_middleware(client: reqwest_middleware::ClientWithMiddleware) -> Self {
+        Client {
+            middleware: Some(client),
+            ..Self::new()
+        }
+    }
+
+    /// Use a different base URL for the API, e.g. a self-hosted instance
+    /// or a local test server.
+    ///
+    /// Defaults to `https://api.mystb.in`.
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use a different URL for the website pastes are viewed on, as returned by
+    /// [`paste_url`](Self::paste_url).
+    ///
+    /// Defaults to `https://mystb.in`.
+    pub fn web_url(mut self, url: impl Into<String>) -> Self {
+        self.web_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use the routes of another version of the API.
+    ///
+    /// Defaults to [`ApiVersion::V3`].
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = version;
+        self
+    }
+
+    pub(crate) fn routes(&self) -> &'static Routes {
+        self.api_version.routes()
+    }
+
+    /// The URL where a paste can be viewed in a browser.
+    pub fn paste_url(&self, paste_id: &str) -> String {
+        format!("{}/{}", self.web_url, paste_id)
+    }
+
+    /// Expire pastes created without an expiration date after `expiry`.
+    pub fn default_expiry(mut self, expiry: Expiry) -> Self {
+        self.default_expiry = Some(expiry);
+        self
+    }
+
+    /// Record every paste this client creates in a [`Journal`](crate::journal::Journal)
+    /// at `path`.
+    ///
+    /// The recorded URLs use the web URL set so far, so call this after `web_url`.
+    pub fn journal(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        let journal = crate::journal::Journal::new(path).web_url(&self.web_url);
+        self.journal = Some(Arc::new(journal));
+        self
+    }
+
+    /// Before creating a paste, look for one with the same files and password in the
+    /// journal, and return it instead of uploading a duplicate.
+    ///
+    /// Only unexpired pastes that can still be fetched are reused, and the expiration
+    /// date of the request is ignored. It can be changed for a paste with
+    /// [`PasteBuilder::deduplicate`].
+    ///
+    /// **Without a `journal`, every paste deduplicated fails with
+    /// [`Error::Unsupported`].**
+    pub fn deduplicate(mut self, enabled: bool) -> Self {
+        self.deduplicate = enabled;
+        self
+    }
+
+    /// Authenticate with a token without checking it first, unlike `auth`.
+    #[cfg_attr(not(any(feature = "config", feature = "keyring")), allow(dead_code))]
+    pub(crate) fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(format!("Bearer {}", token));
+        self
+    }
+
+    /// Configure the underlying HTTP connections, see [`HttpOptions`].
+    ///
+    /// This replaces the connection pool, so call it before sharing the client.
+    /// It panics if the options can't be applied, e.g. with a root certificate
+    /// the TLS backend rejects, use [`try_http_options`](Self::try_http_options) to
+    /// handle it instead.
+    #[allow(clippy::panic)] // documented, `try_http_options` is the fallible version
+    pub fn http_options(self, options: HttpOptions) -> Self {
+        match self.try_http_options(options) {
+            Ok(client) => client,
+            Err(err) => panic!("invalid HTTP options: {}", err),
+        }
+    }
+
+    /// Configure the underlying HTTP connections, see [`HttpOptions`], failing if
+    /// the options can't be applied.
+    pub fn try_http_options(mut self, options: HttpOptions) -> Result<Self, Error> {
+        self.inner = apply_http_options!(&options, reqwest::Client::builder()).build()?;
+        self.http = options;
+        Ok(self)
+    }
+
+    /// Register a [`Metrics`] hook called after every request.
+    pub fn metrics(mut self, metrics: impl Metrics + 'static) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// Register a [`ParseWarnings`] hook told about odd response data, such as a
+    /// paste without a creation date, that was worked around instead of failing
+    /// the call.
+    pub fn parse_warnings(mut self, hook: impl ParseWarnings + 'static) -> Self {
+        self.parse_warnings = Some(Arc::new(hook));
+        self
+    }
+
+    /// Register an [`Interceptor`] that can modify outgoing requests and observe raw responses.
+    ///
+    /// Interceptors run in the order they were registered.
+    pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Register an [`Observer`] notified when pastes are created or deleted,
+    /// and when bookmarks change.
+    pub fn observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.observers.push(Arc::new(observer));
+        self
+    }
+
+    /// Remember the `ETag` of every paste fetched with `get_paste` and send it back
+    /// as `If-None-Match` when the same paste is fetched again.
+    ///
+    /// When the API answers `304 Not Modified`, the remembered paste is returned
+    /// without downloading it again. Clones of this client share the same validators.
+    pub fn conditional_requests(mut self, enabled: bool) -> Self {
+        self.validators = enabled.then(Validators::default);
+        self
+    }
+
+    /// Serve repeated `get_paste` calls from an in-memory [`MemoryCache`].
+    ///
+    /// Pastes deleted through this client are dropped from the cache.
+    /// Clones of this client share the same cache.
+    pub fn with_cache(self, config: CacheConfig) -> Self {
+        self.cache_store(MemoryCache::new(config))
+    }
+
+    /// Serve repeated `get_paste` calls from your own [`PasteCache`].
+    pub fn cache_store(mut self, cache: impl PasteCache + 'static) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Encrypt the content of every uploaded file, and decrypt encrypted files
+    /// returned by `get_paste`.
+    ///
+    /// Content read from a reader can't be encrypted, such uploads fail with
+    /// [`Error::Unsupported`].
+    #[cfg(feature = "encryption")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+    pub fn encryption(mut self, encryption: crate::encryption::Encryption) -> Self {
+        self.encryption = Some(Arc::new(encryption));
+        self
+    }
+
+    /// Sign every created paste, attaching the signature as an extra file named
+    /// [`SIGNATURE_FILENAME`](crate::signing::SIGNATURE_FILENAME).
+    ///
+    /// The signature covers the files before encryption. Content read from a reader
+    /// can't be signed, such uploads fail with [`Error::Unsupported`].
+    #[cfg(feature = "signing")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signing")))]
+    pub fn signing_key(mut self, key: crate::signing::SigningKey) -> Self {
+        self.signing_key = Some(Arc::new(key));
+        self
+    }
+
+    fn notify(&self, event: impl Fn(&dyn Observer)) {
+        for observer in &self.observers {
+            event(observer.as_ref());
+        }
+    }
+
+    fn lenient(&self) -> Lenient<'_> {
+        Lenient(self.parse_warnings.as_deref())
+    }
+
+    fn paste_deleted(&self, paste_id: &str) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(paste_id);
+        }
+        if let Some(validators) = &self.validators {
+            lock(validators).retain(|(id, _), _| id != paste_id);
+        }
+        if let Some(pool) = &self.tokens {
+            pool.deleted(paste_id);
+        }
+        self.notify(|o| o.on_paste_deleted(paste_id));
+    }
+
+    /// Wait for a slot of `limiter` before sending each request, so this client, its
+    /// clones and the other clients sharing the limiter have a bounded number of
+    /// requests in flight.
+    pub fn request_limiter(mut self, limiter: RequestLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// The rate limit state reported on the latest response, if the API sent one.
+    ///
+    /// Clones of this client share the same state.
+    pub fn rate_limit_status(&self) -> Option<RateLimit> {
+        *lock(&self.rate_limit)
+    }
+
+    /// The URL of the latest response, after following redirects, without the paste
+    /// password.
+    ///
+    /// Clones of this client share the same state.
+    pub fn last_url(&self) -> Option<Url> {
+        lock(&self.last_url).clone()
+    }
+
+    /// Check that the API is reachable and measure the round-trip time.
+    ///
+    /// Any response below 500 counts as reachable, the time being measured until
+    /// its headers arrive. The request goes through the limiter, interceptors and
+    /// hooks like any other, as `GET /`.
+    pub async fn ping(&self) -> Result<Duration, Error> {
+        let started = Instant::now();
+        let (response, request_id) = self
+            .send(
+                &self.routes().ping,
+                None,
+                "",
+                Vec::new().into(),
+                HeaderMap::new(),
+            )
+            .await?;
+        let elapsed = started.elapsed();
+        let response = self.read_response(response, request_id).await;
+        match response.status_code {
+            500.. => Err(MystbinError::from_response(response).into()),
+            _ => Ok(elapsed),
+        }
+    }
+
+    /// Rotate across several tokens to spread the rate limit, e.g. for bot accounts
+    /// doing heavy archival work. The tokens aren't checked.
+    ///
+    /// Creating and getting pastes rotate. Other requests act on one account, so they
+    /// use the token the paste was created with if it was created through the pool,
+    /// or else the token set with `auth`, defaulting to the first of the pool.
+    /// Clones of this client share the rotation.
+    pub fn token_pool(
+        mut self,
+        tokens: impl IntoIterator<Item = impl Into<String>>,
+        rotation: TokenRotation,
+    ) -> Self {
+        self.tokens = TokenPool::new(tokens, rotation).map(Arc::new);
+        if self.token.is_none() {
+            self.token = self
+                .tokens
+                .as_ref()
+                .and_then(|pool| pool.token(0))
+                .map(str::to_string);
+        }
+        self
+    }
+
     /// Authenticate to mystb.in's API.
-    /// 
-    /// This method will panic if the provided token is invalid.
+    ///
+    /// This method will panic if the API rejects the provided token, use
+    /// [`try_auth`](Self::try_auth) to handle it instead. A token that can't be
+    /// checked, e.g. because the API is unreachable, is kept, the error being
+    /// returned by the requests made with it.
+    #[allow(clippy::panic)] // documented, `try_auth` is the fallible version
     pub async fn auth(mut self, token: impl Into<String>) -> Self {
         let token_str = token.into();
-        let code = Self::check_token(self.inner.clone(), token_str.clone()).await;
-        match code {
+        if let Ok(response) = self.check_token(&token_str).await {
+            if token_rejected(response.status_code) {
+                let err = Error::from(MystbinError::from_response(response));
+                panic!("the provided token is invalid: {}", err);
+            }
+        }
+        self.token = Some(format!("Bearer {}", token_str));
+        self
+    }
+
+    /// Authenticate to mystb.in's API, failing with the API's error if the token is
+    /// invalid.
+    pub async fn try_auth(mut self, token: impl Into<String>) -> Result<Self, Error> {
+        let token_str = token.into();
+        let response = self.check_token(&token_str).await?;
+        match response.status_code {
             200 => {
                 self.token = Some(format!("Bearer {}", token_str));
-                self
+                Ok(self)
             }
-            _ => panic!("the provided token is invalid"),
-        }
-    }
-
-    async fn request(&self, method: &str, url: &str, json: Value) -> MyustResponse {
-        let methods = HashMap::from([
-            ("GET", Method::GET),
-            ("PUT", Method::PUT),
-            ("DELETE", Method::DELETE),
-        ]);
-        let response = if let Some(token) = &self.token {
-            self.inner
-                .request(methods[method].clone(), url.clone())
-                .header("Authorization", token)
-                .json(&json)
-                .send()
-                .await
-                .unwrap()
-        } else {
-            self.inner
-                .request(methods[method].clone(), url.clone())
-                .json(&json)
-                .send()
-                .await
-                .unwrap()
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+
+    pub(crate) async fn request(
+        &self,
+        endpoint: &Endpoint,
+        id: Option<&str>,
+        query: &str,
+        json: impl Serialize + Send,
+    ) -> Result<MyustResponse, Error> {
+        self.request_with_headers(endpoint, id, query, json, HeaderMap::new())
+            .await
+    }
+
+    async fn request_with_headers(
+        &self,
+        endpoint: &Endpoint,
+        id: Option<&str>,
+        query: &str,
+        json: impl Serialize + Send,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error> {
+        let body = serde_json::to_vec(&json).map_err(|err| Error::Io(err.into()))?;
+        let (response, request_id) = self.send(endpoint, id, query, body.into(), headers).await?;
+        Ok(self.read_response(response, request_id).await)
+    }
+
+    async fn read_response(
+        &self,
+        response: reqwest::Response,
+        request_id: String,
+    ) -> MyustResponse {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let token = response
+            .extensions()
+            .get::<TokenIndex>()
+            .map(|index| index.0);
+        let mut url = response.url().clone();
+        redact_url(&mut url);
+        let body = response.bytes().await.unwrap_or_default();
+        intercept_response(&self.interceptors, status, &headers, &body);
+        MyustResponse {
+            token,
+            ..MyustResponse::new(status.as_u16(), headers, url, request_id, &body)
+        }
+    }
+
+    /// Send a request through the hooks, returning the response with its body unread
+    /// and the request ID.
+    async fn send(
+        &self,
+        endpoint: &Endpoint,
+        id: Option<&str>,
+        query: &str,
+        body: reqwest::Body,
+        mut headers: HeaderMap,
+    ) -> Result<(reqwest::Response, String), Error> {
+        let url = endpoint.url(&self.base_url, id, query)?;
+        let started = Instant::now();
+        let mut builder = self
+            .inner
+            .request(endpoint.method.clone(), &url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body);
+        let request_id = new_request_id();
+        let token_index = self
+            .tokens
+            .as_ref()
+            .and_then(|pool| pool.select(endpoint.user_scoped, id));
+        let token = match (&self.tokens, token_index) {
+            (Some(pool), Some(index)) => pool.token(index),
+            _ => self.token.as_deref(),
         };
+        // the request ID and the token replace the per-call headers of the same name
+        // rather than being sent alongside them
+        headers.remove(REQUEST_ID_HEADER);
+        if token.is_some() {
+            headers.remove(AUTHORIZATION);
+        }
+        builder = builder
+            .headers(headers)
+            .header(REQUEST_ID_HEADER, &request_id);
+        if let Some(token) = token {
+            builder = builder.header(AUTHORIZATION, token);
+        }
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("myust.request", method = %endpoint.method, endpoint = endpoint.path, request_id = %request_id);
+        let mut request = builder.build()?;
+        #[cfg(feature = "compression")]
+        if let Some(threshold) = self.http.compress_requests_over {
+            let compressed = request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .filter(|b| b.len() > threshold)
+                .map(gzip);
+            if let Some(compressed) = compressed {
+                *request.body_mut() = Some(compressed.into());
+                request.headers_mut().insert(
+                    reqwest::header::CONTENT_ENCODING,
+                    reqwest::header::HeaderValue::from_static("gzip"),
+                );
+            }
+        }
+        if !self.interceptors.is_empty() {
+            let mut url = request.url().clone();
+            let mut headers = take(request.headers_mut());
+            let body = request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(|b| b.to_vec());
+            intercept_request(
+                &self.interceptors,
+                request.method(),
+                &mut url,
+                &mut headers,
+                body.as_deref(),
+            );
+            *request.url_mut() = url;
+            *request.headers_mut() = headers;
+        }
+        let response = self.execute(request);
+        #[cfg(feature = "tracing")]
+        let response = tracing::Instrument::instrument(response, span.clone());
+        let mut response = response.await?;
         let status_code = response.status().as_u16();
-        let json_value = response.json::<Value>().await.ok();
-        MyustResponse {
-            json: json_value,
-            status_code,
+        if let Some(metrics) = &self.metrics {
+            metrics.on_request_complete(&endpoint.label(), status_code, started.elapsed());
+        }
+        let rate_limit = RateLimit::from_headers(response.headers());
+        if let Some(rate_limit) = rate_limit {
+            *lock(&self.rate_limit) = Some(rate_limit);
         }
+        if let (Some(pool), Some(index)) = (&self.tokens, token_index) {
+            pool.observe(index, response.status(), rate_limit);
+            response.extensions_mut().insert(TokenIndex(index));
+        }
+        let mut url = response.url().clone();
+        redact_url(&mut url);
+        *lock(&self.last_url) = Some(url);
+        let request_id = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .map(|id| id.to_string())
+            .unwrap_or(request_id);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(parent: &span, status = status_code, request_id = %request_id, "response received");
+        Ok((response, request_id))
     }
 
     /// Create a paste.
-    pub async fn create_paste<F>(&self, paste: F) -> Result<PasteResult, MystbinError>
+    ///
+    /// Content larger than [`MAX_FILE_CHARACTERS`] is rejected with
+    /// [`Error::ContentTooLarge`] before anything is sent.
+    pub async fn create_paste<F>(&self, paste: F) -> Result<PasteResult, Error>
     where
         F: FnOnce(&mut PasteBuilder) -> &mut PasteBuilder,
     {
-        let mut builder = PasteBuilder {
-            ..Default::default()
-        };
+        let mut builder = PasteBuilder::default();
         let data = paste(&mut builder);
-        let files = vec![File {
-            filename: data.filename.to_string(),
-            content: data.content.to_string(),
-        }];
-        let mut map = Map::new();
-        map.insert("files".to_string(), json!(files));
-        map.insert("password".to_string(), json!(data.password));
-        if let Some(expiry) = &data.expires {
-            if expiry.valid() {
-                if expiry.is_default() {
-                    map.insert("expires".to_string(), json!(None::<()>));
-                } else {
-                    map.insert("expires".to_string(), json!(expiry.to_rfc3339()));
-                }
-            } else {
-                let invalid = expiry.invalid_field();
-                panic!("{} can not be negative, value: {}", invalid.0, invalid.1)
+        if data.reader.is_some() {
+            if data.compressed() {
+                return Err(Error::Unsupported(
+                    "content read from a reader can't be compressed",
+                ));
             }
-        };
-        let json = Value::Object(map);
-        let response = self.request_create_paste(json).await;
+            let password = data.password.take();
+            let expires = data.expires.take();
+            let headers = take(&mut data.headers);
+            let key = data.idempotency_key.take();
+            let files = vec![StreamedFile::from(data)];
+            return self
+                .send_streamed_paste(files, password, expires, headers, key)
+                .await;
+        }
+        self.send_create_paste(data.into()).await
+    }
 
-        match response.status_code {
-            200 | 201 | 204 => {
-                let paste_result = response.json.unwrap();
-                Ok(PasteResult {
-                    created_at: paste_result["created_at"].as_str().unwrap().to_string(),
-                    expires: paste_result["expires"].as_str().map(|d| d.to_string()),
-                    files,
-                    id: paste_result["id"].as_str().unwrap().to_string(),
-                })
-            }
-            _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
-                }
-            }
+    /// Create a paste with a request built with [`PasteBuilder::build`] or
+    /// [`PastesBuilder::build`], which can be sent again, e.g. to retry it.
+    pub async fn create_paste_from_request(
+        &self,
+        request: CreatePasteRequest,
+    ) -> Result<PasteResult, Error> {
+        self.send_create_paste(request).await
+    }
+
+    /// Create a paste from everything written to stdin, e.g. for `some_command | paste`.
+    ///
+    /// The filename, expiration date and password are set with `paste`, the content
+    /// being read until EOF. Input larger than [`MAX_FILE_CHARACTERS`] is split into
+    /// several files with [`File::split`], and rejected with [`Error::TooManyFiles`]
+    /// when it needs more than [`MAX_FILES`].
+    pub async fn create_paste_from_stdin<F>(&self, paste: F) -> Result<PasteResult, Error>
+    where
+        F: FnOnce(&mut PasteBuilder) -> &mut PasteBuilder,
+    {
+        let mut content = String::new();
+        tokio::io::stdin().read_to_string(&mut content).await?;
+        let mut builder = PasteBuilder::default();
+        let data = paste(&mut builder);
+        data.content = content;
+        let mut request = CreatePasteRequest::from(data);
+        if !request.compressed() {
+            request.files = request.files.into_iter().flat_map(File::split).collect();
         }
+        self.send_create_paste(request).await
     }
 
     /// Create a paste with multiple files.
     ///
     /// If you want to provide `expires` and `password`,
     /// put it in the first file.
-    pub async fn create_multifile_paste<F>(&self, pastes: F) -> Result<PasteResult, MystbinError>
+    ///
+    /// More than [`MAX_FILES`] files are rejected with [`Error::TooManyFiles`]
+    /// before anything is sent.
+    pub async fn create_multifile_paste<F>(&self, pastes: F) -> Result<PasteResult, Error>
     where
         F: FnOnce(&mut PastesBuilder) -> &mut PastesBuilder,
     {
         let mut builder = PastesBuilder::default();
-        let data = &pastes(&mut builder).files;
-        let first_paste = &data[0];
-        let files = data
-            .iter()
-            .map(|file| File {
-                filename: file.filename.clone(),
-                content: file.content.clone(),
+        let data = pastes(&mut builder);
+        if data.files.iter().any(|file| file.reader.is_some()) {
+            if data.files.first().is_some_and(PasteBuilder::compressed) {
+                return Err(Error::Unsupported(
+                    "content read from a reader can't be compressed",
+                ));
+            }
+            let (password, expires, headers, key) = match data.files.first_mut() {
+                Some(first) => (
+                    first.password.take(),
+                    first.expires.take(),
+                    take(&mut first.headers),
+                    first.idempotency_key.take(),
+                ),
+                None => (None, None, HeaderMap::new(), None),
+            };
+            let files = data.files.iter_mut().map(StreamedFile::from).collect();
+            return self
+                .send_streamed_paste(files, password, expires, headers, key)
+                .await;
+        }
+        self.send_create_paste(data.into()).await
+    }
+
+    /// Create many pastes concurrently.
+    ///
+    /// Returns a stream yielding each request's index alongside its result, in
+    /// completion order.
+    ///
+    /// ```rust,ignore
+    /// let results: Vec<_> = client
+    ///     .bulk_create(requests, |bulk| {
+    ///         bulk.concurrency(8)
+    ///             .progress(|done, total| println!("{done}/{total}"))
+    ///     })
+    ///     .collect()
+    ///     .await;
+    /// ```
+    pub fn bulk_create<'a, F>(
+        &'a self,
+        requests: Vec<CreatePasteRequest>,
+        options: F,
+    ) -> impl Stream<Item = (usize, Result<PasteResult, Error>)> + 'a
+    where
+        F: for<'b> FnOnce(&'b mut BulkOptions<'a>) -> &'b mut BulkOptions<'a>,
+    {
+        let mut builder = BulkOptions::default();
+        options(&mut builder);
+        let BulkOptions {
+            concurrency,
+            mut progress,
+            cancellation,
+        } = builder;
+        let total = requests.len();
+        let mut done = 0;
+        let cancellation = cancellation.unwrap_or_default();
+
+        stream::iter(requests.into_iter().enumerate())
+            .map(move |(index, request)| {
+                let cancellation = cancellation.clone();
+                async move {
+                    let created = self.send_create_paste(request);
+                    (index, cancellable(&cancellation, created).await)
+                }
             })
-            .collect();
-        let mut map = Map::new();
-        map.insert("files".to_string(), json!(files));
-        map.insert("password".to_string(), json!(first_paste.password));
-        if let Some(expiry) = &first_paste.expires {
-            if expiry.valid() {
-                if expiry.is_default() {
-                    map.insert("expires".to_string(), json!(None::<()>));
-                } else {
-                    map.insert("expires".to_string(), json!(expiry.to_rfc3339()));
+            .buffer_unordered(concurrency)
+            .map(move |item| {
+                done += 1;
+                if let Some(progress) = progress.as_mut() {
+                    progress(done, total);
                 }
-            } else {
-                let invalid = expiry.invalid_field();
-                panic!("{} can not be negative, value: {}", invalid.0, invalid.1)
-            }
+                item
+            })
+    }
+
+    async fn send_create_paste(
+        &self,
+        mut request: CreatePasteRequest,
+    ) -> Result<PasteResult, Error> {
+        // checked before the content is processed, the sizes are checked once it is
+        File::validate_count(request.files.len())?;
+        request.normalize_line_endings();
+        request.apply_filters();
+        request.check_characters()?;
+        request.check_filenames()?;
+        let content_hash = self
+            .journal
+            .as_ref()
+            .map(|_| crate::journal::content_hash(&request));
+        if let Some(paste) = self
+            .find_duplicate(&request, content_hash.as_deref())
+            .await?
+        {
+            return Ok(paste);
+        }
+        #[cfg(feature = "signing")]
+        let request = match &self.signing_key {
+            Some(key) => CreatePasteRequest {
+                files: crate::signing::sign(key, request.files),
+                ..request
+            },
+            None => request,
+        };
+        let files = Cow::Borrowed(request.files.as_slice());
+        #[cfg(feature = "compression")]
+        let files = match request.compress {
+            true => Cow::Owned(files.iter().map(File::compress).collect()),
+            false => files,
+        };
+        #[cfg(feature = "encryption")]
+        let files = match &self.encryption {
+            Some(encryption) => Cow::Owned(encryption.encrypt_files(&files)?),
+            None => files,
+        };
+        File::check_limits(
+            files
+                .iter()
+                .map(|file| (file.filename.as_str(), file.content.as_str())),
+        )?;
+        let expires = request.expires.as_ref().or(self.default_expiry.as_ref());
+        if let Some(expiry) = expires {
+            expiry.validate()?;
+        }
+        let body = CreatePaste::new(&files, request.password.as_deref(), expires)?;
+        let mut headers = request.headers;
+        let key = idempotency_key(&mut headers, request.idempotency_key);
+        let response = self.request_create_paste(&body, headers).await?;
+        let line_endings = Some(request.line_endings);
+        self.paste_created(
+            response,
+            Some(request.files),
+            line_endings,
+            content_hash,
+            key,
+        )
+    }
+
+    async fn send_streamed_paste(
+        &self,
+        files: Vec<StreamedFile>,
+        password: Option<String>,
+        expires: Option<Expiry>,
+        mut headers: HeaderMap,
+        key: Option<String>,
+    ) -> Result<PasteResult, Error> {
+        #[cfg(feature = "encryption")]
+        if self.encryption.is_some() {
+            return Err(Error::Unsupported(
+                "content read from a reader can't be encrypted",
+            ));
+        }
+        #[cfg(feature = "signing")]
+        if self.signing_key.is_some() {
+            return Err(Error::Unsupported(
+                "content read from a reader can't be signed",
+            ));
+        }
+        File::validate_count(files.len())?;
+        for file in files.iter().filter(|file| file.reader.is_none()) {
+            File::validate_size(&file.filename, &file.content)?;
+        }
+        let expires = expires.or_else(|| self.default_expiry.clone());
+        if let Some(expiry) = &expires {
+            expiry.validate()?;
+        }
+        let body = StreamedBody::new(files, password.as_deref(), expires.as_ref())?;
+        let key = idempotency_key(&mut headers, key);
+        let (response, request_id) = self
+            .send(
+                &self.routes().create_paste,
+                None,
+                "",
+                reqwest::Body::wrap_stream(body.into_stream()),
+                headers,
+            )
+            .await?;
+        let response = self.read_response(response, request_id).await;
+        self.paste_created(response, None, None, None, key)
+    }
+
+    /// An identical paste recorded in the journal that still exists, see `deduplicate`.
+    ///
+    /// Fails if deduplication is enabled without a journal.
+    async fn find_duplicate(
+        &self,
+        request: &CreatePasteRequest,
+        content_hash: Option<&str>,
+    ) -> Result<Option<PasteResult>, Error> {
+        if !request.deduplicate.unwrap_or(self.deduplicate) {
+            return Ok(None);
+        }
+        let (Some(journal), Some(content_hash)) = (&self.journal, content_hash) else {
+            return Err(Error::Unsupported(
+                "pastes can't be deduplicated without a journal",
+            ));
+        };
+        let Some(entry) = journal.find(content_hash) else {
+            return Ok(None);
         };
-        let json = Value::Object(map);
-        let response = self.request_create_paste(json).await;
+        let paste = self
+            .get_paste(|p| {
+                p.id(entry.id);
+                if let Some(password) = &request.password {
+                    p.password(password);
+                }
+                p
+            })
+            .await;
+        Ok(paste.ok())
+    }
 
+    /// Build the result of a create request. Without `files`, they're taken from the
+    /// response.
+    fn paste_created(
+        &self,
+        response: MyustResponse,
+        files: Option<Vec<File>>,
+        line_endings: Option<LineEndings>,
+        content_hash: Option<String>,
+        key: String,
+    ) -> Result<PasteResult, Error> {
         match response.status_code {
             200 | 201 | 204 => {
-                let paste_result = response.json.unwrap();
-                Ok(PasteResult {
-                    created_at: paste_result["created_at"].as_str().unwrap().to_string(),
-                    expires: paste_result["expires"].as_str().map(|d| d.to_string()),
+                let mut paste_result = json_body(response.json)?;
+                let id = str_field(&paste_result, "id")?;
+                let lenient = self.lenient();
+                let files = match files {
+                    Some(files) => files,
+                    None => lenient.files(&mut paste_result, &id)?,
+                };
+                let result = PasteResult {
+                    created_at: lenient.str_field(&paste_result, "created_at", &id),
+                    expires: paste_result
+                        .get("expires")
+                        .and_then(Value::as_str)
+                        .map(|d| d.to_string()),
                     files,
-                    id: paste_result["id"].as_str().unwrap().to_string(),
-                })
-            }
-            _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
+                    id,
+                    idempotency_key: Some(key),
+                    line_endings,
+                };
+                if let (Some(pool), Some(index)) = (&self.tokens, response.token) {
+                    pool.created(&result.id, index);
+                }
+                if let Some(journal) = &self.journal {
+                    // a journal that can't be written never fails a paste
+                    let _ = journal.record_hashed(&result, content_hash);
                 }
+                self.notify(|o| o.on_paste_created(&result));
+                Ok(result)
             }
+            _ => Err(MystbinError::from_response(response).into()),
         }
     }
 
     /// Get a paste.
-    pub async fn get_paste<F>(&self, paste: F) -> Result<PasteResult, MystbinError>
+    pub async fn get_paste<F>(&self, paste: F) -> Result<PasteResult, Error>
     where
         F: FnOnce(&mut GetPasteBuilder) -> &mut GetPasteBuilder,
     {
         let mut builder = GetPasteBuilder::default();
-        let data = paste(&mut builder);
+        let request = paste(&mut builder).build()?;
+        self.get_paste_from_request(&request).await
+    }
+
+    /// Get a paste with a request built with [`GetPasteBuilder::build`].
+    pub async fn get_paste_from_request(
+        &self,
+        request: &GetPasteRequest,
+    ) -> Result<PasteResult, Error> {
+        let paste = self.fetch_paste(request).await?;
+        request.select(paste)
+    }
+
+    /// Get a paste, asking for its password with `prompt` if it has one.
+    ///
+    /// `prompt` is called with what the API tells about the paste, and the paste is
+    /// fetched again once with the password it returns. If it returns `None`, the
+    /// [`PasteRequiresPassword`](Error::PasteRequiresPassword) error is returned.
+    pub async fn get_paste_with_password_prompt<P>(
+        &self,
+        id: &str,
+        prompt: P,
+    ) -> Result<PasteResult, Error>
+    where
+        P: FnOnce(&LockedPaste) -> Option<String> + Send,
+    {
+        match self.get_paste(|p| p.id(id)).await {
+            Err(Error::PasteRequiresPassword(paste)) => match prompt(&paste) {
+                Some(password) => self.get_paste(|p| p.id(id).password(password)).await,
+                None => Err(Error::PasteRequiresPassword(paste)),
+            },
+            result => result,
+        }
+    }
+
+    /// Get a whole paste, from the cache when possible.
+    async fn fetch_paste(&self, data: &GetPasteRequest) -> Result<PasteResult, Error> {
+        let id = data.id.as_str().to_string();
+        if let Some(paste) = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(&id, data.password.as_deref()))
+        {
+            return Ok(paste);
+        }
+        let key = (id.clone(), data.password.clone());
+        let cached = self
+            .validators
+            .as_ref()
+            .and_then(|validators| lock(validators).get(&key).cloned());
         let response = self
-            .request_get_paste(data.id.clone(), data.password.clone())
-            .await;
-        match response.status_code {
-            200 => {
-                let paste_result = response.json.unwrap();
-                let files = paste_result["files"]
-                    .as_array()
-                    .unwrap()
-                    .iter()
-                    .map(|x| File {
-                        filename: x.get("filename").unwrap().to_string(),
-                        content: x.get("content").unwrap().to_string(),
-                    })
-                    .collect::<Vec<File>>();
-                Ok(PasteResult {
-                    created_at: paste_result["created_at"].as_str().unwrap().to_string(),
-                    expires: paste_result["expires"].as_str().map(|d| d.to_string()),
+            .request_get_paste(
+                id.clone(),
+                data.password.clone(),
+                cached.as_ref().map(|(etag, _)| etag.clone()),
+                data.headers.clone(),
+            )
+            .await?;
+        match (response.status_code, cached) {
+            (304, Some((_, paste))) => Ok(paste),
+            (200, _) => {
+                let mut paste_result = json_body(response.json)?;
+                let lenient = self.lenient();
+                let files = lenient.files(&mut paste_result, &id)?;
+                #[cfg(feature = "encryption")]
+                let files = match &self.encryption {
+                    Some(encryption) => encryption.decrypt_files(files)?,
+                    None => files,
+                };
+                #[cfg(feature = "compression")]
+                let files: Vec<File> = files
+                    .into_iter()
+                    .map(|file| file.decompress().unwrap_or(file))
+                    .collect();
+                let result = PasteResult {
+                    created_at: lenient.str_field(&paste_result, "created_at", &id),
+                    expires: paste_result
+                        .get("expires")
+                        .and_then(Value::as_str)
+                        .map(|d| d.to_string()),
                     files,
-                    id: data.id.clone(),
-                })
+                    id: id.clone(),
+                    idempotency_key: None,
+                    line_endings: None,
+                };
+                if let Some(validators) = &self.validators {
+                    let mut validators = lock(validators);
+                    match response.headers.get(ETAG).and_then(|e| e.to_str().ok()) {
+                        Some(etag) => validators.insert(key, (etag.to_string(), result.clone())),
+                        None => validators.remove(&key),
+                    };
+                }
+                if let Some(cache) = &self.cache {
+                    cache.insert(data.password.as_deref(), result.clone());
+                }
+                Ok(result)
             }
             _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
+                if let Some(validators) = &self.validators {
+                    lock(validators).remove(&key);
                 }
+                Err(Error::from_paste_response(
+                    response,
+                    &id,
+                    data.password.as_deref(),
+                ))
             }
         }
     }
 
-    /// Delete a paste.
-    pub async fn delete_paste(&self, paste_id: &str) -> Result<DeleteResult, MystbinError> {
-        let response = self.request_delete_paste(paste_id).await;
+    /// Get a paste and check that it was signed by the holder of `key` and not
+    /// changed since.
+    ///
+    /// The signature file is removed from the result.
+    #[cfg(feature = "signing")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signing")))]
+    pub async fn get_paste_verified<F>(
+        &self,
+        paste: F,
+        key: &crate::signing::VerifyingKey,
+    ) -> Result<PasteResult, Error>
+    where
+        F: FnOnce(&mut GetPasteBuilder) -> &mut GetPasteBuilder,
+    {
+        let mut builder = GetPasteBuilder::default();
+        let data = paste(&mut builder).build()?;
+        let mut result = self.fetch_paste(&data).await?;
+        result.files = crate::signing::verify(key, result.files)?;
+        data.select(result)
+    }
+
+    /// Stream a paste's raw response body into `writer` without buffering it in
+    /// memory, returning the number of bytes written.
+    ///
+    /// Meant for very large pastes. Interceptors see the status and headers of a
+    /// successful download, but not its body.
+    /// The file and lines selected on the builder are ignored.
+    pub async fn download_paste<F, W>(&self, paste: F, writer: &mut W) -> Result<u64, Error>
+    where
+        F: FnOnce(&mut GetPasteBuilder) -> &mut GetPasteBuilder,
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let mut builder = GetPasteBuilder::default();
+        let data = paste(&mut builder).build()?;
+        let id = data.id;
+        let query = password_query(data.password.as_deref());
+        let (mut response, request_id) = self
+            .send(
+                &self.routes().get_paste,
+                Some(id.as_str()),
+                &query,
+                "{}".into(),
+                data.headers.clone(),
+            )
+            .await?;
+        if response.status() != StatusCode::OK {
+            let response = self.read_response(response, request_id).await;
+            return Err(MystbinError::from_response(response).into());
+        }
+        intercept_response(
+            &self.interceptors,
+            response.status(),
+            response.headers(),
+            &[],
+        );
+        let mut written = 0;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| io::Error::other(redact_error(err)))?
+        {
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    /// Delete a paste, by ID or URL, see [`IntoPasteId`].
+    pub async fn delete_paste(&self, paste_id: impl IntoPasteId) -> Result<DeleteResult, Error> {
+        let paste_id = paste_id.into_paste_id()?;
+        let response = self.request_delete_paste(&paste_id).await?;
         match response.status_code {
-            200 => Ok(DeleteResult {
-                succeeded: Some(vec![paste_id.to_string()]),
-                ..Default::default()
-            }),
-            _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
-                }
+            200 => {
+                self.paste_deleted(paste_id.as_str());
+                Ok(DeleteResult {
+                    succeeded: Some(vec![paste_id.to_string()]),
+                    ..Default::default()
+                })
             }
+            _ => Err(MystbinError::from_response(response).into()),
         }
     }
 
     /// Delete pastes.
-    pub async fn delete_pastes(&self, paste_ids: Vec<&str>) -> Result<DeleteResult, MystbinError> {
+    ///
+    /// The IDs are split into batches the API accepts, which are sent concurrently
+    /// and merged into one result. A batch the API rejects has its IDs added to
+    /// `failed`; the error is only returned if every batch was rejected.
+    ///
+    /// Every ID is checked before anything is sent, see [`IntoPasteId`].
+    pub async fn delete_pastes(
+        &self,
+        paste_ids: Vec<impl IntoPasteId>,
+    ) -> Result<DeleteResult, Error> {
+        let paste_ids = paste_ids
+            .into_iter()
+            .map(|id| id.into_paste_id().map(PasteId::into_string))
+            .collect::<Result<Vec<_>, _>>()?;
+        let batches: Vec<Result<DeleteResult, (Vec<String>, Error)>> =
+            stream::iter(paste_ids.chunks(DELETE_BATCH_LIMIT))
+                .map(|batch| async move {
+                    self.delete_pastes_batch(batch)
+                        .await
+                        .map_err(|err| (batch.to_vec(), err))
+                })
+                .buffered(DELETE_BATCH_CONCURRENCY)
+                .collect()
+                .await;
+
+        let mut succeeded = vec![];
+        let mut failed = vec![];
+        let mut first_error = None;
+        let batch_count = batches.len();
+        let mut error_count = 0;
+        for batch in batches {
+            match batch {
+                Ok(deleted) => {
+                    succeeded.extend(deleted.succeeded.unwrap_or_default());
+                    failed.extend(deleted.failed.unwrap_or_default());
+                }
+                Err((ids, err)) => {
+                    error_count += 1;
+                    failed.extend(ids);
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        match first_error {
+            Some(err) if error_count == batch_count => Err(err),
+            _ => Ok(DeleteResult {
+                succeeded: Some(succeeded),
+                failed: Some(failed),
+            }),
+        }
+    }
+
+    async fn delete_pastes_batch(&self, paste_ids: &[String]) -> Result<DeleteResult, Error> {
         let json = json!({ "pastes": paste_ids });
-        let response = self.request_delete_pastes(json).await;
+        let response = self.request_delete_pastes(json).await?;
         match response.status_code {
             200 => {
-                let data = response.json.unwrap();
+                let data = json_body(response.json)?;
+                let ids = |key: &str| -> Result<Vec<String>, Error> {
+                    Ok(array_field(&data, key)?
+                        .iter()
+                        .filter_map(|p| p.as_str().map(|p| p.to_string()))
+                        .collect())
+                };
+                let succeeded = ids("succeeded")?;
+                for paste_id in &succeeded {
+                    self.paste_deleted(paste_id);
+                }
                 Ok(DeleteResult {
-                    succeeded: Some(
-                        data["succeeded"]
-                            .as_array()
-                            .unwrap()
-                            .iter()
-                            .map(|p| p.to_string())
-                            .collect(),
-                    ),
-                    failed: Some(
-                        data["failed"]
-                            .as_array()
-                            .unwrap()
-                            .iter()
-                            .map(|p| p.to_string())
-                            .collect(),
-                    ),
+                    succeeded: Some(succeeded),
+                    failed: Some(ids("failed")?),
                 })
             }
-            _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
-                }
-            }
+            _ => Err(MystbinError::from_response(response).into()),
         }
     }
 
     /// Get the authenticated user pastes.
-    pub async fn get_user_pastes<F>(&self, options: F) -> Result<Vec<UserPaste>, MystbinError>
+    pub async fn get_user_pastes<F>(&self, options: F) -> Result<Vec<UserPaste>, Error>
+    where
+        F: FnOnce(&mut UserPastesOptions) -> &mut UserPastesOptions,
+    {
+        self.get_user_pastes_page(options)
+            .await
+            .map(|page| page.pastes)
+    }
+
+    /// Get a page of the authenticated user pastes, with the total number of pastes
+    /// if the API reports it.
+    pub async fn get_user_pastes_page<F>(&self, options: F) -> Result<UserPastesPage, Error>
     where
         F: FnOnce(&mut UserPastesOptions) -> &mut UserPastesOptions,
     {
@@ -351,186 +1243,448 @@ impl Client {
             "limit": data.limit,
             "page": data.page
         });
-        let response = self.request_get_user_pastes(json).await;
+        let response = self
+            .request_get_user_pastes(json, take(&mut data.headers))
+            .await?;
         match response.status_code {
             200 => {
-                let results = response.json.unwrap();
-                let pastes = results["pastes"]
-                    .as_array()
-                    .unwrap()
+                let results = json_body(response.json)?;
+                let lenient = self.lenient();
+                let pastes = array_field(&results, "pastes")?
                     .iter()
-                    .map(|result| UserPaste {
-                        created_at: result["created_at"].as_str().unwrap().to_string(),
-                        expires: result["expires"].as_str().map(|d| d.to_string()),
-                        id: result["id"].as_str().unwrap().to_string(),
+                    .filter_map(|result| {
+                        let id = lenient.id(result)?;
+                        Some(UserPaste {
+                            created_at: lenient.str_field(result, "created_at", &id),
+                            expires: result["expires"].as_str().map(|d| d.to_string()),
+                            id,
+                        })
                     })
                     .collect();
-                Ok(pastes)
+                Ok(UserPastesPage {
+                    pastes,
+                    page: data.page,
+                    total: results
+                        .get("total")
+                        .and_then(Value::as_u64)
+                        .map(|total| total as usize),
+                })
             }
-            _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+
+    /// The number of pastes of the authenticated user.
+    ///
+    /// The total reported by the API is used if there's one, or else the pastes are
+    /// counted page by page.
+    pub async fn count_user_pastes(&self) -> Result<usize, Error> {
+        let mut count = 0;
+        for page in 1.. {
+            let listed = self
+                .get_user_pastes_page(|p| p.limit(USER_PASTES_PAGE_SIZE).page(page))
+                .await?;
+            if let Some(total) = listed.total {
+                return Ok(total);
+            }
+            count += listed.pastes.len();
+            if listed.pastes.len() < USER_PASTES_PAGE_SIZE as usize {
+                break;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Every paste of the authenticated user, fetched page by page.
+    pub(crate) async fn all_user_pastes(&self) -> Result<Vec<UserPaste>, Error> {
+        self.get_all_user_pastes(&CancellationToken::new()).await
+    }
+
+    /// Get every paste of the authenticated user, fetching page by page until `cancel`
+    /// is cancelled, which fails with [`Error::Cancelled`].
+    pub async fn get_all_user_pastes(
+        &self,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<UserPaste>, Error> {
+        let mut pastes = vec![];
+        for page in 1.. {
+            let listed = cancellable(
+                cancel,
+                self.get_user_pastes(|p| p.limit(USER_PASTES_PAGE_SIZE).page(page)),
+            )
+            .await?;
+            let last = listed.len() < USER_PASTES_PAGE_SIZE as usize;
+            pastes.extend(listed);
+            if last {
+                break;
+            }
+        }
+        Ok(pastes)
+    }
+
+    /// The pastes of the authenticated user that expire within `window` from now,
+    /// soonest first.
+    pub async fn expiring_within(&self, window: Duration) -> Result<Vec<UserPaste>, Error> {
+        let now = SystemTime::now();
+        // a window too long to add to now has no upper bound
+        let until = now.checked_add(window);
+        let mut expiring: Vec<(SystemTime, UserPaste)> = self
+            .all_user_pastes()
+            .await?
+            .into_iter()
+            .filter_map(|paste| {
+                let expires = parse_timestamp(paste.expires.as_deref()?)?;
+                (expires > now && until.is_none_or(|until| expires <= until))
+                    .then_some((expires, paste))
+            })
+            .collect();
+        expiring.sort_by_key(|(expires, _)| *expires);
+        Ok(expiring.into_iter().map(|(_, paste)| paste).collect())
+    }
+
+    /// Usage statistics of the authenticated user's pastes.
+    ///
+    /// Pastes are listed page by page, then fetched a few at a time to measure
+    /// their files.
+    pub async fn account_stats(&self) -> Result<crate::report::AccountStats, Error> {
+        let pastes = self.all_user_pastes().await?;
+        let mut stats = crate::report::AccountStats {
+            pastes: pastes.len(),
+            ..Default::default()
+        };
+        for paste in &pastes {
+            if parse_timestamp(&paste.created_at).is_some() {
+                let month = paste.created_at.get(..7).unwrap_or_default();
+                *stats.per_month.entry(month.to_string()).or_default() += 1;
+            }
+        }
+        stats.soonest_expiry = pastes
+            .iter()
+            .filter_map(|paste| Some((parse_timestamp(paste.expires.as_deref()?)?, paste)))
+            .min_by_key(|(expires, _)| *expires)
+            .map(|(_, paste)| paste.clone());
+
+        let fetched: Vec<_> = stream::iter(&pastes)
+            .map(|paste| async move { (&paste.id, self.get_paste(|p| p.id(&paste.id)).await) })
+            .buffered(4)
+            .collect()
+            .await;
+        for (id, result) in fetched {
+            match result {
+                Ok(paste) => {
+                    stats.files += paste.files.len();
+                    stats.total_size += paste.files.iter().map(File::len_bytes).sum::<usize>();
                 }
+                Err(_) => stats.unfetched.push(id.clone()),
             }
         }
+        Ok(stats)
+    }
+
+    /// Get the authenticated user, with their settings and subscription status.
+    pub async fn current_user(&self) -> Result<CurrentUser, Error> {
+        let response = self
+            .request(&self.routes().current_user, None, "", json!({}))
+            .await?;
+        match response.status_code {
+            200 => serde_json::from_value(json_body(response.json)?)
+                .map_err(|err| Error::InvalidResponse(err.to_string())),
+            _ => Err(MystbinError::from_response(response).into()),
+        }
     }
 
     /// Add a paste to the authenticated user's bookmark.
-    pub async fn create_bookmark(&self, paste_id: &str) -> Result<(), MystbinError> {
-        let json = json!({ "paste_id": paste_id });
-        let response = self.request_create_bookmark(json).await;
+    pub async fn create_bookmark(&self, paste_id: impl IntoPasteId) -> Result<(), Error> {
+        let paste_id = paste_id.into_paste_id()?;
+        let json = json!({ "paste_id": paste_id.as_str() });
+        let response = self.request_create_bookmark(json).await?;
         match response.status_code {
-            201 => Ok(()),
-            _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
-                }
+            201 => {
+                self.notify(|o| o.on_bookmark_changed(paste_id.as_str(), true));
+                Ok(())
             }
+            _ => Err(MystbinError::from_response(response).into()),
         }
     }
 
     /// Delete a paste from the authenticated user's bookmark.
-    pub async fn delete_bookmark(&self, paste_id: &str) -> Result<(), MystbinError> {
-        let json = json!({ "paste_id": paste_id });
-        let response = self.request_delete_bookmark(json).await;
+    pub async fn delete_bookmark(&self, paste_id: impl IntoPasteId) -> Result<(), Error> {
+        let paste_id = paste_id.into_paste_id()?;
+        let json = json!({ "paste_id": paste_id.as_str() });
+        let response = self.request_delete_bookmark(json).await?;
         match response.status_code {
-            204 => Ok(()),
-            _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
-                }
+            204 => {
+                self.notify(|o| o.on_bookmark_changed(paste_id.as_str(), false));
+                Ok(())
             }
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+
+    /// Bookmark the paste if it isn't bookmarked, or else remove its bookmark.
+    ///
+    /// Returns whether the paste is bookmarked now. The bookmark is removed first,
+    /// and only created if there was none, so there's no window between checking and
+    /// changing it.
+    pub async fn toggle_bookmark(&self, paste_id: impl IntoPasteId) -> Result<bool, Error> {
+        let paste_id = paste_id.into_paste_id()?;
+        match self.delete_bookmark(&paste_id).await {
+            Ok(()) => Ok(false),
+            Err(err) if err.is_not_found() => self.create_bookmark(&paste_id).await.map(|()| true),
+            Err(err) => Err(err),
         }
     }
 
     /// Get the authenticated user's bookmarks.
-    pub async fn get_user_bookmarks(&self) -> Result<Vec<UserPaste>, MystbinError> {
-        let response = self.request_get_user_bookmarks().await;
+    pub async fn get_user_bookmarks(&self) -> Result<Vec<Bookmark>, Error> {
+        let response = self.request_get_user_bookmarks().await?;
         match response.status_code {
             200 => {
-                let data = response.json.unwrap();
-                let bookmarks = data["bookmarks"]
-                    .as_array()
-                    .unwrap()
+                let data = json_body(response.json)?;
+                let lenient = self.lenient();
+                Ok(array_field(&data, "bookmarks")?
                     .iter()
-                    .map(|paste| UserPaste {
-                        created_at: paste["created_at"].as_str().unwrap().to_string(),
-                        expires: paste["expires"].as_str().map(|d| d.to_string()),
-                        id: paste["id"].as_str().unwrap().to_string(),
+                    .filter_map(|paste| {
+                        let id = lenient.id(paste)?;
+                        Some(Bookmark {
+                            created_at: lenient.str_field(paste, "created_at", &id),
+                            expires: paste["expires"].as_str().map(|d| d.to_string()),
+                            id,
+                            bookmarked_at: paste["bookmarked_at"].as_str().map(|d| d.to_string()),
+                            origin: paste["origin"].as_str().map(|o| o.to_string()),
+                        })
                     })
-                    .collect();
-                Ok(bookmarks)
+                    .collect())
             }
-            _ => {
-                let json = response.json;
-                if let Some(data) = json {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        error: data["error"].as_str().map(|s| s.to_string()),
-                        notice: data["notice"].as_str().map(|s| s.to_string()),
-                        detail: data["detail"]
-                            .as_object()
-                            .map(|m| m.clone().into_iter().collect()),
-                    })
-                } else {
-                    Err(MystbinError {
-                        code: response.status_code,
-                        ..Default::default()
-                    })
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+
+    /// Whether the authenticated user bookmarked the paste `paste_id`.
+    ///
+    /// The API has no query for a single bookmark, so the bookmarks are listed in
+    /// one request and only their IDs are looked at.
+    pub async fn has_bookmark(&self, paste_id: impl IntoPasteId) -> Result<bool, Error> {
+        let paste_id = paste_id.into_paste_id()?;
+        let response = self.request_get_user_bookmarks().await?;
+        match response.status_code {
+            200 => {
+                let data = json_body(response.json)?;
+                Ok(array_field(&data, "bookmarks")?
+                    .iter()
+                    .any(|paste| paste["id"].as_str() == Some(paste_id.as_str())))
+            }
+            _ => Err(MystbinError::from_response(response).into()),
+        }
+    }
+
+    /// Remove the bookmarks of pastes that expired or were deleted.
+    ///
+    /// A paste is dead when its expiration date has passed, or when fetching it
+    /// fails with a 404 status. Pastes with a password are alive. Every paste is
+    /// checked before any bookmark is removed, so another error stops the pruning
+    /// without changing anything.
+    pub async fn prune_bookmarks(&self) -> Result<PruneReport, Error> {
+        let now = SystemTime::now();
+        let checks: Vec<_> = stream::iter(self.get_user_bookmarks().await?)
+            .map(|bookmark| async move {
+                let expired = bookmark
+                    .expires
+                    .as_deref()
+                    .and_then(parse_timestamp)
+                    .is_some_and(|expires| expires <= now);
+                if expired {
+                    return Ok((bookmark.id, true));
+                }
+                match self.get_paste(|p| p.id(bookmark.id.as_str())).await {
+                    Ok(_) | Err(Error::PasteRequiresPassword(_)) => Ok((bookmark.id, false)),
+                    Err(err) if err.is_not_found() => Ok((bookmark.id, true)),
+                    Err(err) => Err(err),
+                }
+            })
+            .buffered(4)
+            .collect()
+            .await;
+
+        let mut report = PruneReport::default();
+        for check in checks {
+            match check? {
+                (id, true) => report.pruned.push(id),
+                (id, false) => report.kept.push(id),
+            }
+        }
+        let mut failed = Vec::new();
+        for id in &report.pruned {
+            if let Err(err) = self.delete_bookmark(id).await {
+                if !err.is_not_found() {
+                    failed.push(id.clone());
                 }
             }
         }
+        report.pruned.retain(|id| !failed.contains(id));
+        report.failed = failed;
+        Ok(report)
+    }
+
+    /// Get the authenticated user's bookmarked pastes with their files, in the order
+    /// of [`get_user_bookmarks`](Self::get_user_bookmarks).
+    ///
+    /// A paste with a password fails with [`Error::PasteRequiresPassword`] unless
+    /// [`skip_protected`](BookmarkedPastesOptions::skip_protected) is set.
+    pub async fn get_bookmarked_pastes<F>(&self, options: F) -> Result<Vec<PasteResult>, Error>
+    where
+        F: FnOnce(&mut BookmarkedPastesOptions) -> &mut BookmarkedPastesOptions,
+    {
+        let mut builder = BookmarkedPastesOptions::default();
+        let options = options(&mut builder);
+        let skip_protected = options.skip_protected;
+        let bookmarks = self.get_user_bookmarks().await?;
+        let pastes: Vec<_> = stream::iter(bookmarks)
+            .map(|bookmark| self.get_paste(move |p| p.id(bookmark.id)))
+            .buffered(options.concurrency)
+            .collect()
+            .await;
+        pastes
+            .into_iter()
+            .filter(|paste| {
+                !(skip_protected && matches!(paste, Err(Error::PasteRequiresPassword(_))))
+            })
+            .collect()
     }
 }
 
 #[async_trait]
 impl ClientPaste for Client {
-    async fn request_create_paste(&self, json: Value) -> MyustResponse {
-        self.request("PUT", PASTE_ENDPOINT, json).await
+    async fn request_create_paste(
+        &self,
+        body: &CreatePaste<'_>,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error> {
+        self.request_with_headers(&self.routes().create_paste, None, "", body, headers)
+            .await
     }
 
-    async fn request_delete_paste(&self, paste_id: &str) -> MyustResponse {
+    async fn request_delete_paste(&self, paste_id: &PasteId) -> Result<MyustResponse, Error> {
         self.request(
-            "DELETE",
-            &format!("{}/{}", PASTE_ENDPOINT, paste_id),
+            &self.routes().delete_paste,
+            Some(paste_id.as_str()),
+            "",
             json!({}),
         )
         .await
     }
 
-    async fn request_delete_pastes(&self, json: Value) -> MyustResponse {
-        self.request("DELETE", PASTE_ENDPOINT, json).await
+    async fn request_delete_pastes(&self, json: Value) -> Result<MyustResponse, Error> {
+        self.request(&self.routes().delete_pastes, None, "", json)
+            .await
     }
 
-    async fn request_get_paste(&self, paste_id: String, password: Option<String>) -> MyustResponse {
-        let url = if password.is_some() {
-            format!(
-                "{}/{}?password={}",
-                PASTE_ENDPOINT,
-                paste_id,
-                password.unwrap()
-            )
-        } else {
-            format!("{}/{}", PASTE_ENDPOINT, paste_id)
-        };
-        self.request("GET", &url, json!({})).await
+    async fn request_get_paste(
+        &self,
+        paste_id: String,
+        password: Option<String>,
+        etag: Option<String>,
+        mut headers: HeaderMap,
+    ) -> Result<MyustResponse, Error> {
+        let query = password_query(password.as_deref());
+        if let Some(value) = etag.and_then(|etag| HeaderValue::from_str(&etag).ok()) {
+            headers.insert(IF_NONE_MATCH, value);
+        }
+        self.request_with_headers(
+            &self.routes().get_paste,
+            Some(&paste_id),
+            &query,
+            json!({}),
+            headers,
+        )
+        .await
     }
 
-    async fn request_get_user_pastes(&self, json: Value) -> MyustResponse {
-        self.request("GET", USER_PASTES_ENDPOINT, json).await
+    async fn request_get_user_pastes(
+        &self,
+        json: Value,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error> {
+        self.request_with_headers(&self.routes().user_pastes, None, "", json, headers)
+            .await
     }
 }
 
 #[async_trait]
 impl ClientBookmark for Client {
-    async fn request_create_bookmark(&self, json: Value) -> MyustResponse {
-        self.request("PUT", BOOKMARK_ENDPOINT, json).await
+    async fn request_create_bookmark(&self, json: Value) -> Result<MyustResponse, Error> {
+        self.request(&self.routes().create_bookmark, None, "", json)
+            .await
+    }
+
+    async fn request_delete_bookmark(&self, json: Value) -> Result<MyustResponse, Error> {
+        self.request(&self.routes().delete_bookmark, None, "", json)
+            .await
+    }
+
+    async fn request_get_user_bookmarks(&self) -> Result<MyustResponse, Error> {
+        self.request(&self.routes().bookmarks, None, "", json!({}))
+            .await
+    }
+}
+
+#[async_trait]
+impl PasteProvider for Client {
+    fn name(&self) -> &str {
+        "mystbin"
     }
 
-    async fn request_delete_bookmark(&self, json: Value) -> MyustResponse {
-        self.request("DELETE", BOOKMARK_ENDPOINT, json).await
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        self.send_create_paste(request).await
     }
 
-    async fn request_get_user_bookmarks(&self) -> MyustResponse {
-        self.request("GET", BOOKMARK_ENDPOINT, json!({})).await
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error> {
+        self.get_paste(|p| {
+            p.id(id);
+            match password {
+                Some(password) => p.password(password),
+                None => p,
+            }
+        })
+        .await
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        self.delete_paste(id).await?;
+        Ok(())
+    }
+
+    fn url_for(&self, id: &str) -> String {
+        self.paste_url(id)
+    }
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("web_url", &self.web_url)
+            .field("api_version", &self.api_version)
+            .field("default_expiry", &self.default_expiry)
+            .field("journal", &self.journal)
+            .field("deduplicate", &self.deduplicate)
+            .field("http", &self.http)
+            .field("authenticated", &self.token.is_some())
+            .field("metrics", &self.metrics.is_some())
+            .field("parse_warnings", &self.parse_warnings.is_some())
+            .field("interceptors", &self.interceptors.len())
+            .field("observers", &self.observers.len())
+            .field("conditional_requests", &self.validators.is_some())
+            .field("cache", &self.cache.is_some())
+            .field("limiter", &self.limiter)
+            .field("token_pool", &self.tokens)
+            .field("middleware", &self.has_middleware())
+            .finish()
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
     }
 }
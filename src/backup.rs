@@ -0,0 +1,238 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "backup")))]
+
+//! Backing up every paste and bookmark of an account, and restoring them, e.g. to
+//! move to another account or instance.
+//!
+//! A backup is a directory laid out for [`import`](crate::import): one directory per
+//! paste, named by its ID, and a manifest with the expiration dates and bookmarks. It
+//! can be restored from an archive of the directory too.
+//!
+//! ```rust,no_run
+//! use myust::{backup::ConflictPolicy, Client};
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let old = Client::new().auth("OLD_TOKEN").await;
+//! old.backup_account("mystbin-backup").await?;
+//!
+//! let new = Client::new().auth("NEW_TOKEN").await;
+//! let restored = new
+//!     .restore_account("mystbin-backup", |restore| {
+//!         restore.conflicts(ConflictPolicy::Duplicate)
+//!     })
+//!     .await?;
+//! for (old_id, new_id) in &restored.ids {
+//!     println!("{old_id} -> {new_id}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Pastes with a password can't be read back, so they're reported as failures of the
+//! backup. Pastes that have expired since the backup are reported as failures of the
+//! restore.
+
+use std::{collections::HashMap, fmt, fs, io, path::Path};
+
+use crate::{
+    import::{plan, read_tree, Manifest, ManifestPaste, MANIFEST_FILENAME},
+    utils::cancellable,
+    CancellationToken, Client, CreatePasteRequest, Error, File, PasteResult,
+};
+
+/// What to do when a paste of the backup still exists on the account.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the existing paste, mapping its ID to itself.
+    #[default]
+    Skip,
+    /// Create a copy alongside the existing paste.
+    Duplicate,
+    /// Delete the existing paste and create it again.
+    Replace,
+}
+
+/// The options of [`Client::restore_account`].
+#[derive(Default)]
+pub struct RestoreOptions<'a> {
+    pub conflicts: ConflictPolicy,
+    pub progress: Option<Box<dyn FnMut(usize, usize) + Send + 'a>>,
+}
+
+impl<'a> RestoreOptions<'a> {
+    /// What to do with pastes that still exist. Defaults to [`ConflictPolicy::Skip`].
+    pub fn conflicts(&mut self, conflicts: ConflictPolicy) -> &mut Self {
+        self.conflicts = conflicts;
+        self
+    }
+
+    /// (optional) A callback receiving the number of restored pastes and the total
+    /// after each paste.
+    pub fn progress(&mut self, progress: impl FnMut(usize, usize) + Send + 'a) -> &mut Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+}
+
+impl fmt::Debug for RestoreOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RestoreOptions")
+            .field("conflicts", &self.conflicts)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// The outcome of [`Client::backup_account`].
+#[derive(Debug, Default)]
+pub struct BackupResult {
+    /// The IDs of the pastes written to the backup.
+    pub pastes: Vec<String>,
+    /// The IDs of the bookmarked pastes.
+    pub bookmarks: Vec<String>,
+    /// The IDs of the pastes that couldn't be backed up, with the reason.
+    pub failed: Vec<(String, Error)>,
+}
+
+/// The outcome of [`Client::restore_account`].
+#[derive(Debug, Default)]
+pub struct RestoreResult {
+    /// The ID of every restored or kept paste, by its ID in the backup.
+    pub ids: HashMap<String, String>,
+    /// The IDs of the pastes kept as they were by [`ConflictPolicy::Skip`].
+    pub skipped: Vec<String>,
+    /// The IDs of the bookmarks added back, after remapping.
+    pub bookmarks: Vec<String>,
+    /// The IDs in the backup of the pastes and bookmarks that couldn't be restored,
+    /// with the reason.
+    pub failed: Vec<(String, Error)>,
+}
+
+impl Client {
+    /// Write every paste and bookmark of the authenticated user to a directory.
+    ///
+    /// A paste that can't be read is reported without stopping the others. Listing
+    /// the pastes or writing to the directory failing is returned as an error.
+    pub async fn backup_account(&self, path: impl AsRef<Path>) -> Result<BackupResult, Error> {
+        self.backup_account_cancellable(path, &CancellationToken::new())
+            .await
+    }
+
+    /// Like [`backup_account`](Self::backup_account), stopping with
+    /// [`Error::Cancelled`] once `cancel` is cancelled.
+    ///
+    /// The pastes written until then are left in the directory, without a manifest.
+    pub async fn backup_account_cancellable(
+        &self,
+        path: impl AsRef<Path>,
+        cancel: &CancellationToken,
+    ) -> Result<BackupResult, Error> {
+        let root = path.as_ref();
+        fs::create_dir_all(root)?;
+        let mut result = BackupResult::default();
+        let mut manifest = Manifest::default();
+        for paste in self.get_all_user_pastes(cancel).await? {
+            let fetched = cancellable(cancel, self.get_paste(|p| p.id(&paste.id))).await;
+            if let Err(Error::Cancelled) = fetched {
+                return Err(Error::Cancelled);
+            }
+            match fetched.and_then(|fetched| write_paste(root, &fetched)) {
+                Ok(()) => {
+                    manifest.pastes.push(ManifestPaste {
+                        path: paste.id.clone(),
+                        expires_at: paste.expires,
+                        ..Default::default()
+                    });
+                    result.pastes.push(paste.id);
+                }
+                Err(err) => result.failed.push((paste.id, err)),
+            }
+        }
+        manifest.bookmarks = cancellable(cancel, self.get_user_bookmarks())
+            .await?
+            .into_iter()
+            .map(|bookmark| bookmark.id)
+            .collect();
+        result.bookmarks = manifest.bookmarks.clone();
+        let manifest = serde_json::to_vec_pretty(&manifest).map_err(io::Error::other)?;
+        fs::write(root.join(MANIFEST_FILENAME), manifest)?;
+        Ok(result)
+    }
+
+    /// Recreate the pastes and bookmarks of a backup made with
+    /// [`backup_account`](Self::backup_account), from its directory or an archive of it.
+    ///
+    /// Bookmarks of restored pastes point to their new ID. A paste or bookmark that
+    /// can't be restored is reported without stopping the others.
+    pub async fn restore_account<'a, F>(
+        &self,
+        path: impl AsRef<Path>,
+        options: F,
+    ) -> Result<RestoreResult, Error>
+    where
+        F: for<'b> FnOnce(&'b mut RestoreOptions<'a>) -> &'b mut RestoreOptions<'a>,
+    {
+        let mut builder = RestoreOptions::default();
+        options(&mut builder);
+        let RestoreOptions {
+            conflicts,
+            mut progress,
+        } = builder;
+        let (pastes, manifest) = plan(read_tree(path.as_ref())?)?;
+        let existing: Vec<String> = match conflicts {
+            ConflictPolicy::Duplicate => vec![],
+            _ => self
+                .all_user_pastes()
+                .await?
+                .into_iter()
+                .map(|paste| paste.id)
+                .collect(),
+        };
+        let total = pastes.len();
+        let mut result = RestoreResult::default();
+        for (done, (id, request)) in pastes.into_iter().enumerate() {
+            let restored = match (existing.contains(&id), conflicts) {
+                (true, ConflictPolicy::Skip) => {
+                    result.skipped.push(id.clone());
+                    Ok(id.clone())
+                }
+                (true, ConflictPolicy::Replace) => match self.delete_paste(&id).await {
+                    Ok(_) => self.import_paste(request, false).await.map(new_id),
+                    Err(err) => Err(err),
+                },
+                _ => self.import_paste(request, false).await.map(new_id),
+            };
+            match restored {
+                Ok(new_id) => {
+                    result.ids.insert(id, new_id);
+                }
+                Err(err) => result.failed.push((id, err)),
+            }
+            if let Some(progress) = progress.as_mut() {
+                progress(done + 1, total);
+            }
+        }
+        for bookmark in manifest.bookmarks {
+            let id = result.ids.get(&bookmark).unwrap_or(&bookmark).clone();
+            match self.create_bookmark(&id).await {
+                Ok(()) => result.bookmarks.push(id),
+                Err(err) => result.failed.push((bookmark, err)),
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn new_id((_, paste): (CreatePasteRequest, Option<PasteResult>)) -> String {
+    paste.map(|paste| paste.id).unwrap_or_default()
+}
+
+/// Write a paste's files to a directory named by its ID.
+fn write_paste(root: &Path, paste: &PasteResult) -> Result<(), Error> {
+    let dir = root.join(&paste.id);
+    fs::create_dir_all(&dir)?;
+    for file in &paste.files {
+        File::validate_filename(&file.filename)?;
+        fs::write(dir.join(&file.filename), &file.content)?;
+    }
+    Ok(())
+}
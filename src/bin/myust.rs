@@ -0,0 +1,244 @@
+//! A small command-line client for mystb.in, built on myust.
+//!
+//! ```text
+//! myust create notes.txt main.rs --expires 1d --password hunter2
+//! cat error.log | myust create -
+//! myust get https://mystb.in/EquipmentMovingExpensive
+//! myust delete EquipmentMovingExpensive
+//! myust list --json
+//! ```
+//!
+//! Deleting and listing pastes need a token, from `--token`, the `MYSTBIN_TOKEN`
+//! environment variable or the configuration file read by [`myust::config::Config`],
+//! whose profiles are selected with `--profile`.
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+    process::ExitCode,
+    time::Duration,
+};
+
+use clap::{Parser, Subcommand};
+use myust::{
+    config::Config,
+    report::{CreateReport, DeleteReport},
+    CancellationToken, Client, CreatePasteRequest, Error, Expiry, File, PasteProvider,
+};
+use serde::Serialize;
+
+#[derive(Parser)]
+#[command(name = "myust", version, about = "A command-line client for mystb.in")]
+struct Cli {
+    /// The mystb.in API token, instead of the configured one.
+    #[arg(long, global = true)]
+    token: Option<String>,
+    /// The URL of the API, for self-hosted instances.
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+    /// The profile of the configuration file to use, e.g. `work`.
+    #[arg(long, global = true, env = "MYUST_PROFILE")]
+    profile: Option<String>,
+    /// Print machine-readable JSON instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a paste from files, or from stdin with `-`.
+    Create {
+        /// The files of the paste.
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// When the paste expires, e.g. `1d` or `2h 30m`.
+        #[arg(long, value_parser = humantime::parse_duration)]
+        expires: Option<Duration>,
+        /// The password of the paste.
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Print the files of a paste.
+    Get {
+        /// The ID or URL of the paste.
+        paste: String,
+        /// The password of the paste.
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Delete pastes of the authenticated user.
+    Delete {
+        /// The IDs of the pastes.
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// List the pastes of the authenticated user.
+    List,
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), Error> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::from)?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn read_file(path: &str) -> io::Result<File> {
+    if path == "-" {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        return Ok(File {
+            filename: "stdin.txt".to_string(),
+            content,
+            syntax: None,
+        });
+    }
+    let filename = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string());
+    let bytes = fs::read(path)?;
+    Ok(match String::from_utf8(bytes) {
+        Ok(content) => File {
+            filename,
+            content,
+            syntax: None,
+        },
+        Err(err) => File::from_bytes(filename, err.as_bytes()),
+    })
+}
+
+/// Run the command, failing with [`ExitCode::FAILURE`] when only part of it succeeded.
+async fn run(cli: Cli) -> Result<ExitCode, Error> {
+    let config = match &cli.profile {
+        Some(profile) => Config::load_profile(profile)?,
+        None => Config::load()?,
+    };
+    let mut client = Client::from_config(&config);
+    if let Some(base_url) = cli.base_url {
+        client = client.base_url(base_url);
+    }
+    if let Some(token) = cli.token {
+        client = client.try_auth(token).await?;
+    }
+
+    match cli.command {
+        Command::Create {
+            files,
+            expires,
+            password,
+        } => {
+            let paste = match files.as_slice() {
+                [stdin] if stdin == "-" => {
+                    client
+                        .create_paste_from_stdin(|p| {
+                            p.filename("stdin.txt");
+                            if let Some(expires) = expires {
+                                p.expires(Expiry::from(expires));
+                            }
+                            if let Some(password) = password {
+                                p.password(password);
+                            }
+                            p
+                        })
+                        .await?
+                }
+                _ => {
+                    let files = files
+                        .iter()
+                        .map(|path| read_file(path))
+                        .collect::<io::Result<Vec<_>>>()?;
+                    // other fields depend on the enabled features
+                    #[allow(clippy::needless_update)]
+                    let request = CreatePasteRequest {
+                        files,
+                        password,
+                        expires: expires.map(Expiry::from),
+                        ..Default::default()
+                    };
+                    client.create(request).await?
+                }
+            };
+            let report = CreateReport::new(&paste, client.paste_url(&paste.id));
+            match cli.json {
+                true => print_json(&report)?,
+                false => println!("{}", report.url),
+            }
+        }
+        Command::Get { paste, password } => {
+            let paste = client
+                .get_paste(|p| {
+                    p.id(&paste);
+                    if let Some(password) = password {
+                        p.password(password);
+                    }
+                    p
+                })
+                .await?;
+            if cli.json {
+                print_json(&paste)?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            let several = paste.files.len() > 1;
+            for file in paste.files {
+                if several {
+                    println!("==> {} <==", file.filename);
+                }
+                println!("{}", file.content);
+            }
+        }
+        Command::Delete { ids } => {
+            let report = DeleteReport::from(
+                client
+                    .delete_pastes(ids.iter().map(|id| id.as_str()).collect())
+                    .await?,
+            );
+            let code = match report.failed.is_empty() {
+                true => ExitCode::SUCCESS,
+                false => ExitCode::FAILURE,
+            };
+            if cli.json {
+                print_json(&report)?;
+                return Ok(code);
+            }
+            for id in report.deleted {
+                println!("deleted {}", id);
+            }
+            for id in report.failed {
+                eprintln!("failed to delete {}", id);
+            }
+            return Ok(code);
+        }
+        Command::List => {
+            let pastes = client
+                .get_all_user_pastes(&CancellationToken::new())
+                .await?;
+            if cli.json {
+                print_json(&pastes)?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            for paste in pastes {
+                match paste.expires {
+                    Some(expires) => {
+                        println!("{}\t{}\texpires {}", paste.id, paste.created_at, expires)
+                    }
+                    None => println!("{}\t{}", paste.id, paste.created_at),
+                }
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::io;
+
+use crate::{utils::WEB_URL, Client, PasteResult};
+
+impl PasteResult {
+    /// Open the paste on mystb.in in the default browser.
+    ///
+    /// For a self-hosted instance, use [`Client::open_in_browser`] instead, which
+    /// honors [`Client::web_url`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "browser")))]
+    pub fn open_in_browser(&self) -> io::Result<()> {
+        webbrowser::open(&format!("{}/{}", WEB_URL, self.id))
+    }
+}
+
+impl Client {
+    /// Open a paste in the default browser, at [`paste_url`](Self::paste_url).
+    #[cfg_attr(docsrs, doc(cfg(feature = "browser")))]
+    pub fn open_in_browser(&self, paste_id: &str) -> io::Result<()> {
+        webbrowser::open(&self.paste_url(paste_id))
+    }
+}
+
+#[cfg(feature = "sync")]
+impl crate::SyncClient {
+    /// Open a paste in the default browser, at [`paste_url`](Self::paste_url).
+    #[cfg_attr(docsrs, doc(cfg(feature = "browser")))]
+    pub fn open_in_browser(&self, paste_id: &str) -> io::Result<()> {
+        webbrowser::open(&self.paste_url(paste_id))
+    }
+}
@@ -1,18 +1,50 @@
-use std::mem::take;
+use std::{
+    fmt, fs,
+    io::{self, Read},
+    mem::take,
+    ops::{Bound, Range, RangeBounds},
+    path::Path,
+};
 
-use crate::Expiry;
+use reqwest::header::{HeaderMap, HeaderValue, IntoHeaderName};
+use serde::Serialize;
+use tokio::io::AsyncRead;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    stream::ContentReader,
+    utils::{header_names, redact_option},
+    Error, Expiry, File, IntoPasteId, PasteId, PasteResult, ValidationError,
+};
 
 /// The builder to get a paste.
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct GetPasteBuilder {
     pub id: String,
     pub password: Option<String>,
+    pub file: Option<String>,
+    pub lines: Option<Range<usize>>,
+    pub headers: HeaderMap,
+    /// Why the ID given to [`id`](Self::id) was rejected, returned by `build`.
+    id_error: Option<ValidationError>,
 }
 
 impl GetPasteBuilder {
-    /// The ID of the paste.
-    pub fn id(&mut self, id: impl Into<String>) -> &mut Self {
-        self.id = id.into();
+    /// The ID of the paste, or its URL, see [`IntoPasteId`].
+    ///
+    /// An invalid ID fails the request with [`ValidationError::InvalidPasteId`]
+    /// before anything is sent.
+    pub fn id(&mut self, id: impl IntoPasteId) -> &mut Self {
+        (self.id, self.id_error) = match id.into_paste_id() {
+            Ok(id) => (id.into_string(), None),
+            Err(err) => {
+                let id = match &err {
+                    ValidationError::InvalidPasteId(id) => id.clone(),
+                    _ => String::new(),
+                };
+                (id, Some(err))
+            }
+        };
         self
     }
 
@@ -21,17 +53,273 @@ impl GetPasteBuilder {
         self.password = Some(password.into());
         self
     }
+
+    /// (optional) Only return the file with this name.
+    ///
+    /// Fails with [`Error::FileNotFound`] if the paste has no such file.
+    pub fn file(&mut self, filename: impl Into<String>) -> &mut Self {
+        self.file = Some(filename.into());
+        self
+    }
+
+    /// (optional) Only return these lines of each file, counted from zero like
+    /// slice indices: `lines(100..160)` returns the 101st to the 160th line.
+    ///
+    /// Lines past the end of a file are ignored. The whole paste is still
+    /// downloaded, and cached, before being trimmed.
+    pub fn lines(&mut self, lines: impl RangeBounds<usize>) -> &mut Self {
+        let start = match lines.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match lines.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => usize::MAX,
+        };
+        self.lines = Some(start..end);
+        self
+    }
+
+    /// (optional) Send an extra header with this request only, e.g. a tracing
+    /// header. It's added to the client's default headers, replacing any of the
+    /// same name.
+    pub fn header(&mut self, name: impl IntoHeaderName, value: HeaderValue) -> &mut Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Check the paste ID and build a request that can be reused, e.g. with the
+    /// client's `get_paste_from_request`.
+    pub fn build(&self) -> Result<GetPasteRequest, ValidationError> {
+        if let Some(err) = &self.id_error {
+            return Err(err.clone());
+        }
+        Ok(GetPasteRequest {
+            id: PasteId::parse(&self.id)?,
+            password: self.password.clone(),
+            file: self.file.clone(),
+            lines: self.lines.clone(),
+            headers: self.headers.clone(),
+        })
+    }
+}
+
+/// A checked request to get a paste, built with [`GetPasteBuilder::build`].
+#[derive(Clone)]
+pub struct GetPasteRequest {
+    /// The paste's ID.
+    pub id: PasteId,
+    /// The paste's password, if any.
+    pub password: Option<String>,
+    /// The only file to return, if any, see [`GetPasteBuilder::file`].
+    pub file: Option<String>,
+    /// The only lines to return, if any, see [`GetPasteBuilder::lines`].
+    pub lines: Option<Range<usize>>,
+    /// Extra headers sent with the request, see [`GetPasteBuilder::header`].
+    pub headers: HeaderMap,
+}
+
+impl GetPasteRequest {
+    /// Trim a fetched paste down to the selected file and lines.
+    pub(crate) fn select(&self, mut paste: PasteResult) -> Result<PasteResult, Error> {
+        if let Some(filename) = &self.file {
+            paste.files.retain(|file| &file.filename == filename);
+            if paste.files.is_empty() {
+                return Err(Error::FileNotFound(filename.clone()));
+            }
+        }
+        if let Some(lines) = &self.lines {
+            for file in paste.files.iter_mut() {
+                file.content = file
+                    .content
+                    .split_inclusive('\n')
+                    .skip(lines.start)
+                    .take(lines.end.saturating_sub(lines.start))
+                    .collect();
+            }
+        }
+        Ok(paste)
+    }
 }
 /// The builder to create a paste.
-#[derive(Debug, Default)]
+#[derive(Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PasteBuilder {
     pub filename: String,
     pub content: String,
     pub expires: Option<Expiry>,
     pub password: Option<String>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub(crate) reader: Option<ContentReader>,
+    #[cfg(feature = "compression")]
+    pub compress: bool,
+    #[cfg(feature = "language-detection")]
+    pub detect_language: bool,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub headers: HeaderMap,
+    pub idempotency_key: Option<String>,
+    pub syntax: Option<String>,
+    pub invalid_characters: InvalidCharacters,
+    pub line_endings: LineEndings,
+    pub filters: Vec<ContentFilter>,
+    pub duplicate_filenames: DuplicateFilenames,
+    pub deduplicate: Option<bool>,
+}
+
+/// What to do with content the API may reject with an opaque error: NUL and other
+/// control characters than tabs and line breaks, and the replacement characters left
+/// by lossy UTF-8 conversions, e.g. from [`PasteBuilder::content_bytes`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum InvalidCharacters {
+    /// Send the content as is.
+    #[default]
+    Keep,
+    /// Fail with [`ValidationError::InvalidCharacter`] before anything is sent.
+    Reject,
+    /// Remove the characters before uploading the content.
+    Strip,
+}
+
+/// How the line endings of the content are uploaded.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LineEndings {
+    /// Keep them as they are.
+    #[default]
+    Preserve,
+    /// Turn Windows (`\r\n`) and old Mac (`\r`) line endings into `\n`, so they
+    /// don't show as `^M` in the viewer.
+    Lf,
+}
+
+/// What to do with files of a multi-file paste named like an earlier file, which
+/// the API accepts but makes the paste confusing to read.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum DuplicateFilenames {
+    /// Send the files as they are.
+    #[default]
+    Keep,
+    /// Fail with [`ValidationError::DuplicateFilename`] before anything is sent.
+    Reject,
+    /// Number the later files before their extension, e.g. `foo (1).txt`, see
+    /// [`File::rename_duplicates`].
+    Rename,
+}
+
+/// A transform applied to the content before it's uploaded, e.g. to tidy up a
+/// terminal capture. Filters are chained with [`PasteBuilder::filter`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ContentFilter {
+    /// Replace tabs with spaces, up to the next multiple of this many columns. A
+    /// width of 0 removes them.
+    ExpandTabs(usize),
+    /// Remove the whitespace at the end of each line, keeping its line ending.
+    TrimTrailingWhitespace,
+    /// Keep at most this many blank lines in a row.
+    CollapseBlankLines(usize),
+}
+
+impl ContentFilter {
+    /// The content with the filter applied.
+    pub fn apply(&self, content: &str) -> String {
+        match *self {
+            ContentFilter::ExpandTabs(width) => {
+                let mut filtered = String::with_capacity(content.len());
+                let mut column = 0;
+                for c in content.chars() {
+                    match c {
+                        '\t' if width > 0 => {
+                            let spaces = width - column % width;
+                            filtered.extend(std::iter::repeat_n(' ', spaces));
+                            column += spaces;
+                        }
+                        '\t' => {}
+                        '\n' | '\r' => {
+                            filtered.push(c);
+                            column = 0;
+                        }
+                        _ => {
+                            filtered.push(c);
+                            column += 1;
+                        }
+                    }
+                }
+                filtered
+            }
+            ContentFilter::TrimTrailingWhitespace => content
+                .split_inclusive('\n')
+                .flat_map(|line| {
+                    let (text, ending) = split_line_ending(line);
+                    [text.trim_end(), ending]
+                })
+                .collect(),
+            ContentFilter::CollapseBlankLines(max) => {
+                let mut blank = 0;
+                content
+                    .split_inclusive('\n')
+                    .filter(|line| {
+                        blank = match line.trim().is_empty() {
+                            true => blank + 1,
+                            false => 0,
+                        };
+                        blank <= max
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A line split into its text and its `\n` or `\r\n` ending.
+fn split_line_ending(line: &str) -> (&str, &str) {
+    let text = line
+        .strip_suffix('\n')
+        .map(|text| text.strip_suffix('\r').unwrap_or(text))
+        .unwrap_or(line);
+    line.split_at(text.len())
 }
 
 impl PasteBuilder {
+    /// A builder for the file at `path`, named after it and with its content.
+    ///
+    /// Fails with [`Error::Io`] if the file can't be read, isn't UTF-8, or the path
+    /// has no file name.
+    ///
+    /// ```rust,no_run
+    /// use myust::{Client, Expiry, PasteBuilder, PasteProvider};
+    ///
+    /// # async fn run(client: Client) -> Result<(), myust::Error> {
+    /// let mut paste = PasteBuilder::from_file("logs/crash.log")?;
+    /// paste.expires(Expiry::ONE_DAY);
+    /// let result = client.create(paste.into()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let filename = path
+            .file_name()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} has no file name", path.display()),
+                )
+            })?
+            .to_string_lossy()
+            .into_owned();
+        Ok(PasteBuilder {
+            filename,
+            content: fs::read_to_string(path)?,
+            ..Default::default()
+        })
+    }
+
     /// The filename for the paste.
     pub fn filename(&mut self, filename: impl Into<String>) -> &mut Self {
         self.filename = filename.into();
@@ -44,6 +332,33 @@ impl PasteBuilder {
         self
     }
 
+    /// The content for the paste from bytes that may not be UTF-8, e.g. a log file.
+    ///
+    /// Invalid sequences are replaced with `U+FFFD`, which
+    /// [`invalid_characters`](Self::invalid_characters) can reject or strip.
+    pub fn content_bytes(&mut self, content: impl AsRef<[u8]>) -> &mut Self {
+        self.content = String::from_utf8_lossy(content.as_ref()).into_owned();
+        self
+    }
+
+    /// Read the content from `reader` while the paste is uploaded, instead of
+    /// loading it into a `String` first. This replaces [`content`](Self::content).
+    ///
+    /// Only the async client can read from an async reader.
+    pub fn content_reader(&mut self, reader: impl AsyncRead + Send + 'static) -> &mut Self {
+        self.reader = Some(ContentReader::Async(Box::pin(reader)));
+        self
+    }
+
+    /// Like [`content_reader`](Self::content_reader), for blocking readers such as
+    /// a file handle or a child process' stdout.
+    ///
+    /// The async client reads it on the task sending the request.
+    pub fn content_blocking_reader(&mut self, reader: impl Read + Send + 'static) -> &mut Self {
+        self.reader = Some(ContentReader::Blocking(Box::new(reader)));
+        self
+    }
+
     /// (optional) The expiration date for the paste.
     pub fn expires(&mut self, expires: Expiry) -> &mut Self {
         self.expires = Some(expires);
@@ -55,10 +370,264 @@ impl PasteBuilder {
         self.password = Some(password.into());
         self
     }
+
+    /// (optional) Send an extra header with this request only, e.g. a tracing
+    /// header or an experimental API toggle. It's added to the client's default
+    /// headers, replacing any of the same name.
+    pub fn header(&mut self, name: impl IntoHeaderName, value: HeaderValue) -> &mut Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// (optional) The idempotency key sent with the request, so the API creates the
+    /// paste only once however many times the request is sent. Defaults to a
+    /// generated key, available on [`PasteResult::idempotency_key`].
+    pub fn idempotency_key(&mut self, key: impl Into<String>) -> &mut Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// (optional) Whether to return an identical paste recorded in the client's
+    /// journal instead of creating this one, overriding the client's `deduplicate`.
+    /// Enabling it on a client without a journal fails the paste with
+    /// [`Error::Unsupported`]. In a multi-file paste, the setting of the first file
+    /// applies to the paste.
+    pub fn deduplicate(&mut self, enabled: bool) -> &mut Self {
+        self.deduplicate = Some(enabled);
+        self
+    }
+
+    /// (optional) The language to highlight the file as, such as `rust` or `rs`.
+    ///
+    /// The API has no field for it, so its extension is appended to a filename that
+    /// has none. Language names such as `rust` are turned into their extension with
+    /// the `language-detection` feature, other hints are used as the extension.
+    pub fn syntax(&mut self, syntax: impl Into<String>) -> &mut Self {
+        self.syntax = Some(syntax.into());
+        self
+    }
+
+    /// (optional) Gzip the content and store it base64-encoded, to fit large text
+    /// such as logs under [`MAX_FILE_CHARACTERS`](crate::MAX_FILE_CHARACTERS).
+    /// Defaults to `false`.
+    ///
+    /// The filename gets the [`COMPRESSED_SUFFIX`](crate::COMPRESSED_SUFFIX) and
+    /// `get_paste` decompresses the file again. In a multi-file paste, the setting
+    /// of the first file applies to every file.
+    #[cfg(feature = "compression")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+    pub fn compress(&mut self, compress: bool) -> &mut Self {
+        self.compress = compress;
+        self
+    }
+
+    /// (optional) Append the extension of the language detected in the content
+    /// when the filename has none, so mystb.in highlights it. Defaults to `false`.
+    ///
+    /// Content read from a reader isn't looked at.
+    #[cfg(feature = "language-detection")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "language-detection")))]
+    pub fn detect_language(&mut self, detect_language: bool) -> &mut Self {
+        self.detect_language = detect_language;
+        self
+    }
+
+    /// (optional) What to do with NUL and other control characters, see
+    /// [`InvalidCharacters`]. Defaults to keeping them.
+    ///
+    /// Content read from a reader isn't looked at. In a multi-file paste, the
+    /// setting of the first file applies to every file.
+    pub fn invalid_characters(&mut self, policy: InvalidCharacters) -> &mut Self {
+        self.invalid_characters = policy;
+        self
+    }
+
+    /// (optional) Normalize the line endings before uploading the content, see
+    /// [`LineEndings`]. Defaults to preserving them.
+    ///
+    /// The setting is recorded in [`PasteResult::line_endings`]. Content read from a
+    /// reader isn't changed. In a multi-file paste, the setting of the first file
+    /// applies to every file.
+    pub fn line_endings(&mut self, line_endings: LineEndings) -> &mut Self {
+        self.line_endings = line_endings;
+        self
+    }
+
+    /// (optional) Add a filter the content goes through before it's uploaded, after
+    /// the previous ones, see [`ContentFilter`].
+    ///
+    /// ```rust
+    /// use myust::{ContentFilter, PasteBuilder};
+    ///
+    /// let mut paste = PasteBuilder::default();
+    /// paste
+    ///     .filter(ContentFilter::ExpandTabs(4))
+    ///     .filter(ContentFilter::TrimTrailingWhitespace)
+    ///     .filter(ContentFilter::CollapseBlankLines(1));
+    /// ```
+    ///
+    /// Filters run after the line endings are normalized, and the limits are
+    /// checked on the filtered content when it's sent. Content read from a reader
+    /// isn't changed. In a multi-file paste, the filters of the first file apply to
+    /// every file.
+    pub fn filter(&mut self, filter: ContentFilter) -> &mut Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// (optional) What to do with files named like an earlier file of the paste,
+    /// see [`DuplicateFilenames`]. Defaults to keeping them.
+    ///
+    /// Filenames are compared once the syntax hint's extension is appended. In a
+    /// multi-file paste, the setting of the first file applies to every file.
+    pub fn duplicate_filenames(&mut self, policy: DuplicateFilenames) -> &mut Self {
+        self.duplicate_filenames = policy;
+        self
+    }
+
+    /// Check the filename, content and expiration date without sending anything.
+    ///
+    /// Content read from a reader isn't checked, and the size of compressed
+    /// content is only checked once it's compressed.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        File::validate_filename(&self.filename)?;
+        if self.reader.is_none() {
+            File::validate_content(&self.content)?;
+            if self.invalid_characters == InvalidCharacters::Reject {
+                File::validate_characters(&self.filename, &self.content)?;
+            }
+            if !self.compressed() {
+                File::validate_size(&self.filename, &self.content)?;
+            }
+        }
+        match &self.expires {
+            Some(expiry) => expiry.validate(),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the paste like [`validate`](Self::validate) and build a request that
+    /// can be cloned and reused, e.g. with the client's `create_paste_from_request`.
+    ///
+    /// Fails with [`Error::Unsupported`] if the content comes from a reader, which
+    /// can only be read once.
+    pub fn build(&self) -> Result<CreatePasteRequest, Error> {
+        self.validate()?;
+        Ok(self.try_clone()?.into())
+    }
+
+    /// A copy of the builder, unless its content comes from a reader.
+    fn try_clone(&self) -> Result<PasteBuilder, Error> {
+        if self.reader.is_some() {
+            return Err(Error::Unsupported(
+                "content read from a reader can't be built into a request",
+            ));
+        }
+        Ok(PasteBuilder {
+            filename: self.filename.clone(),
+            content: self.content.clone(),
+            expires: self.expires.clone(),
+            password: self.password.clone(),
+            reader: None,
+            #[cfg(feature = "compression")]
+            compress: self.compress,
+            #[cfg(feature = "language-detection")]
+            detect_language: self.detect_language,
+            headers: self.headers.clone(),
+            idempotency_key: self.idempotency_key.clone(),
+            syntax: self.syntax.clone(),
+            invalid_characters: self.invalid_characters,
+            line_endings: self.line_endings,
+            filters: self.filters.clone(),
+            duplicate_filenames: self.duplicate_filenames,
+            deduplicate: self.deduplicate,
+        })
+    }
+
+    pub(crate) fn compressed(&self) -> bool {
+        #[cfg(feature = "compression")]
+        return self.compress;
+        #[cfg(not(feature = "compression"))]
+        false
+    }
+
+    /// Take the filename, with the extension of the syntax hint, or else the
+    /// detected extension if enabled, appended.
+    pub(crate) fn take_filename(&mut self) -> String {
+        let filename = take(&mut self.filename);
+        if let Some(syntax) = &self.syntax {
+            if Path::new(&filename).extension().is_none() {
+                return format!("{}.{}", filename, syntax_extension(syntax));
+            }
+            return filename;
+        }
+        #[cfg(feature = "language-detection")]
+        if self.detect_language {
+            return crate::language::with_extension(filename, &self.content);
+        }
+        filename
+    }
+}
+
+/// The extension of a syntax hint: the extension of the language it names with
+/// language detection, or else the hint itself.
+fn syntax_extension(syntax: &str) -> String {
+    let syntax = syntax.trim_start_matches('.');
+    #[cfg(feature = "language-detection")]
+    if let Some(language) = crate::language::Language::from_name(syntax) {
+        return language.extension().to_string();
+    }
+    syntax.to_ascii_lowercase()
+}
+
+impl fmt::Debug for GetPasteBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetPasteBuilder")
+            .field("id", &self.id)
+            .field("password", &redact_option(&self.password))
+            .field("file", &self.file)
+            .field("lines", &self.lines)
+            .field("headers", &header_names(&self.headers))
+            .finish()
+    }
+}
+
+impl fmt::Debug for GetPasteRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetPasteRequest")
+            .field("id", &self.id)
+            .field("password", &redact_option(&self.password))
+            .field("file", &self.file)
+            .field("lines", &self.lines)
+            .field("headers", &header_names(&self.headers))
+            .finish()
+    }
+}
+
+impl fmt::Debug for PasteBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasteBuilder")
+            .field("filename", &self.filename)
+            .field("content_len", &self.content.len())
+            .field("reader", &self.reader)
+            .field("compress", &self.compressed())
+            .field("expires", &self.expires)
+            .field("password", &redact_option(&self.password))
+            .field("headers", &header_names(&self.headers))
+            .field("idempotency_key", &self.idempotency_key)
+            .field("syntax", &self.syntax)
+            .field("invalid_characters", &self.invalid_characters)
+            .field("line_endings", &self.line_endings)
+            .field("filters", &self.filters)
+            .field("duplicate_filenames", &self.duplicate_filenames)
+            .field("deduplicate", &self.deduplicate)
+            .finish()
+    }
 }
 
 /// The builder to create multiple pastes.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PastesBuilder {
     pub files: Vec<PasteBuilder>,
 }
@@ -73,13 +642,81 @@ impl PastesBuilder {
         self.files.push(take(data));
         self
     }
+
+    /// Like [`file`](Self::file), for files whose content can fail to load. The
+    /// file is checked as with [`PasteBuilder::validate`], and only added if it's
+    /// valid and there's room for it under [`MAX_FILES`](crate::MAX_FILES).
+    ///
+    /// ```rust,no_run
+    /// use std::fs;
+    /// use myust::{Client, PasteBuilder, PasteProvider, PastesBuilder};
+    ///
+    /// # async fn run(client: Client) -> Result<(), myust::Error> {
+    /// let mut pastes = PastesBuilder::default();
+    /// pastes
+    ///     .try_file(|f| Ok(f.filename("app.log").content(fs::read_to_string("app.log")?)))?
+    ///     .try_file(|f| {
+    ///         *f = PasteBuilder::from_file("config.toml")?;
+    ///         Ok(f)
+    ///     })?;
+    /// let result = client.create(pastes.into()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_file(
+        &mut self,
+        paste: impl FnOnce(&mut PasteBuilder) -> Result<&mut PasteBuilder, Error>,
+    ) -> Result<&mut Self, Error> {
+        let mut builder = PasteBuilder::default();
+        let data = paste(&mut builder)?;
+        data.validate()?;
+        File::validate_count(self.files.len() + 1)?;
+        self.files.push(take(data));
+        Ok(self)
+    }
+
+    /// Check every file without sending anything.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.files.is_empty() {
+            return Err(ValidationError::NoFiles);
+        }
+        File::validate_count(self.files.len())?;
+        self.files.iter().try_for_each(PasteBuilder::validate)?;
+        if self
+            .files
+            .first()
+            .is_some_and(|first| first.duplicate_filenames == DuplicateFilenames::Reject)
+        {
+            File::validate_unique_filenames(self.files.iter().map(|file| file.filename.as_str()))?;
+        }
+        match self.files.first().map(|first| first.invalid_characters) {
+            Some(InvalidCharacters::Reject) => self
+                .files
+                .iter()
+                .filter(|file| file.reader.is_none())
+                .try_for_each(|file| File::validate_characters(&file.filename, &file.content)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check every file like [`validate`](Self::validate) and build a request that
+    /// can be cloned and reused, see [`PasteBuilder::build`].
+    pub fn build(&self) -> Result<CreatePasteRequest, Error> {
+        self.validate()?;
+        let files = self
+            .files
+            .iter()
+            .map(PasteBuilder::try_clone)
+            .collect::<Result<_, _>>()?;
+        Ok(PastesBuilder { files }.into())
+    }
 }
 
 /// The builder to build options for getting user pastes.
-#[derive(Debug)]
 pub struct UserPastesOptions {
     pub limit: i32,
     pub page: i32,
+    pub headers: HeaderMap,
 }
 
 impl UserPastesOptions {
@@ -94,10 +731,386 @@ impl UserPastesOptions {
         self.page = page;
         self
     }
+
+    /// (optional) Send an extra header with this request only. It's added to the
+    /// client's default headers, replacing any of the same name.
+    pub fn header(&mut self, name: impl IntoHeaderName, value: HeaderValue) -> &mut Self {
+        self.headers.insert(name, value);
+        self
+    }
 }
 
 impl Default for UserPastesOptions {
     fn default() -> Self {
-        UserPastesOptions { limit: 50, page: 1 }
+        UserPastesOptions {
+            limit: 50,
+            page: 1,
+            headers: HeaderMap::new(),
+        }
+    }
+}
+
+impl fmt::Debug for UserPastesOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserPastesOptions")
+            .field("limit", &self.limit)
+            .field("page", &self.page)
+            .field("headers", &header_names(&self.headers))
+            .finish()
+    }
+}
+
+/// The builder to build options for getting the user's bookmarked pastes.
+#[derive(Clone, Debug)]
+pub struct BookmarkedPastesOptions {
+    pub concurrency: usize,
+    pub skip_protected: bool,
+}
+
+impl BookmarkedPastesOptions {
+    /// How many pastes are fetched at once. Defaults to 4.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Leave out the pastes with a password instead of failing on them. Defaults to
+    /// `false`.
+    pub fn skip_protected(&mut self, skip: bool) -> &mut Self {
+        self.skip_protected = skip;
+        self
+    }
+}
+
+impl Default for BookmarkedPastesOptions {
+    fn default() -> Self {
+        BookmarkedPastesOptions {
+            concurrency: 4,
+            skip_protected: false,
+        }
+    }
+}
+
+/// An owned, self-contained request to create a paste.
+///
+/// It can be built from a [`PasteBuilder`] or a [`PastesBuilder`] and is used
+/// for bulk operations.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CreatePasteRequest {
+    /// The paste's files.
+    pub files: Vec<File>,
+    /// The paste's password, if any.
+    pub password: Option<String>,
+    /// The paste's expiration date, if any.
+    pub expires: Option<Expiry>,
+    /// Whether the files are compressed before upload, see [`PasteBuilder::compress`].
+    #[cfg(feature = "compression")]
+    pub compress: bool,
+    /// Extra headers sent with the request, see [`PasteBuilder::header`].
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub headers: HeaderMap,
+    /// The idempotency key, generated when the request is sent if `None`, see
+    /// [`PasteBuilder::idempotency_key`].
+    pub idempotency_key: Option<String>,
+    /// What to do with NUL and other control characters, see
+    /// [`PasteBuilder::invalid_characters`].
+    pub invalid_characters: InvalidCharacters,
+    /// How the line endings are uploaded, see [`PasteBuilder::line_endings`].
+    pub line_endings: LineEndings,
+    /// The filters the content goes through, see [`PasteBuilder::filter`].
+    pub filters: Vec<ContentFilter>,
+    /// What to do with files named like an earlier file, see
+    /// [`PasteBuilder::duplicate_filenames`].
+    pub duplicate_filenames: DuplicateFilenames,
+    /// Whether to reuse an identical paste from the journal, the client's setting if
+    /// `None`, see [`PasteBuilder::deduplicate`].
+    pub deduplicate: Option<bool>,
+}
+
+impl CreatePasteRequest {
+    /// Whether the request fits the limits the API enforces before any compression:
+    /// at most [`MAX_FILES`](crate::MAX_FILES) files, of at most
+    /// [`MAX_FILE_CHARACTERS`](crate::MAX_FILE_CHARACTERS) each.
+    ///
+    /// Unlike [`validate`](Self::validate), it doesn't check the filenames or the
+    /// expiration date.
+    pub fn fits_limits(&self) -> bool {
+        File::validate_count(self.files.len()).is_ok() && self.files.iter().all(File::fits_limits)
+    }
+
+    /// Check every file and the expiration date without sending anything.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.files.is_empty() {
+            return Err(ValidationError::NoFiles);
+        }
+        File::validate_count(self.files.len())?;
+        if self.duplicate_filenames == DuplicateFilenames::Reject {
+            File::validate_unique_filenames(self.files.iter().map(|file| file.filename.as_str()))?;
+        }
+        for file in &self.files {
+            File::validate_filename(&file.filename)?;
+            File::validate_content(&file.content)?;
+            if self.invalid_characters == InvalidCharacters::Reject {
+                File::validate_characters(&file.filename, &file.content)?;
+            }
+            if !self.compressed() {
+                File::validate_size(&file.filename, &file.content)?;
+            }
+        }
+        match &self.expires {
+            Some(expiry) => expiry.validate(),
+            None => Ok(()),
+        }
+    }
+
+    /// Split the request into requests of at most [`MAX_FILES`](crate::MAX_FILES)
+    /// files each, e.g. to send them with `bulk_create` after
+    /// [`ValidationError::TooManyFiles`]. They share the password, expiration date
+    /// and settings, and a set idempotency key gets a `-1`, `-2`... suffix so each
+    /// part is created.
+    ///
+    /// A request that fits is returned as is.
+    pub fn split_files(mut self) -> Vec<CreatePasteRequest> {
+        if self.files.len() <= crate::MAX_FILES {
+            return vec![self];
+        }
+        let files = take(&mut self.files);
+        files
+            .chunks(crate::MAX_FILES)
+            .enumerate()
+            .map(|(index, files)| CreatePasteRequest {
+                files: files.to_vec(),
+                idempotency_key: self
+                    .idempotency_key
+                    .as_ref()
+                    .map(|key| format!("{}-{}", key, index + 1)),
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    pub(crate) fn compressed(&self) -> bool {
+        #[cfg(feature = "compression")]
+        return self.compress;
+        #[cfg(not(feature = "compression"))]
+        false
+    }
+
+    /// Normalize the line endings of the files, as set by `line_endings`.
+    pub(crate) fn normalize_line_endings(&mut self) {
+        if self.line_endings == LineEndings::Lf {
+            for file in self.files.iter_mut() {
+                if file.content.contains('\r') {
+                    file.content = file.content.replace("\r\n", "\n").replace('\r', "\n");
+                }
+            }
+        }
+    }
+
+    /// Run the content of the files through `filters`.
+    pub(crate) fn apply_filters(&mut self) {
+        for filter in &self.filters {
+            for file in self.files.iter_mut() {
+                file.content = filter.apply(&file.content);
+            }
+        }
+    }
+
+    /// Reject or rename the files with duplicate names, as set by
+    /// `duplicate_filenames`.
+    pub(crate) fn check_filenames(&mut self) -> Result<(), ValidationError> {
+        match self.duplicate_filenames {
+            DuplicateFilenames::Keep => Ok(()),
+            DuplicateFilenames::Reject => File::validate_unique_filenames(
+                self.files.iter().map(|file| file.filename.as_str()),
+            ),
+            DuplicateFilenames::Rename => {
+                File::rename_duplicates(&mut self.files);
+                Ok(())
+            }
+        }
+    }
+
+    /// Reject or strip the invalid characters of the files, as set by
+    /// `invalid_characters`.
+    pub(crate) fn check_characters(&mut self) -> Result<(), ValidationError> {
+        match self.invalid_characters {
+            InvalidCharacters::Keep => {}
+            InvalidCharacters::Reject => {
+                for file in &self.files {
+                    File::validate_characters(&file.filename, &file.content)?;
+                }
+            }
+            InvalidCharacters::Strip => {
+                for file in self.files.iter_mut() {
+                    file.content = File::strip_invalid_characters(&file.content);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CreatePasteRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreatePasteRequest")
+            .field(
+                "files",
+                &self.files.iter().map(|f| &f.filename).collect::<Vec<_>>(),
+            )
+            .field("password", &redact_option(&self.password))
+            .field("expires", &self.expires)
+            .field("compress", &self.compressed())
+            .field("headers", &header_names(&self.headers))
+            .field("idempotency_key", &self.idempotency_key)
+            .field("invalid_characters", &self.invalid_characters)
+            .field("line_endings", &self.line_endings)
+            .field("filters", &self.filters)
+            .field("duplicate_filenames", &self.duplicate_filenames)
+            .field("deduplicate", &self.deduplicate)
+            .finish()
+    }
+}
+
+impl From<&mut PasteBuilder> for CreatePasteRequest {
+    fn from(paste: &mut PasteBuilder) -> Self {
+        CreatePasteRequest {
+            files: vec![File {
+                filename: paste.take_filename(),
+                content: take(&mut paste.content),
+                syntax: paste.syntax.take(),
+            }],
+            password: paste.password.take(),
+            expires: paste.expires.take(),
+            #[cfg(feature = "compression")]
+            compress: paste.compress,
+            headers: take(&mut paste.headers),
+            idempotency_key: paste.idempotency_key.take(),
+            invalid_characters: paste.invalid_characters,
+            line_endings: paste.line_endings,
+            filters: take(&mut paste.filters),
+            duplicate_filenames: paste.duplicate_filenames,
+            deduplicate: paste.deduplicate,
+        }
+    }
+}
+
+impl From<PasteBuilder> for CreatePasteRequest {
+    fn from(mut paste: PasteBuilder) -> Self {
+        Self::from(&mut paste)
+    }
+}
+
+impl From<&mut PastesBuilder> for CreatePasteRequest {
+    /// The password, expiration date, headers and idempotency key are taken from the
+    /// first file.
+    fn from(pastes: &mut PastesBuilder) -> Self {
+        let (password, expires, headers, idempotency_key) = match pastes.files.first_mut() {
+            Some(first) => (
+                first.password.take(),
+                first.expires.take(),
+                take(&mut first.headers),
+                first.idempotency_key.take(),
+            ),
+            None => (None, None, HeaderMap::new(), None),
+        };
+        #[cfg(feature = "compression")]
+        let compress = pastes.files.first().is_some_and(|first| first.compress);
+        let (invalid_characters, line_endings, duplicate_filenames, deduplicate) = pastes
+            .files
+            .first()
+            .map(|first| {
+                (
+                    first.invalid_characters,
+                    first.line_endings,
+                    first.duplicate_filenames,
+                    first.deduplicate,
+                )
+            })
+            .unwrap_or_default();
+        let filters = pastes
+            .files
+            .first_mut()
+            .map(|first| take(&mut first.filters))
+            .unwrap_or_default();
+        CreatePasteRequest {
+            files: pastes
+                .files
+                .iter_mut()
+                .map(|file| File {
+                    filename: file.take_filename(),
+                    content: take(&mut file.content),
+                    syntax: file.syntax.take(),
+                })
+                .collect(),
+            password,
+            expires,
+            #[cfg(feature = "compression")]
+            compress,
+            headers,
+            idempotency_key,
+            invalid_characters,
+            line_endings,
+            filters,
+            duplicate_filenames,
+            deduplicate,
+        }
+    }
+}
+
+impl From<PastesBuilder> for CreatePasteRequest {
+    fn from(mut pastes: PastesBuilder) -> Self {
+        Self::from(&mut pastes)
+    }
+}
+
+/// The options for bulk operations.
+pub struct BulkOptions<'a> {
+    pub concurrency: usize,
+    pub progress: Option<Box<dyn FnMut(usize, usize) + Send + 'a>>,
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl<'a> BulkOptions<'a> {
+    /// How many requests are in flight at once. Defaults to 4.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// (optional) A callback receiving the number of completed items and the total
+    /// after each item completes.
+    pub fn progress(&mut self, progress: impl FnMut(usize, usize) + Send + 'a) -> &mut Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// (optional) A token to stop the operation with. Once it's cancelled, the
+    /// requests in flight are dropped and every remaining item fails with
+    /// [`Error::Cancelled`].
+    pub fn cancellation(&mut self, token: CancellationToken) -> &mut Self {
+        self.cancellation = Some(token);
+        self
+    }
+}
+
+impl Default for BulkOptions<'_> {
+    fn default() -> Self {
+        BulkOptions {
+            concurrency: 4,
+            progress: None,
+            cancellation: None,
+        }
+    }
+}
+
+impl fmt::Debug for BulkOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkOptions")
+            .field("concurrency", &self.concurrency)
+            .field("progress", &self.progress.is_some())
+            .field("cancellation", &self.cancellation)
+            .finish()
     }
 }
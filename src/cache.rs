@@ -0,0 +1,145 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{utils::lock, PasteResult};
+
+/// The configuration of the built-in [`MemoryCache`].
+///
+/// ```rust
+/// use std::time::Duration;
+/// use myust::{CacheConfig, Client};
+///
+/// let client = Client::new().with_cache(CacheConfig {
+///     capacity: 256,
+///     ttl: Duration::from_secs(30),
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// The most pastes kept at once. The oldest entry is evicted to make room.
+    pub capacity: usize,
+    /// How long a paste is served from the cache before being fetched again.
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            capacity: 128,
+            ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A store for pastes read with `get_paste`.
+///
+/// Entries are keyed by paste ID and password, so a paste fetched with one password
+/// is never served to a call using another. Implement it to bring your own store,
+/// and register it with the client's `cache_store` method.
+pub trait PasteCache: Send + Sync {
+    /// Return the paste if it's stored and still fresh.
+    fn get(&self, id: &str, password: Option<&str>) -> Option<PasteResult>;
+
+    /// Store a paste that was just fetched.
+    fn insert(&self, password: Option<&str>, paste: PasteResult);
+
+    /// Drop every entry of a paste, e.g. after it's deleted.
+    fn invalidate(&self, id: &str);
+}
+
+impl<T: PasteCache + ?Sized> PasteCache for Arc<T> {
+    fn get(&self, id: &str, password: Option<&str>) -> Option<PasteResult> {
+        (**self).get(id, password)
+    }
+
+    fn insert(&self, password: Option<&str>, paste: PasteResult) {
+        (**self).insert(password, paste)
+    }
+
+    fn invalidate(&self, id: &str) {
+        (**self).invalidate(id)
+    }
+}
+
+type Entries = HashMap<(String, Option<String>), (Instant, PasteResult)>;
+
+/// An in-memory [`PasteCache`] with a capacity and a time-to-live.
+#[derive(Default)]
+pub struct MemoryCache {
+    config: CacheConfig,
+    entries: Mutex<Entries>,
+}
+
+impl MemoryCache {
+    /// Create an empty cache.
+    pub fn new(config: CacheConfig) -> Self {
+        MemoryCache {
+            config,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// The number of entries currently stored, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        lock(&self.entries).len()
+    }
+
+    /// Whether the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl PasteCache for MemoryCache {
+    fn get(&self, id: &str, password: Option<&str>) -> Option<PasteResult> {
+        let mut entries = lock(&self.entries);
+        let key = (id.to_string(), password.map(|p| p.to_string()));
+        match entries.get(&key) {
+            Some((stored, paste)) if stored.elapsed() < self.config.ttl => Some(paste.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, password: Option<&str>, paste: PasteResult) {
+        if self.config.capacity == 0 {
+            return;
+        }
+        let mut entries = lock(&self.entries);
+        let key = (paste.id.clone(), password.map(|p| p.to_string()));
+        if !entries.contains_key(&key) && entries.len() >= self.config.capacity {
+            let ttl = self.config.ttl;
+            entries.retain(|_, (stored, _)| stored.elapsed() < ttl);
+            if entries.len() >= self.config.capacity {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (stored, _))| *stored)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(key, (Instant::now(), paste));
+    }
+
+    fn invalidate(&self, id: &str) {
+        lock(&self.entries).retain(|(paste_id, _), _| paste_id != id);
+    }
+}
+
+impl fmt::Debug for MemoryCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryCache")
+            .field("config", &self.config)
+            .field("len", &self.len())
+            .finish()
+    }
+}
@@ -0,0 +1,272 @@
+//! Named groups of pastes, e.g. every file shared during one incident.
+//!
+//! Collections are kept in a local JSON file, and can be mirrored as an index paste
+//! listing their pastes, so they can be shared with a single URL:
+//!
+//! ```rust,no_run
+//! use myust::{collection::Collections, Client};
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new();
+//! let collections = Collections::open("collections.json")?;
+//! collections.create("incident-42")?;
+//! collections.add("incident-42", "EquipmentMovingExpensive")?;
+//! let index = client.mirror_collection(&collections, "incident-42").await?;
+//! println!("{}", client.paste_url(&index.id));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{utils::lock, Client, CreatePasteRequest, Error, File, PasteProvider, PasteResult};
+
+/// The name of the file of an index paste holding the collection itself.
+pub const COLLECTION_FILENAME: &str = "collection.json";
+
+/// A named set of pastes.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Collection {
+    /// The collection's name.
+    pub name: String,
+    /// The IDs of the collection's pastes, in the order they were added.
+    pub pastes: Vec<String>,
+    /// The ID of the index paste mirroring the collection, if any.
+    #[serde(default)]
+    pub index_paste: Option<String>,
+}
+
+impl Collection {
+    /// An empty collection.
+    pub fn new(name: impl Into<String>) -> Self {
+        Collection {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the collection holds a paste.
+    pub fn contains(&self, id: &str) -> bool {
+        self.pastes.iter().any(|paste| paste == id)
+    }
+
+    /// Add a paste, returning whether it wasn't in the collection yet.
+    pub fn add(&mut self, id: impl Into<String>) -> bool {
+        let id = id.into();
+        if self.contains(&id) {
+            return false;
+        }
+        self.pastes.push(id);
+        true
+    }
+
+    /// Remove a paste, returning whether it was in the collection.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let count = self.pastes.len();
+        self.pastes.retain(|paste| paste != id);
+        self.pastes.len() != count
+    }
+}
+
+fn not_found(name: &str) -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no collection is named {}", name),
+    ))
+}
+
+/// Collections stored in a JSON file, or in memory.
+pub struct Collections {
+    path: Option<PathBuf>,
+    collections: Mutex<BTreeMap<String, Collection>>,
+}
+
+impl Collections {
+    /// The collections stored at `path`, created on the first change.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let collections: Vec<Collection> = match fs::read(&path) {
+            Ok(content) => serde_json::from_slice(&content)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => vec![],
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Collections {
+            path: Some(path),
+            collections: Mutex::new(
+                collections
+                    .into_iter()
+                    .map(|collection| (collection.name.clone(), collection))
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Collections kept in memory, lost when dropped.
+    pub fn in_memory() -> Self {
+        Collections {
+            path: None,
+            collections: Mutex::default(),
+        }
+    }
+
+    fn collections(&self) -> MutexGuard<'_, BTreeMap<String, Collection>> {
+        lock(&self.collections)
+    }
+
+    /// Write the collections to a temporary file first, so they aren't lost if
+    /// writing is interrupted.
+    fn save(&self, collections: &BTreeMap<String, Collection>) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let collections: Vec<&Collection> = collections.values().collect();
+        let temporary = path.with_extension("tmp");
+        fs::write(
+            &temporary,
+            serde_json::to_vec_pretty(&collections).map_err(std::io::Error::from)?,
+        )?;
+        fs::rename(temporary, path)?;
+        Ok(())
+    }
+
+    /// Change a collection and save the result.
+    fn update<T>(&self, name: &str, change: impl FnOnce(&mut Collection) -> T) -> Result<T, Error> {
+        let mut collections = self.collections();
+        let collection = collections.get_mut(name).ok_or_else(|| not_found(name))?;
+        let result = change(collection);
+        self.save(&collections)?;
+        Ok(result)
+    }
+
+    /// Create an empty collection.
+    pub fn create(&self, name: &str) -> Result<Collection, Error> {
+        let mut collections = self.collections();
+        if collections.contains_key(name) {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("a collection is already named {}", name),
+            )));
+        }
+        let collection = Collection::new(name);
+        collections.insert(name.to_string(), collection.clone());
+        self.save(&collections)?;
+        Ok(collection)
+    }
+
+    /// Store a collection, replacing the one with the same name.
+    pub fn insert(&self, collection: Collection) -> Result<(), Error> {
+        let mut collections = self.collections();
+        collections.insert(collection.name.clone(), collection);
+        self.save(&collections)
+    }
+
+    /// Delete a collection, returning it. Its pastes and index paste are kept.
+    pub fn delete(&self, name: &str) -> Result<Option<Collection>, Error> {
+        let mut collections = self.collections();
+        let collection = collections.remove(name);
+        self.save(&collections)?;
+        Ok(collection)
+    }
+
+    /// Add a paste to a collection, returning whether it wasn't in it yet.
+    pub fn add(&self, name: &str, id: &str) -> Result<bool, Error> {
+        self.update(name, |collection| collection.add(id))
+    }
+
+    /// Remove a paste from a collection, returning whether it was in it.
+    pub fn remove(&self, name: &str, id: &str) -> Result<bool, Error> {
+        self.update(name, |collection| collection.remove(id))
+    }
+
+    /// A collection, by name.
+    pub fn get(&self, name: &str) -> Option<Collection> {
+        self.collections().get(name).cloned()
+    }
+
+    /// Every collection, sorted by name.
+    pub fn list(&self) -> Vec<Collection> {
+        self.collections().values().cloned().collect()
+    }
+}
+
+impl fmt::Debug for Collections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collections")
+            .field("path", &self.path)
+            .field(
+                "collections",
+                &self.collections().keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Client {
+    /// Create an index paste listing the pastes of a collection, and remember it in
+    /// the collection.
+    ///
+    /// The index paste holds a Markdown list of the pastes' URLs and the collection
+    /// itself as [`COLLECTION_FILENAME`], read back by [`get_collection`](Self::get_collection).
+    /// The previous index paste, if any, is deleted, which requires owning it.
+    pub async fn mirror_collection(
+        &self,
+        collections: &Collections,
+        name: &str,
+    ) -> Result<PasteResult, Error> {
+        let mut collection = collections.get(name).ok_or_else(|| not_found(name))?;
+        let previous = collection.index_paste.take();
+        let mut listing = format!("# {}\n\n", collection.name);
+        for id in &collection.pastes {
+            listing.push_str(&format!("- {}\n", self.paste_url(id)));
+        }
+        // other fields depend on the enabled features
+        #[allow(clippy::needless_update)]
+        let request = CreatePasteRequest {
+            files: vec![
+                File {
+                    filename: "README.md".to_string(),
+                    content: listing,
+                    syntax: None,
+                },
+                File {
+                    filename: COLLECTION_FILENAME.to_string(),
+                    content: serde_json::to_string_pretty(&collection)
+                        .map_err(std::io::Error::from)?,
+                    syntax: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let paste = self.create(request).await?;
+        collections.update(name, |stored| stored.index_paste = Some(paste.id.clone()))?;
+        if let Some(previous) = previous {
+            self.delete_paste(&previous).await?;
+        }
+        Ok(paste)
+    }
+
+    /// Read a collection back from its index paste, e.g. one shared by someone else.
+    pub async fn get_collection(&self, index_paste: &str) -> Result<Collection, Error> {
+        let paste = self.get_paste(|p| p.id(index_paste)).await?;
+        let file = paste
+            .files
+            .iter()
+            .find(|file| file.filename == COLLECTION_FILENAME)
+            .ok_or_else(|| Error::FileNotFound(COLLECTION_FILENAME.to_string()))?;
+        let mut collection: Collection = serde_json::from_str(&file.content)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        collection.index_paste = Some(paste.id);
+        Ok(collection)
+    }
+}
@@ -0,0 +1,363 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "config")))]
+
+//! Reading the settings of a client from a TOML file, so tools built on myust share
+//! one configuration.
+//!
+//! The file is read from `$MYUST_CONFIG`, or else `$XDG_CONFIG_HOME/myust/config.toml`,
+//! or else `~/.config/myust/config.toml`:
+//!
+//! ```toml
+//! token = "YOUR_MYSTBIN_TOKEN"
+//! base_url = "https://api.mystb.in"
+//! expires = "7d"
+//! provider = "mystbin"
+//!
+//! [providers.gist]
+//! token = "ghp_..."
+//!
+//! [profiles.work]
+//! token = "WORK_MYSTBIN_TOKEN"
+//! base_url = "https://mystbin.work.example"
+//! ```
+//!
+//! Every setting is optional, and these environment variables take precedence over
+//! the file: `MYSTBIN_TOKEN`, `MYUST_BASE_URL`, `MYUST_WEB_URL`, `MYUST_EXPIRES` and
+//! `MYUST_PROVIDER`. `MYUST_PROFILE` selects a [`Profile`], whose settings replace the
+//! top-level ones.
+//!
+//! ```rust,no_run
+//! use myust::{config::Config, Client};
+//!
+//! # fn run() -> Result<(), myust::Error> {
+//! let client = Client::from_config(&Config::load()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{utils::redact_option, Client, Error, Expiry, PasteProvider};
+
+/// Settings read from a configuration file and the environment.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The mystb.in API token.
+    pub token: Option<String>,
+    /// The URL of the API, for self-hosted instances.
+    pub base_url: Option<String>,
+    /// The URL pastes are viewed on.
+    pub web_url: Option<String>,
+    /// How long pastes last when created without an expiration date, e.g. `"1d"`.
+    #[serde(deserialize_with = "duration")]
+    pub expires: Option<Duration>,
+    /// The name of the provider to use, see [`paste_provider`](Self::paste_provider).
+    pub provider: Option<String>,
+    /// The settings of each provider, by name.
+    pub providers: HashMap<String, HashMap<String, String>>,
+    /// Named sets of settings, e.g. for a work and a personal account.
+    pub profiles: HashMap<String, Profile>,
+}
+
+/// Settings of a named profile, replacing the top-level ones of a [`Config`] when
+/// set.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// The mystb.in API token.
+    pub token: Option<String>,
+    /// The URL of the API, for self-hosted instances.
+    pub base_url: Option<String>,
+    /// The URL pastes are viewed on.
+    pub web_url: Option<String>,
+    /// How long pastes last when created without an expiration date, e.g. `"1d"`.
+    #[serde(deserialize_with = "duration")]
+    pub expires: Option<Duration>,
+}
+
+fn duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(duration) => humantime::parse_duration(&duration)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+fn invalid(err: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+impl Config {
+    /// The path of the configuration file.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("MYUST_CONFIG") {
+            return Some(path.into());
+        }
+        let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(config_dir.join("myust").join("config.toml"))
+    }
+
+    /// Read the configuration file, if it exists, and apply the environment variables.
+    pub fn load() -> Result<Config, Error> {
+        match Self::path() {
+            Some(path) => Self::load_from(path),
+            None => Self::default().with_env(),
+        }
+    }
+
+    /// Read a configuration file, if it exists, and apply the environment variables.
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Config, Error> {
+        let config = Self::read(path.as_ref())?;
+        match env::var("MYUST_PROFILE") {
+            Ok(name) if !name.is_empty() => config.profile(&name)?.with_env(),
+            _ => config.with_env(),
+        }
+    }
+
+    /// Read the configuration file with the settings of a profile, and apply the
+    /// environment variables.
+    pub fn load_profile(name: &str) -> Result<Config, Error> {
+        let config = match Self::path() {
+            Some(path) => Self::read(&path)?,
+            None => Self::default(),
+        };
+        config.profile(name)?.with_env()
+    }
+
+    fn read(path: &Path) -> Result<Config, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// This configuration with the settings of a profile replacing the top-level ones.
+    pub fn profile(&self, name: &str) -> Result<Config, Error> {
+        let profile = self.profiles.get(name).ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no profile is named {}", name),
+            ))
+        })?;
+        let mut config = self.clone();
+        if let Some(token) = &profile.token {
+            config.token = Some(token.clone());
+        }
+        if let Some(base_url) = &profile.base_url {
+            config.base_url = Some(base_url.clone());
+        }
+        if let Some(web_url) = &profile.web_url {
+            config.web_url = Some(web_url.clone());
+        }
+        if let Some(expires) = profile.expires {
+            config.expires = Some(expires);
+        }
+        Ok(config)
+    }
+
+    fn with_env(mut self) -> Result<Config, Error> {
+        let var = |name| env::var(name).ok().filter(|value| !value.is_empty());
+        if let Some(token) = var("MYSTBIN_TOKEN") {
+            self.token = Some(token);
+        }
+        if let Some(base_url) = var("MYUST_BASE_URL") {
+            self.base_url = Some(base_url);
+        }
+        if let Some(web_url) = var("MYUST_WEB_URL") {
+            self.web_url = Some(web_url);
+        }
+        if let Some(expires) = var("MYUST_EXPIRES") {
+            self.expires = Some(humantime::parse_duration(&expires).map_err(invalid)?);
+        }
+        if let Some(provider) = var("MYUST_PROVIDER") {
+            self.provider = Some(provider);
+        }
+        Ok(self)
+    }
+
+    /// A setting of a provider, e.g. `config.provider_setting("gist", "token")`.
+    pub fn provider_setting(&self, provider: &str, key: &str) -> Option<&str> {
+        self.providers.get(provider)?.get(key).map(String::as_str)
+    }
+
+    /// The configured provider, mystb.in by default.
+    ///
+    /// Other providers need their feature enabled: `gist` (setting `token`),
+    /// `hastebin` (`base_url`, `token`), `dpaste` (`base_url`, `token`) and `pastebin`
+    /// (`dev_key`, `user_key`).
+    pub fn paste_provider(&self) -> Result<Box<dyn PasteProvider>, Error> {
+        #[allow(unused_variables)]
+        let setting = |key| self.provider_setting(self.provider.as_deref().unwrap_or(""), key);
+        match self.provider.as_deref().unwrap_or("mystbin") {
+            "mystbin" => Ok(Box::new(Client::from_config(self))),
+            #[cfg(feature = "gist")]
+            "gist" => {
+                let mut gist = crate::gist::GistProvider::new();
+                if let Some(token) = setting("token") {
+                    gist = gist.token(token);
+                }
+                Ok(Box::new(gist))
+            }
+            #[cfg(feature = "haste")]
+            "hastebin" => {
+                let base_url = setting("base_url").ok_or(Error::Unsupported(
+                    "the hastebin provider needs a base_url setting",
+                ))?;
+                let mut haste = crate::haste::HastebinProvider::new(base_url);
+                if let Some(token) = setting("token") {
+                    haste = haste.token(token);
+                }
+                Ok(Box::new(haste))
+            }
+            #[cfg(feature = "haste")]
+            "dpaste" => {
+                let mut dpaste = crate::haste::DpasteProvider::new();
+                if let Some(base_url) = setting("base_url") {
+                    dpaste = dpaste.base_url(base_url);
+                }
+                if let Some(token) = setting("token") {
+                    dpaste = dpaste.token(token);
+                }
+                Ok(Box::new(dpaste))
+            }
+            #[cfg(feature = "pastebin")]
+            "pastebin" => {
+                let dev_key = setting("dev_key").ok_or(Error::Unsupported(
+                    "the pastebin provider needs a dev_key setting",
+                ))?;
+                let mut pastebin = crate::pastebin::PastebinProvider::new(dev_key);
+                if let Some(user_key) = setting("user_key") {
+                    pastebin = pastebin.user_key(user_key);
+                }
+                Ok(Box::new(pastebin))
+            }
+            _ => Err(Error::Unsupported(
+                "the configured provider is unknown, or its feature isn't enabled",
+            )),
+        }
+    }
+}
+
+impl FromStr for Config {
+    type Err = Error;
+
+    /// Parse a configuration file's content, without applying the environment.
+    fn from_str(toml: &str) -> Result<Self, Self::Err> {
+        toml::from_str(toml).map_err(invalid)
+    }
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("token", &redact_option(&self.token))
+            .field("base_url", &self.base_url)
+            .field("web_url", &self.web_url)
+            .field("expires", &self.expires)
+            .field("provider", &self.provider)
+            .field("providers", &self.providers.keys().collect::<Vec<_>>())
+            .field("profiles", &self.profiles)
+            .finish()
+    }
+}
+
+impl fmt::Debug for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profile")
+            .field("token", &redact_option(&self.token))
+            .field("base_url", &self.base_url)
+            .field("web_url", &self.web_url)
+            .field("expires", &self.expires)
+            .finish()
+    }
+}
+
+impl Client {
+    /// Instantiate a client with the settings of a [`Config`].
+    ///
+    /// Unlike `auth`, the token isn't checked until it's used.
+    pub fn from_config(config: &Config) -> Client {
+        Client::new().configure(config)
+    }
+
+    /// Instantiate a client with the settings of a profile of the configuration
+    /// file, see [`Config::load_profile`].
+    pub fn from_profile(name: &str) -> Result<Client, Error> {
+        Ok(Client::from_config(&Config::load_profile(name)?))
+    }
+
+    /// Instantiate a client with the settings of a [`Config`] that shares this
+    /// client's connection pool, see [`share_pool`](Self::share_pool).
+    pub fn with_config(&self, config: &Config) -> Client {
+        self.share_pool().configure(config)
+    }
+
+    fn configure(self, config: &Config) -> Client {
+        let mut client = self;
+        if let Some(base_url) = &config.base_url {
+            client = client.base_url(base_url);
+        }
+        if let Some(web_url) = &config.web_url {
+            client = client.web_url(web_url);
+        }
+        if let Some(expires) = config.expires {
+            client = client.default_expiry(Expiry::from(expires));
+        }
+        if let Some(token) = &config.token {
+            client = client.with_token(token);
+        }
+        client
+    }
+}
+
+#[cfg(feature = "sync")]
+impl crate::SyncClient {
+    /// Instantiate a client with the settings of a [`Config`].
+    ///
+    /// Unlike `auth`, the token isn't checked until it's used.
+    pub fn from_config(config: &Config) -> crate::SyncClient {
+        crate::SyncClient::new().configure(config)
+    }
+
+    /// Instantiate a client with the settings of a profile of the configuration
+    /// file, see [`Config::load_profile`].
+    pub fn from_profile(name: &str) -> Result<crate::SyncClient, Error> {
+        Ok(crate::SyncClient::from_config(&Config::load_profile(name)?))
+    }
+
+    /// Instantiate a client with the settings of a [`Config`] that shares this
+    /// client's connection pool, see [`share_pool`](Self::share_pool).
+    pub fn with_config(&self, config: &Config) -> crate::SyncClient {
+        self.share_pool().configure(config)
+    }
+
+    fn configure(self, config: &Config) -> crate::SyncClient {
+        let mut client = self;
+        if let Some(base_url) = &config.base_url {
+            client = client.base_url(base_url);
+        }
+        if let Some(web_url) = &config.web_url {
+            client = client.web_url(web_url);
+        }
+        if let Some(expires) = config.expires {
+            client = client.default_expiry(Expiry::from(expires));
+        }
+        if let Some(token) = &config.token {
+            client = client.with_token(token);
+        }
+        client
+    }
+}
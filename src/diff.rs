@@ -0,0 +1,237 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "diff")))]
+
+//! Comparing a paste with a local file or another paste, e.g. to check whether a
+//! shared snippet drifted from the repository.
+//!
+//! ```rust,no_run
+//! use myust::{diff::DiffTarget, Client};
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new();
+//! let diff = client
+//!     .diff_paste("EquipmentMovingExpensive", DiffTarget::path("src/main.rs"))
+//!     .await?;
+//! if !diff.is_unchanged() {
+//!     print!("{}", diff);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use similar::{ChangeTag, TextDiff};
+
+use crate::{Client, Error, File};
+
+/// What a paste is compared with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffTarget {
+    /// A local file, compared with the paste's file of the same name, or with its
+    /// only file.
+    Path(PathBuf),
+    /// Another paste, whose files are matched by name.
+    Paste(String),
+}
+
+impl DiffTarget {
+    /// A local file.
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        DiffTarget::Path(path.into())
+    }
+
+    /// Another paste, by ID.
+    pub fn paste(id: impl Into<String>) -> Self {
+        DiffTarget::Paste(id.into())
+    }
+}
+
+/// How a file differs between both sides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The file is only on the compared side.
+    Added,
+    /// The file is only in the paste.
+    Removed,
+    /// The file's content changed.
+    Modified,
+    /// The file's content is the same.
+    Unchanged,
+}
+
+/// The difference of one file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileDiff {
+    /// The file's name.
+    pub filename: String,
+    /// How the file differs.
+    pub status: FileStatus,
+    /// The number of added lines.
+    pub insertions: usize,
+    /// The number of removed lines.
+    pub deletions: usize,
+    /// The unified diff, empty if the file is unchanged.
+    pub unified: String,
+}
+
+/// The difference between a paste and what it's compared with, see
+/// [`Client::diff_paste`].
+///
+/// Its `Display` implementation prints the unified diffs of every changed file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PasteDiff {
+    /// The files, in the order of the paste then of the compared side.
+    pub files: Vec<FileDiff>,
+}
+
+impl PasteDiff {
+    /// Whether both sides have the same files with the same content.
+    pub fn is_unchanged(&self) -> bool {
+        self.files
+            .iter()
+            .all(|file| file.status == FileStatus::Unchanged)
+    }
+
+    /// The total number of added lines.
+    pub fn insertions(&self) -> usize {
+        self.files.iter().map(|file| file.insertions).sum()
+    }
+
+    /// The total number of removed lines.
+    pub fn deletions(&self) -> usize {
+        self.files.iter().map(|file| file.deletions).sum()
+    }
+}
+
+impl fmt::Display for PasteDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for file in &self.files {
+            f.write_str(&file.unified)?;
+        }
+        Ok(())
+    }
+}
+
+/// Diff one file, `None` standing for a side the file is missing from. Each side is
+/// a label for the diff header and the content.
+fn diff_file(filename: &str, old: Option<(&str, &str)>, new: Option<(&str, &str)>) -> FileDiff {
+    let (old_label, old_content) = old.unwrap_or(("/dev/null", ""));
+    let (new_label, new_content) = new.unwrap_or(("/dev/null", ""));
+    let diff = TextDiff::from_lines(old_content, new_content);
+    let (mut insertions, mut deletions) = (0, 0);
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => insertions += 1,
+            ChangeTag::Delete => deletions += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    let status = match (old, new) {
+        (None, _) => FileStatus::Added,
+        (_, None) => FileStatus::Removed,
+        _ if old_content == new_content => FileStatus::Unchanged,
+        _ => FileStatus::Modified,
+    };
+    let unified = match status {
+        FileStatus::Unchanged => String::new(),
+        _ => diff.unified_diff().header(old_label, new_label).to_string(),
+    };
+    FileDiff {
+        filename: filename.to_string(),
+        status,
+        insertions,
+        deletions,
+        unified,
+    }
+}
+
+/// Compare the files of a paste with those of another side, matched by name.
+fn diff_files(id: &str, old: &[File], other: &str, new: &[File]) -> PasteDiff {
+    let label = |id: &str, file: &File| format!("{}/{}", id, file.filename);
+    let mut files = vec![];
+    for file in old {
+        let new_file = new
+            .iter()
+            .find(|n| n.filename == file.filename)
+            .map(|n| (label(other, n), n.content.as_str()));
+        files.push(diff_file(
+            &file.filename,
+            Some((&label(id, file), &file.content)),
+            new_file
+                .as_ref()
+                .map(|(label, content)| (label.as_str(), *content)),
+        ));
+    }
+    for file in new
+        .iter()
+        .filter(|n| !old.iter().any(|o| o.filename == n.filename))
+    {
+        files.push(diff_file(
+            &file.filename,
+            None,
+            Some((&label(other, file), &file.content)),
+        ));
+    }
+    PasteDiff { files }
+}
+
+/// Compare a paste with a local file.
+fn diff_path(id: &str, old: &[File], path: &Path) -> Result<PasteDiff, Error> {
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let file = match old {
+        [only] => only,
+        _ => old
+            .iter()
+            .find(|file| file.filename == filename)
+            .ok_or_else(|| Error::FileNotFound(filename.clone()))?,
+    };
+    let content = fs::read_to_string(path)?;
+    Ok(PasteDiff {
+        files: vec![diff_file(
+            &file.filename,
+            Some((&format!("{}/{}", id, file.filename), &file.content)),
+            Some((&path.display().to_string(), &content)),
+        )],
+    })
+}
+
+impl Client {
+    /// Compare a paste with a local file or another paste.
+    ///
+    /// The paste is the old side of the diff. Pastes with a password can't be
+    /// compared.
+    pub async fn diff_paste(&self, id: &str, target: DiffTarget) -> Result<PasteDiff, Error> {
+        let paste = self.get_paste(|p| p.id(id)).await?;
+        match target {
+            DiffTarget::Path(path) => diff_path(id, &paste.files, &path),
+            DiffTarget::Paste(other) => {
+                let other_paste = self.get_paste(|p| p.id(&other)).await?;
+                Ok(diff_files(id, &paste.files, &other, &other_paste.files))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+impl crate::SyncClient {
+    /// Compare a paste with a local file or another paste.
+    ///
+    /// The paste is the old side of the diff. Pastes with a password can't be
+    /// compared.
+    pub fn diff_paste(&self, id: &str, target: DiffTarget) -> Result<PasteDiff, Error> {
+        let paste = self.get_paste(|p| p.id(id))?;
+        match target {
+            DiffTarget::Path(path) => diff_path(id, &paste.files, &path),
+            DiffTarget::Paste(other) => {
+                let other_paste = self.get_paste(|p| p.id(&other))?;
+                Ok(diff_files(id, &paste.files, &other, &other_paste.files))
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+
+//! Client-side encryption of paste contents with [age](https://age-encryption.org).
+//!
+//! Register an [`Encryption`] key on a client and every file it uploads is encrypted
+//! before leaving the machine, then decrypted again by `get_paste`:
+//!
+//! ```rust,no_run
+//! use myust::{encryption::Encryption, Client};
+//!
+//! let client = Client::new().encryption(Encryption::passphrase("correct horse"));
+//! ```
+//!
+//! Encrypted files are stored as ASCII-armored age files, so they can also be
+//! decrypted with the `age` command line tool.
+
+use std::{
+    fmt,
+    io::{Read, Write},
+    iter,
+};
+
+use age::{
+    armor::{ArmoredReader, ArmoredWriter, Format},
+    scrypt,
+    secrecy::SecretString,
+    Decryptor, Encryptor,
+};
+
+/// age's X25519 keys, to create identities for [`Encryption::x25519`].
+pub use age::x25519;
+
+use crate::{utils::REDACTED, Error, File};
+
+/// The first line of every encrypted file, used to detect encrypted pastes.
+pub const HEADER: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
+
+enum Key {
+    Passphrase(SecretString),
+    X25519(x25519::Identity),
+}
+
+/// The key used to encrypt files before upload and decrypt them on `get_paste`.
+pub struct Encryption {
+    key: Key,
+    work_factor: Option<u8>,
+}
+
+impl Encryption {
+    /// Encrypt with a passphrase. Anyone with the passphrase can decrypt the files.
+    pub fn passphrase(passphrase: impl Into<String>) -> Self {
+        Encryption {
+            key: Key::Passphrase(SecretString::from(passphrase.into())),
+            work_factor: None,
+        }
+    }
+
+    /// Encrypt to an age X25519 key. Only the holder of the identity can decrypt
+    /// the files.
+    pub fn x25519(identity: x25519::Identity) -> Self {
+        Encryption {
+            key: Key::X25519(identity),
+            work_factor: None,
+        }
+    }
+
+    /// Set the scrypt work factor of passphrase encryption to `2^log_n`, between
+    /// 1 and 63.
+    ///
+    /// Defaults to a value taking about a second on the current machine.
+    pub fn work_factor(mut self, log_n: u8) -> Self {
+        self.work_factor = Some(log_n.clamp(1, 63));
+        self
+    }
+
+    /// Whether some content was encrypted by myust.
+    pub fn is_encrypted(content: &str) -> bool {
+        content.starts_with(HEADER)
+    }
+
+    /// Encrypt some content into an armored age file.
+    pub fn encrypt(&self, content: &str) -> Result<String, Error> {
+        let encryptor = match &self.key {
+            Key::Passphrase(passphrase) => {
+                let mut recipient = scrypt::Recipient::new(passphrase.clone());
+                if let Some(log_n) = self.work_factor {
+                    recipient.set_work_factor(log_n);
+                }
+                Encryptor::with_recipients(iter::once(&recipient as _))
+            }
+            Key::X25519(identity) => {
+                Encryptor::with_recipients(iter::once(&identity.to_public() as _))
+            }
+        }
+        .map_err(encryption_error)?;
+
+        let mut encrypted = vec![];
+        let armor = ArmoredWriter::wrap_output(&mut encrypted, Format::AsciiArmor)?;
+        let mut writer = encryptor.wrap_output(armor)?;
+        writer.write_all(content.as_bytes())?;
+        writer.finish()?.finish()?;
+        String::from_utf8(encrypted).map_err(encryption_error)
+    }
+
+    /// Decrypt an armored age file.
+    pub fn decrypt(&self, content: &str) -> Result<String, Error> {
+        let decryptor = Decryptor::new_buffered(ArmoredReader::new(content.as_bytes()))
+            .map_err(encryption_error)?;
+        let mut reader = match &self.key {
+            Key::Passphrase(passphrase) => {
+                let mut identity = scrypt::Identity::new(passphrase.clone());
+                if let Some(log_n) = self.work_factor {
+                    identity.set_max_work_factor(log_n.max(22));
+                }
+                decryptor.decrypt(iter::once(&identity as _))
+            }
+            Key::X25519(identity) => decryptor.decrypt(iter::once(identity as _)),
+        }
+        .map_err(encryption_error)?;
+
+        let mut decrypted = String::new();
+        reader.read_to_string(&mut decrypted)?;
+        Ok(decrypted)
+    }
+
+    /// Encrypt the content of every file.
+    pub(crate) fn encrypt_files(&self, files: &[File]) -> Result<Vec<File>, Error> {
+        files
+            .iter()
+            .map(|file| {
+                Ok(File {
+                    filename: file.filename.clone(),
+                    content: self.encrypt(&file.content)?,
+                    syntax: file.syntax.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Decrypt the content of every encrypted file, leaving the others as they are.
+    pub(crate) fn decrypt_files(&self, mut files: Vec<File>) -> Result<Vec<File>, Error> {
+        for file in files.iter_mut() {
+            if Self::is_encrypted(&file.content) {
+                file.content = self.decrypt(&file.content)?;
+            }
+        }
+        Ok(files)
+    }
+}
+
+fn encryption_error(err: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::Encryption(Box::new(err))
+}
+
+impl fmt::Debug for Encryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self.key {
+            Key::Passphrase(_) => "Passphrase",
+            Key::X25519(_) => "X25519",
+        };
+        f.debug_struct("Encryption")
+            .field("key", &format_args!("{}({})", key, REDACTED))
+            .field("work_factor", &self.work_factor)
+            .finish()
+    }
+}
@@ -0,0 +1,404 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "gist")))]
+
+//! A [`PasteProvider`] for [GitHub Gists](https://gist.github.com), so an application can
+//! switch between mystb.in and gists with a configuration flag.
+//!
+//! ```rust,no_run
+//! use myust::{gist::GistProvider, Client, PasteProvider};
+//!
+//! fn provider(use_gist: bool) -> Box<dyn PasteProvider> {
+//!     match use_gist {
+//!         true => Box::new(GistProvider::new().token("ghp_...")),
+//!         false => Box::new(Client::new()),
+//!     }
+//! }
+//! ```
+//!
+//! Gists have no passwords or expiration dates, creating a paste with either fails
+//! with [`Error::Unsupported`]. The description of a new gist lists its filenames,
+//! unless one is set with [`GistProvider::description`].
+
+use std::fmt;
+
+use async_trait::async_trait;
+use reqwest::{header, Method};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    provider::http_error, Client, CreatePasteRequest, Error, File, PasteProvider, PasteResult,
+    MAX_FILES,
+};
+
+/// The default URL of the GitHub API.
+pub const API_URL: &str = "https://api.github.com";
+/// The default URL gists are viewed on.
+pub const WEB_URL: &str = "https://gist.github.com";
+
+const API_VERSION: &str = "2022-11-28";
+
+/// The name of the file holding a gist's description in a migrated paste.
+pub const DESCRIPTION_FILENAME: &str = "description.txt";
+
+/// How many gists are listed per request.
+const PAGE_SIZE: usize = 100;
+
+/// A client for GitHub Gists.
+#[derive(Clone)]
+pub struct GistProvider {
+    inner: reqwest::Client,
+    token: Option<String>,
+    api_url: String,
+    web_url: String,
+    public: bool,
+    description: Option<String>,
+}
+
+impl GistProvider {
+    /// Instantiate a provider without a token, which can only read public gists.
+    pub fn new() -> Self {
+        GistProvider {
+            inner: reqwest::Client::new(),
+            token: None,
+            api_url: API_URL.to_string(),
+            web_url: WEB_URL.to_string(),
+            public: false,
+            description: None,
+        }
+    }
+
+    /// Authenticate with a GitHub token allowed to manage gists.
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Use a different API URL, e.g. a GitHub Enterprise instance.
+    ///
+    /// Defaults to [`API_URL`].
+    pub fn api_url(mut self, url: impl Into<String>) -> Self {
+        self.api_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use a different URL for viewing gists. Defaults to [`WEB_URL`].
+    pub fn web_url(mut self, url: impl Into<String>) -> Self {
+        self.web_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Create public gists instead of secret ones. Defaults to `false`.
+    pub fn public(mut self, public: bool) -> Self {
+        self.public = public;
+        self
+    }
+
+    /// The description of every gist created, instead of their filenames.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value, Error> {
+        let mut request = self
+            .inner
+            .request(method, format!("{}{}", self.api_url, path))
+            .header(header::ACCEPT, "application/vnd.github+json")
+            .header(header::USER_AGENT, "myust")
+            .header("X-GitHub-Api-Version", API_VERSION);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.map_err(http_error)?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(http_error)?;
+        let json: Value = serde_json::from_slice(&body).unwrap_or_default();
+        match status {
+            status if status.is_success() => Ok(json),
+            _ => Err(Error::Provider {
+                provider: "gist",
+                code: status,
+                message: json
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .map(|m| m.to_string()),
+            }),
+        }
+    }
+
+    /// Get a gist, with the full content of the files GitHub truncates, over about
+    /// a megabyte.
+    async fn get_gist(&self, id: &str) -> Result<Value, Error> {
+        let mut gist = self
+            .request(Method::GET, &format!("/gists/{}", id), None)
+            .await?;
+        let files = gist
+            .get_mut("files")
+            .and_then(Value::as_object_mut)
+            .into_iter()
+            .flat_map(|files| files.values_mut());
+        for file in files.filter_map(Value::as_object_mut) {
+            if file.get("truncated").and_then(Value::as_bool) != Some(true) {
+                continue;
+            }
+            let raw_url = file.get("raw_url").and_then(Value::as_str).ok_or_else(|| {
+                Error::InvalidResponse("a truncated gist file has no raw_url".to_string())
+            })?;
+            let content = self.raw(raw_url).await?;
+            file.insert("content".to_string(), Value::String(content));
+        }
+        Ok(gist)
+    }
+
+    /// Download the content of a gist file from its `raw_url`.
+    async fn raw(&self, url: &str) -> Result<String, Error> {
+        let response = self
+            .inner
+            .get(url)
+            .header(header::USER_AGENT, "myust")
+            .send()
+            .await
+            .map_err(http_error)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Provider {
+                provider: "gist",
+                code: status,
+                message: None,
+            });
+        }
+        response.text().await.map_err(http_error)
+    }
+}
+
+/// Whether `source` is a gist ID rather than a username.
+fn is_gist_id(source: &str) -> bool {
+    source.len() >= 20 && source.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl GistProvider {
+    /// The IDs of every gist of a user visible with this provider's token.
+    async fn user_gists(&self, user: &str) -> Result<Vec<String>, Error> {
+        let mut ids = vec![];
+        for page in 1.. {
+            let path = format!("/users/{}/gists?per_page={}&page={}", user, PAGE_SIZE, page);
+            let gists = self.request(Method::GET, &path, None).await?;
+            let gists = gists.as_array().map(Vec::as_slice).unwrap_or_default();
+            ids.extend(
+                gists
+                    .iter()
+                    .filter_map(|gist| gist["id"].as_str().map(|id| id.to_string())),
+            );
+            if gists.len() < PAGE_SIZE {
+                break;
+            }
+        }
+        Ok(ids)
+    }
+}
+
+/// A gist copied to mystb.in by [`Client::migrate_from_gist`].
+#[derive(Clone, Debug)]
+pub struct GistMigration {
+    /// The URL of the gist.
+    pub gist_url: String,
+    /// The URL of the new paste.
+    pub paste_url: String,
+    /// The new paste.
+    pub paste: PasteResult,
+}
+
+/// The outcome of [`Client::migrate_from_gist`].
+#[derive(Debug, Default)]
+pub struct MigrationResult {
+    /// The gists that were copied.
+    pub succeeded: Vec<GistMigration>,
+    /// The URLs of the gists that couldn't be copied, with the reason.
+    pub failed: Vec<(String, Error)>,
+}
+
+impl Client {
+    /// Copy gists to mystb.in as multi-file pastes: one gist by its ID or URL, or
+    /// every gist of a user.
+    ///
+    /// Filenames are kept, and a gist's description is added as
+    /// [`DESCRIPTION_FILENAME`] when there's room for it under [`MAX_FILES`].
+    /// Files the API truncates, over about a megabyte, are downloaded in full.
+    /// A gist that can't be copied is reported without stopping the others.
+    ///
+    /// ```rust,no_run
+    /// use myust::{gist::GistProvider, Client};
+    ///
+    /// # async fn run() -> Result<(), myust::Error> {
+    /// let gists = GistProvider::new().token("ghp_...");
+    /// let migrated = Client::new().migrate_from_gist(&gists, "octocat").await?;
+    /// for migration in migrated.succeeded {
+    ///     println!("{} -> {}", migration.gist_url, migration.paste_url);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn migrate_from_gist(
+        &self,
+        gists: &GistProvider,
+        gist_id_or_user: &str,
+    ) -> Result<MigrationResult, Error> {
+        let source = gist_id_or_user.trim_end_matches('/');
+        let source = source.rsplit('/').next().unwrap_or(source);
+        let ids = match is_gist_id(source) {
+            true => vec![source.to_string()],
+            false => gists.user_gists(source).await?,
+        };
+        let mut result = MigrationResult::default();
+        for id in ids {
+            let gist_url = gists.url_for(&id);
+            match self.migrate_gist(gists, &id).await {
+                Ok(paste) => result.succeeded.push(GistMigration {
+                    gist_url,
+                    paste_url: self.paste_url(&paste.id),
+                    paste,
+                }),
+                Err(err) => result.failed.push((gist_url, err)),
+            }
+        }
+        Ok(result)
+    }
+
+    async fn migrate_gist(&self, gists: &GistProvider, id: &str) -> Result<PasteResult, Error> {
+        let mut gist = gists.get_gist(id).await?;
+        let description = match gist.get_mut("description").map(Value::take) {
+            Some(Value::String(description)) => description,
+            _ => String::new(),
+        };
+        let mut files = gist_result(gist).files;
+        if !description.trim().is_empty() && files.len() < MAX_FILES {
+            files.push(File {
+                filename: DESCRIPTION_FILENAME.to_string(),
+                content: description,
+                syntax: None,
+            });
+        }
+        self.create(CreatePasteRequest {
+            files,
+            ..Default::default()
+        })
+        .await
+    }
+}
+
+/// Read a gist returned by the API.
+fn gist_result(mut gist: Value) -> PasteResult {
+    let files = match gist.get_mut("files").map(Value::take) {
+        Some(Value::Object(files)) => files
+            .into_iter()
+            .map(|(filename, mut file)| File {
+                filename,
+                content: match file.get_mut("content").map(Value::take) {
+                    Some(Value::String(content)) => content,
+                    _ => String::new(),
+                },
+                syntax: None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+    PasteResult {
+        created_at: gist
+            .get("created_at")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        expires: None,
+        files,
+        id: gist
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        idempotency_key: None,
+        line_endings: None,
+    }
+}
+
+#[async_trait]
+impl PasteProvider for GistProvider {
+    fn name(&self) -> &str {
+        "gist"
+    }
+
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        if request.password.is_some() {
+            return Err(Error::Unsupported("gists can't have a password"));
+        }
+        if request
+            .expires
+            .as_ref()
+            .is_some_and(|expiry| !expiry.is_never())
+        {
+            return Err(Error::Unsupported("gists can't expire"));
+        }
+        let description = match &self.description {
+            Some(description) => description.clone(),
+            None => request
+                .files
+                .iter()
+                .map(|file| file.filename.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        let files: Map<String, Value> = request
+            .files
+            .iter()
+            .map(|file| (file.filename.clone(), json!({ "content": file.content })))
+            .collect();
+        let body = json!({
+            "description": description,
+            "public": self.public,
+            "files": files,
+        });
+        let gist = self.request(Method::POST, "/gists", Some(body)).await?;
+        Ok(gist_result(gist))
+    }
+
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error> {
+        if password.is_some() {
+            return Err(Error::Unsupported("gists can't have a password"));
+        }
+        Ok(gist_result(self.get_gist(id).await?))
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, &format!("/gists/{}", id), None)
+            .await
+            .map(|_| ())
+    }
+
+    fn url_for(&self, id: &str) -> String {
+        format!("{}/{}", self.web_url, id)
+    }
+}
+
+impl fmt::Debug for GistProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GistProvider")
+            .field("api_url", &self.api_url)
+            .field("web_url", &self.web_url)
+            .field("authenticated", &self.token.is_some())
+            .field("public", &self.public)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl Default for GistProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,273 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "haste")))]
+
+//! [`PasteProvider`]s for simple single-file services: hastebin and its many clones,
+//! and [dpaste](https://dpaste.com).
+//!
+//! ```rust,no_run
+//! use myust::{haste::HastebinProvider, PasteProvider};
+//!
+//! let provider: Box<dyn PasteProvider> =
+//!     Box::new(HastebinProvider::new("https://haste.internal.example"));
+//! ```
+//!
+//! These services store one file per paste, without a password. Creating a paste
+//! with several files or a password fails with [`Error::Unsupported`], as does
+//! deleting one.
+
+use std::{fmt, time::SystemTime};
+
+use async_trait::async_trait;
+use reqwest::{header, RequestBuilder, StatusCode};
+use serde_json::Value;
+
+use crate::{
+    provider::{send_text, single_file},
+    utils::format_timestamp,
+    CreatePasteRequest, Error, File, PasteProvider, PasteResult,
+};
+
+/// The default URL of dpaste.
+pub const DPASTE_URL: &str = "https://dpaste.com";
+
+/// The longest dpaste keeps a paste, in days.
+const DPASTE_MAX_DAYS: u64 = 365;
+
+fn now() -> String {
+    format_timestamp(humantime::format_rfc3339(SystemTime::now())).unwrap_or_default()
+}
+
+/// A client for hastebin and compatible services.
+#[derive(Clone)]
+pub struct HastebinProvider {
+    inner: reqwest::Client,
+    token: Option<String>,
+    base_url: String,
+}
+
+impl HastebinProvider {
+    /// Instantiate a provider for the hastebin instance at `base_url`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        HastebinProvider {
+            inner: reqwest::Client::new(),
+            token: None,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Authenticate with a token, for instances requiring one.
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+#[async_trait]
+impl PasteProvider for HastebinProvider {
+    fn name(&self) -> &str {
+        "hastebin"
+    }
+
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        if request
+            .expires
+            .as_ref()
+            .is_some_and(|expiry| !expiry.is_never())
+        {
+            return Err(Error::Unsupported("hastebin pastes can't expire"));
+        }
+        let file = single_file(request)?;
+        let request = self
+            .inner
+            .post(format!("{}/documents", self.base_url))
+            .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(file.content.clone());
+        let body = send_text("hastebin", self.authorize(request)).await?;
+        let json: Value = serde_json::from_str(&body).unwrap_or_default();
+        let key = json
+            .get("key")
+            .and_then(Value::as_str)
+            .ok_or(Error::Provider {
+                provider: "hastebin",
+                code: StatusCode::OK,
+                message: Some("the response has no key".to_string()),
+            })?;
+        Ok(PasteResult {
+            created_at: now(),
+            expires: None,
+            files: vec![file],
+            id: key.to_string(),
+            idempotency_key: None,
+            line_endings: None,
+        })
+    }
+
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error> {
+        if password.is_some() {
+            return Err(Error::Unsupported("this provider can't set a password"));
+        }
+        let request = self.inner.get(format!("{}/raw/{}", self.base_url, id));
+        let content = send_text("hastebin", self.authorize(request)).await?;
+        Ok(PasteResult {
+            created_at: String::new(),
+            expires: None,
+            files: vec![File {
+                filename: id.to_string(),
+                content,
+                syntax: None,
+            }],
+            id: id.to_string(),
+            idempotency_key: None,
+            line_endings: None,
+        })
+    }
+
+    async fn delete(&self, _id: &str) -> Result<(), Error> {
+        Err(Error::Unsupported("hastebin pastes can't be deleted"))
+    }
+
+    fn url_for(&self, id: &str) -> String {
+        format!("{}/{}", self.base_url, id)
+    }
+}
+
+impl fmt::Debug for HastebinProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HastebinProvider")
+            .field("base_url", &self.base_url)
+            .field("authenticated", &self.token.is_some())
+            .finish()
+    }
+}
+
+/// A client for dpaste.
+///
+/// The expiration date is rounded up to whole days, between 1 and 365.
+#[derive(Clone)]
+pub struct DpasteProvider {
+    inner: reqwest::Client,
+    token: Option<String>,
+    base_url: String,
+}
+
+impl DpasteProvider {
+    /// Instantiate a provider for [`DPASTE_URL`].
+    pub fn new() -> Self {
+        DpasteProvider {
+            inner: reqwest::Client::new(),
+            token: None,
+            base_url: DPASTE_URL.to_string(),
+        }
+    }
+
+    /// Use a different dpaste instance.
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Authenticate with an API token, to raise the rate limit.
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+#[async_trait]
+impl PasteProvider for DpasteProvider {
+    fn name(&self) -> &str {
+        "dpaste"
+    }
+
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        let expiry_days = request
+            .expires
+            .as_ref()
+            .filter(|expires| !expires.is_never())
+            .map(|expires| expires.total().as_secs().div_ceil(24 * 60 * 60))
+            .map(|days| days.clamp(1, DPASTE_MAX_DAYS));
+        let file = single_file(request)?;
+        let mut form = vec![
+            ("content", file.content.clone()),
+            ("title", file.filename.clone()),
+        ];
+        if let Some(days) = expiry_days {
+            form.push(("expiry_days", days.to_string()));
+        }
+        let request = self
+            .inner
+            .post(format!("{}/api/v2/", self.base_url))
+            .form(&form);
+        let url = send_text("dpaste", self.authorize(request)).await?;
+        let id = url
+            .trim()
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+        Ok(PasteResult {
+            created_at: now(),
+            expires: None,
+            files: vec![file],
+            id: id.to_string(),
+            idempotency_key: None,
+            line_endings: None,
+        })
+    }
+
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error> {
+        if password.is_some() {
+            return Err(Error::Unsupported("this provider can't set a password"));
+        }
+        let request = self.inner.get(format!("{}/{}.txt", self.base_url, id));
+        let content = send_text("dpaste", self.authorize(request)).await?;
+        Ok(PasteResult {
+            created_at: String::new(),
+            expires: None,
+            files: vec![File {
+                filename: id.to_string(),
+                content,
+                syntax: None,
+            }],
+            id: id.to_string(),
+            idempotency_key: None,
+            line_endings: None,
+        })
+    }
+
+    async fn delete(&self, _id: &str) -> Result<(), Error> {
+        Err(Error::Unsupported("dpaste pastes can't be deleted"))
+    }
+
+    fn url_for(&self, id: &str) -> String {
+        format!("{}/{}", self.base_url, id)
+    }
+}
+
+impl fmt::Debug for DpasteProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DpasteProvider")
+            .field("base_url", &self.base_url)
+            .field("authenticated", &self.token.is_some())
+            .finish()
+    }
+}
+
+impl Default for DpasteProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
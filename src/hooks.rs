@@ -0,0 +1,210 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use reqwest::{header::HeaderMap, Method, StatusCode, Url};
+
+use crate::PasteResult;
+
+/// A hook receiving the outcome of every request sent by a client.
+///
+/// Use it to feed Prometheus/StatsD counters and histograms from myust traffic,
+/// register it with the client's `metrics` method:
+///
+/// ```rust
+/// use myust::Client;
+///
+/// let client = Client::new().metrics(|endpoint: &str, status: u16, duration| {
+///     println!("{endpoint} -> {status} in {duration:?}");
+/// });
+/// ```
+pub trait Metrics: Send + Sync {
+    /// Called once a response is received.
+    ///
+    /// `endpoint` is the method and the route template, e.g. `GET /paste/{id}`,
+    /// so it's safe to use as a low-cardinality label.
+    fn on_request_complete(&self, endpoint: &str, status: u16, duration: Duration);
+}
+
+impl<F> Metrics for F
+where
+    F: Fn(&str, u16, Duration) + Send + Sync,
+{
+    fn on_request_complete(&self, endpoint: &str, status: u16, duration: Duration) {
+        self(endpoint, status, duration)
+    }
+}
+
+/// Odd data in a successful response that was worked around instead of failing
+/// the call, e.g. a paste without a creation date.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseWarning {
+    /// The missing or invalid field, e.g. `created_at`.
+    pub field: &'static str,
+    /// The ID of the paste the field belongs to, if known.
+    pub paste_id: Option<String>,
+    /// Whether the item was left out of a listing, rather than the field filled
+    /// with its default.
+    pub skipped: bool,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing or invalid field `{}`", self.field)?;
+        if let Some(paste_id) = &self.paste_id {
+            write!(f, " in paste {}", paste_id)?;
+        }
+        match self.skipped {
+            true => write!(f, ", the item was left out"),
+            false => write!(f, ", the default was used"),
+        }
+    }
+}
+
+/// A hook told about odd response data the client worked around, see
+/// [`ParseWarning`]. Register it with the client's `parse_warnings` method:
+///
+/// ```rust
+/// use myust::{Client, ParseWarning};
+///
+/// let client = Client::new().parse_warnings(|warning: &ParseWarning| {
+///     eprintln!("mystb.in sent odd data: {warning}");
+/// });
+/// ```
+///
+/// With the `tracing` feature, warnings are also logged as `WARN` events.
+pub trait ParseWarnings: Send + Sync {
+    /// Called once for each worked around field.
+    fn on_parse_warning(&self, warning: &ParseWarning);
+}
+
+impl<F> ParseWarnings for F
+where
+    F: Fn(&ParseWarning) + Send + Sync,
+{
+    fn on_parse_warning(&self, warning: &ParseWarning) {
+        self(warning)
+    }
+}
+
+/// A hook notified of paste lifecycle events, e.g. to keep an audit log or a local mirror.
+///
+/// Events are only emitted for successful calls made through the client the observer
+/// is registered on.
+pub trait Observer: Send + Sync {
+    /// Called after a paste is created.
+    fn on_paste_created(&self, _paste: &PasteResult) {}
+
+    /// Called after a paste is deleted, once per deleted paste.
+    fn on_paste_deleted(&self, _paste_id: &str) {}
+
+    /// Called after a paste is added to (`true`) or removed from (`false`) the bookmarks.
+    fn on_bookmark_changed(&self, _paste_id: &str, _bookmarked: bool) {}
+}
+
+impl<T: Observer + ?Sized> Observer for Arc<T> {
+    fn on_paste_created(&self, paste: &PasteResult) {
+        (**self).on_paste_created(paste)
+    }
+
+    fn on_paste_deleted(&self, paste_id: &str) {
+        (**self).on_paste_deleted(paste_id)
+    }
+
+    fn on_bookmark_changed(&self, paste_id: &str, bookmarked: bool) {
+        (**self).on_bookmark_changed(paste_id, bookmarked)
+    }
+}
+
+/// An outgoing request, as seen by an [`Interceptor`].
+#[derive(Debug)]
+pub struct OutgoingRequest<'a> {
+    /// The HTTP method.
+    pub method: &'a Method,
+    /// The full URL, including the query.
+    pub url: &'a mut Url,
+    /// The headers, including the `Authorization` header if the client is authenticated.
+    pub headers: &'a mut HeaderMap,
+    /// The JSON body, if any.
+    pub body: Option<&'a [u8]>,
+}
+
+/// A response as received from the API, before it's parsed.
+#[derive(Debug)]
+pub struct RawResponse<'a> {
+    /// The status code.
+    pub status: StatusCode,
+    /// The response headers.
+    pub headers: &'a HeaderMap,
+    /// The raw response body.
+    pub body: &'a [u8],
+}
+
+/// A hook to inspect or modify requests before they're sent, and to observe raw
+/// responses before they're parsed.
+///
+/// This can be used to add headers, sign requests or log responses:
+///
+/// ```rust
+/// use myust::{Client, Interceptor, OutgoingRequest};
+///
+/// struct UserAgent;
+///
+/// impl Interceptor for UserAgent {
+///     fn on_request(&self, request: &mut OutgoingRequest<'_>) {
+///         request
+///             .headers
+///             .insert("User-Agent", "my-app/1.0".parse().unwrap());
+///     }
+/// }
+///
+/// let client = Client::new().interceptor(UserAgent);
+/// ```
+pub trait Interceptor: Send + Sync {
+    /// Called before a request is sent.
+    fn on_request(&self, _request: &mut OutgoingRequest<'_>) {}
+
+    /// Called after a response is received, before it's parsed.
+    fn on_response(&self, _response: &RawResponse<'_>) {}
+}
+
+impl<T: Interceptor + ?Sized> Interceptor for Arc<T> {
+    fn on_request(&self, request: &mut OutgoingRequest<'_>) {
+        (**self).on_request(request)
+    }
+
+    fn on_response(&self, response: &RawResponse<'_>) {
+        (**self).on_response(response)
+    }
+}
+
+pub(crate) fn intercept_request(
+    interceptors: &[Arc<dyn Interceptor>],
+    method: &Method,
+    url: &mut Url,
+    headers: &mut HeaderMap,
+    body: Option<&[u8]>,
+) {
+    for interceptor in interceptors {
+        interceptor.on_request(&mut OutgoingRequest {
+            method,
+            url,
+            headers,
+            body,
+        });
+    }
+}
+
+pub(crate) fn intercept_response(
+    interceptors: &[Arc<dyn Interceptor>],
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &[u8],
+) {
+    let response = RawResponse {
+        status,
+        headers,
+        body,
+    };
+    for interceptor in interceptors {
+        interceptor.on_response(&response);
+    }
+}
@@ -0,0 +1,348 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "import")))]
+
+//! Recreating pastes from files on disk: a directory tree, or a `.tar` or `.tar.gz`
+//! archive of one.
+//!
+//! Every directory at the top of the tree is a paste, its files being the paste's
+//! files. A file at the top of the tree is a paste of its own. Deeper directories
+//! are skipped, as filenames can't contain a path separator:
+//!
+//! ```text
+//! pastes/
+//! ├── manifest.json
+//! ├── crash-report/
+//! │   ├── backtrace.txt
+//! │   └── config.toml
+//! └── notes.md
+//! ```
+//!
+//! The optional `manifest.json` sets the password and expiration date of some pastes,
+//! by their path in the tree:
+//!
+//! ```json
+//! {
+//!     "pastes": [
+//!         { "path": "crash-report", "password": "hunter2", "expires": { "days": 7 } },
+//!         { "path": "notes.md", "expires_at": "2030-01-01T00:00:00Z" }
+//!     ]
+//! }
+//! ```
+//!
+//! A paste whose `expires_at` date has passed isn't imported.
+//!
+//! Files that aren't valid UTF-8 are imported with [`File::from_bytes`].
+//!
+//! ```rust,no_run
+//! use myust::Client;
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new();
+//! let planned = client
+//!     .import_dir("pastes", |import| import.dry_run(true))
+//!     .await?;
+//! println!("{} pastes would be created", planned.succeeded.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, Read},
+    path::{Component, Path},
+    time::SystemTime,
+};
+
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    utils::parse_timestamp, Client, CreatePasteRequest, Error, Expiry, File, PasteProvider,
+    PasteResult, ValidationError,
+};
+
+/// The name of the manifest at the top of an imported tree.
+pub const MANIFEST_FILENAME: &str = "manifest.json";
+
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct Manifest {
+    #[serde(default)]
+    pub pastes: Vec<ManifestPaste>,
+    /// The IDs of bookmarked pastes, written by [`backup`](crate::backup).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bookmarks: Vec<String>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct ManifestPaste {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<Expiry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+/// The expiry lasting until `date`, or the number of seconds it's past by.
+fn expiry_until(date: &str) -> Result<Option<Expiry>, u64> {
+    let Some(date) = parse_timestamp(date) else {
+        return Ok(None);
+    };
+    match date.duration_since(SystemTime::now()) {
+        Ok(remaining) => {
+            let seconds = remaining.as_secs();
+            Ok(Some(Expiry {
+                days: (seconds / (24 * 60 * 60)).min(i32::MAX as u64) as i32,
+                seconds: (seconds % (24 * 60 * 60)) as i32,
+                ..Default::default()
+            }))
+        }
+        Err(err) => Err(err.duration().as_secs()),
+    }
+}
+
+/// The options of [`Client::import_dir`] and [`Client::import_archive`].
+#[derive(Default)]
+pub struct ImportOptions<'a> {
+    pub dry_run: bool,
+    pub progress: Option<Box<dyn FnMut(usize, usize) + Send + 'a>>,
+}
+
+impl<'a> ImportOptions<'a> {
+    /// Only read and validate the pastes, without creating them. Defaults to `false`.
+    pub fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// (optional) A callback receiving the number of imported pastes and the total
+    /// after each paste.
+    pub fn progress(&mut self, progress: impl FnMut(usize, usize) + Send + 'a) -> &mut Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+}
+
+impl fmt::Debug for ImportOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImportOptions")
+            .field("dry_run", &self.dry_run)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// A paste read from the imported tree.
+#[derive(Clone, Debug)]
+pub struct ImportedPaste {
+    /// The path of the paste in the tree.
+    pub source: String,
+    /// The request the paste is created with.
+    pub request: CreatePasteRequest,
+    /// The created paste, or `None` in a dry run.
+    pub paste: Option<PasteResult>,
+}
+
+/// The outcome of an import, in the order of the paths in the tree.
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    /// The pastes that were created, or that would be in a dry run.
+    pub succeeded: Vec<ImportedPaste>,
+    /// The paths of the pastes that couldn't be created, with the reason.
+    pub failed: Vec<(String, Error)>,
+}
+
+/// The files of a tree by their path, with `/` separators.
+pub(crate) type Tree = BTreeMap<String, Vec<u8>>;
+
+/// Read a directory, or an archive of one.
+#[cfg_attr(not(feature = "backup"), allow(dead_code))]
+pub(crate) fn read_tree(path: &Path) -> io::Result<Tree> {
+    match path.is_dir() {
+        true => read_dir(path),
+        false => read_archive(path),
+    }
+}
+
+fn read_dir(root: &Path) -> io::Result<Tree> {
+    let mut tree = Tree::new();
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let file_type = entry.file_type()?;
+        if file_type.is_file() {
+            tree.insert(name, fs::read(entry.path())?);
+        } else if file_type.is_dir() {
+            for file in fs::read_dir(entry.path())? {
+                let file = file?;
+                if file.file_type()?.is_file() {
+                    let path = format!("{}/{}", name, file.file_name().to_string_lossy());
+                    tree.insert(path, fs::read(file.path())?);
+                }
+            }
+        }
+    }
+    Ok(tree)
+}
+
+fn read_archive(path: &Path) -> io::Result<Tree> {
+    let bytes = fs::read(path)?;
+    let reader: Box<dyn Read> = match bytes.starts_with(&[0x1f, 0x8b]) {
+        true => Box::new(GzDecoder::new(bytes.as_slice())),
+        false => Box::new(bytes.as_slice()),
+    };
+    let mut archive = tar::Archive::new(reader);
+    let mut tree = Tree::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?;
+        let components: Vec<_> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        if components.is_empty() || components.len() > 2 {
+            continue;
+        }
+        let mut content = vec![];
+        entry.read_to_end(&mut content)?;
+        tree.insert(components.join("/"), content);
+    }
+    Ok(tree)
+}
+
+/// The pastes of a tree by their path, or why one can't be imported.
+pub(crate) type Plan = Vec<(String, Result<CreatePasteRequest, Error>)>;
+
+/// Group the files of a tree into pastes, applying the manifest.
+pub(crate) fn plan(mut tree: Tree) -> Result<(Plan, Manifest), Error> {
+    let manifest = match tree.remove(MANIFEST_FILENAME) {
+        Some(manifest) => serde_json::from_slice::<Manifest>(&manifest)
+            .map_err(|err| Error::Io(io::Error::new(io::ErrorKind::InvalidData, err)))?,
+        None => Manifest::default(),
+    };
+    let mut pastes: BTreeMap<String, CreatePasteRequest> = BTreeMap::new();
+    for (path, content) in tree {
+        let (source, filename) = path.split_once('/').unwrap_or((&path, &path));
+        let file = match String::from_utf8(content) {
+            Ok(content) => File {
+                filename: filename.to_string(),
+                content,
+                syntax: None,
+            },
+            Err(err) => File::from_bytes(filename, err.as_bytes()),
+        };
+        pastes
+            .entry(source.to_string())
+            .or_default()
+            .files
+            .push(file);
+    }
+    let mut plan: BTreeMap<_, _> = pastes
+        .into_iter()
+        .map(|(source, request)| (source, Ok(request)))
+        .collect();
+    for paste in &manifest.pastes {
+        let path = paste.path.trim_matches('/');
+        let entry = plan
+            .get_mut(path)
+            .ok_or_else(|| Error::FileNotFound(paste.path.clone()))?;
+        let Ok(request) = entry else { continue };
+        request.password = paste.password.clone();
+        request.expires = paste.expires.clone();
+        match paste.expires_at.as_deref().map(expiry_until) {
+            Some(Ok(Some(expires))) => request.expires = Some(expires),
+            Some(Err(past)) => {
+                *entry = Err(Error::Invalid(ValidationError::NegativeExpiry {
+                    field: "seconds",
+                    value: -(past.min(i32::MAX as u64) as i32),
+                }))
+            }
+            _ => {}
+        }
+    }
+    let plan = plan.into_iter().collect();
+    Ok((plan, manifest))
+}
+
+impl Client {
+    /// Create a paste from every directory and file at the top of a directory tree.
+    ///
+    /// A paste that can't be created is reported without stopping the others. Reading
+    /// the tree or its manifest failing is returned as an error, before anything is
+    /// created. See the [module documentation](crate::import) for the layout.
+    pub async fn import_dir<'a, F>(
+        &self,
+        path: impl AsRef<Path>,
+        options: F,
+    ) -> Result<ImportResult, Error>
+    where
+        F: for<'b> FnOnce(&'b mut ImportOptions<'a>) -> &'b mut ImportOptions<'a>,
+    {
+        let tree = read_dir(path.as_ref())?;
+        self.import(tree, options).await
+    }
+
+    /// Create pastes from a `.tar` or `.tar.gz` archive, laid out like a directory
+    /// given to [`import_dir`](Self::import_dir).
+    pub async fn import_archive<'a, F>(
+        &self,
+        path: impl AsRef<Path>,
+        options: F,
+    ) -> Result<ImportResult, Error>
+    where
+        F: for<'b> FnOnce(&'b mut ImportOptions<'a>) -> &'b mut ImportOptions<'a>,
+    {
+        let tree = read_archive(path.as_ref())?;
+        self.import(tree, options).await
+    }
+
+    async fn import<'a, F>(&self, tree: Tree, options: F) -> Result<ImportResult, Error>
+    where
+        F: for<'b> FnOnce(&'b mut ImportOptions<'a>) -> &'b mut ImportOptions<'a>,
+    {
+        let mut builder = ImportOptions::default();
+        options(&mut builder);
+        let ImportOptions {
+            dry_run,
+            mut progress,
+        } = builder;
+        let (pastes, _) = plan(tree)?;
+        let total = pastes.len();
+        let mut result = ImportResult::default();
+        for (done, (source, request)) in pastes.into_iter().enumerate() {
+            match self.import_paste(request, dry_run).await {
+                Ok((request, paste)) => result.succeeded.push(ImportedPaste {
+                    source,
+                    request,
+                    paste,
+                }),
+                Err(err) => result.failed.push((source, err)),
+            }
+            if let Some(progress) = progress.as_mut() {
+                progress(done + 1, total);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Create a planned paste, or only validate it in a dry run.
+    pub(crate) async fn import_paste(
+        &self,
+        request: Result<CreatePasteRequest, Error>,
+        dry_run: bool,
+    ) -> Result<(CreatePasteRequest, Option<PasteResult>), Error> {
+        let request = request?;
+        let paste = match dry_run {
+            true => request.validate().map(|_| None).map_err(Error::Invalid)?,
+            false => Some(self.create(request.clone()).await?),
+        };
+        Ok((request, paste))
+    }
+}
@@ -0,0 +1,169 @@
+//! A local record of the pastes a client creates, so anonymous pastes aren't lost
+//! once their URL scrolls away.
+//!
+//! Every created paste is appended as a [`CreateReport`] to a JSON-lines file, by
+//! default `$MYUST_JOURNAL`, or else `$XDG_DATA_HOME/myust/journal.jsonl`, or else
+//! `~/.local/share/myust/journal.jsonl`.
+//!
+//! With `deduplicate`, a client looks up the journal before creating a paste, and
+//! returns an identical paste recorded there instead of uploading it again:
+//!
+//! ```rust,no_run
+//! use myust::{journal::{self, Journal}, Client};
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new()
+//!     .journal(Journal::default_path().unwrap())
+//!     .deduplicate(true);
+//! client
+//!     .create_paste(|p| p.filename("myust.txt").content("Hello from myust!"))
+//!     .await?;
+//! for paste in journal::recent(10)? {
+//!     println!("{} {}", paste.created_at, paste.url);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{report::CreateReport, utils::parse_timestamp, CreatePasteRequest, Error, PasteResult};
+
+/// A JSON-lines file of created pastes, set on a client with its `journal` method.
+#[derive(Clone, Debug)]
+pub struct Journal {
+    path: PathBuf,
+    web_url: String,
+}
+
+impl Journal {
+    /// A journal stored at `path`, created on the first paste.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Journal {
+            path: path.into(),
+            web_url: crate::utils::WEB_URL.to_string(),
+        }
+    }
+
+    /// The default path of the journal.
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("MYUST_JOURNAL") {
+            return Some(path.into());
+        }
+        let data_dir = match env::var_os("XDG_DATA_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+        };
+        Some(data_dir.join("myust").join("journal.jsonl"))
+    }
+
+    /// Use a different URL for the website the recorded URLs point to.
+    ///
+    /// Defaults to `https://mystb.in`.
+    pub fn web_url(mut self, url: impl Into<String>) -> Self {
+        self.web_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// The path of the journal.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a paste to the journal.
+    pub fn record(&self, paste: &PasteResult) -> Result<(), Error> {
+        self.record_hashed(paste, None)
+    }
+
+    pub(crate) fn record_hashed(
+        &self,
+        paste: &PasteResult,
+        content_hash: Option<String>,
+    ) -> Result<(), Error> {
+        let mut report = CreateReport::new(paste, format!("{}/{}", self.web_url, paste.id));
+        report.content_hash = content_hash;
+        let mut line = serde_json::to_vec(&report).map_err(io::Error::other)?;
+        line.push(b'\n');
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&line)?;
+        Ok(())
+    }
+
+    /// Every recorded paste, oldest first. A missing journal is empty.
+    ///
+    /// Lines that can't be parsed, e.g. from an interrupted write, are skipped.
+    pub fn entries(&self) -> Result<Vec<CreateReport>, Error> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// The `n` most recently recorded pastes, newest first.
+    pub fn recent(&self, n: usize) -> Result<Vec<CreateReport>, Error> {
+        let mut entries = self.entries()?;
+        entries.reverse();
+        entries.truncate(n);
+        Ok(entries)
+    }
+
+    /// The most recent unexpired paste recorded with a content hash.
+    pub(crate) fn find(&self, content_hash: &str) -> Option<CreateReport> {
+        let now = SystemTime::now();
+        self.entries().ok()?.into_iter().rev().find(|entry| {
+            entry.content_hash.as_deref() == Some(content_hash)
+                && entry
+                    .expires
+                    .as_deref()
+                    .is_none_or(|expires| parse_timestamp(expires).is_some_and(|at| at > now))
+        })
+    }
+}
+
+/// A hash of the files and password of a request, ignoring its expiration date.
+pub(crate) fn content_hash(request: &CreatePasteRequest) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update([request.password.is_some() as u8]);
+    let mut field = |value: &str| {
+        hasher.update((value.len() as u64).to_le_bytes());
+        hasher.update(value);
+    };
+    field(request.password.as_deref().unwrap_or_default());
+    for file in &request.files {
+        field(&file.filename);
+        field(&file.content);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// The `n` most recently recorded pastes of the journal at the default path, newest
+/// first.
+pub fn recent(n: usize) -> Result<Vec<CreateReport>, Error> {
+    match Journal::default_path() {
+        Some(path) => Journal::new(path).recent(n),
+        None => Ok(vec![]),
+    }
+}
@@ -0,0 +1,89 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "keyring")))]
+
+//! Keeping the API token in the system keychain (macOS Keychain, Windows Credential
+//! Manager or the Linux kernel keyring) rather than in environment variables or a
+//! plaintext configuration file.
+//!
+//! ```rust,no_run
+//! use myust::{keyring, Client};
+//!
+//! # fn run() -> Result<(), myust::Error> {
+//! keyring::store_token("myust", "me", "YOUR_MYSTBIN_TOKEN")?;
+//! let client = Client::new().auth_from_keyring("myust", "me")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+
+use ::keyring::Entry;
+
+use crate::{Client, Error};
+
+fn keyring_error(err: ::keyring::Error) -> Error {
+    Error::Io(io::Error::other(err))
+}
+
+fn entry(service: &str, user: &str) -> Result<Entry, Error> {
+    Entry::new(service, user).map_err(keyring_error)
+}
+
+/// Store a token in the keychain, replacing the one stored for `service` and `user`.
+pub fn store_token(service: &str, user: &str, token: &str) -> Result<(), Error> {
+    entry(service, user)?
+        .set_password(token)
+        .map_err(keyring_error)
+}
+
+/// The token stored in the keychain, or `None` if there's none.
+pub fn load_token(service: &str, user: &str) -> Result<Option<String>, Error> {
+    match entry(service, user)?.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(::keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(keyring_error(err)),
+    }
+}
+
+/// Replace the stored token with a new one, returning the previous token so it can
+/// be revoked.
+pub fn rotate_token(service: &str, user: &str, token: &str) -> Result<Option<String>, Error> {
+    let previous = load_token(service, user)?;
+    store_token(service, user, token)?;
+    Ok(previous)
+}
+
+/// Remove the stored token. Removing a token that isn't stored does nothing.
+pub fn delete_token(service: &str, user: &str) -> Result<(), Error> {
+    match entry(service, user)?.delete_credential() {
+        Ok(()) | Err(::keyring::Error::NoEntry) => Ok(()),
+        Err(err) => Err(keyring_error(err)),
+    }
+}
+
+fn stored_token(service: &str, user: &str) -> Result<String, Error> {
+    load_token(service, user)?.ok_or_else(|| {
+        Error::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no token is stored for {} in {}", user, service),
+        ))
+    })
+}
+
+impl Client {
+    /// Authenticate with the token stored in the keychain with [`store_token`].
+    ///
+    /// Unlike `auth`, the token isn't checked until it's used.
+    pub fn auth_from_keyring(self, service: &str, user: &str) -> Result<Self, Error> {
+        Ok(self.with_token(&stored_token(service, user)?))
+    }
+}
+
+#[cfg(feature = "sync")]
+impl crate::SyncClient {
+    /// Authenticate with the token stored in the keychain with [`store_token`].
+    ///
+    /// Unlike `auth`, the token isn't checked until it's used.
+    pub fn auth_from_keyring(self, service: &str, user: &str) -> Result<Self, Error> {
+        Ok(self.with_token(&stored_token(service, user)?))
+    }
+}
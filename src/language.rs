@@ -0,0 +1,324 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "language-detection")))]
+
+//! Guessing the language of a file, so mystb.in can highlight files without an extension.
+//!
+//! The language is read from the filename's extension when it has one, and otherwise
+//! guessed from the content with a few cheap heuristics: shebangs, document headers and
+//! the keywords lines start with.
+//!
+//! ```rust
+//! use myust::{language::Language, File};
+//!
+//! let file = File {
+//!     filename: "main".to_string(),
+//!     content: "use std::env;\n\nfn main() {}\n".to_string(),
+//!     ..Default::default()
+//! };
+//! assert_eq!(file.language(), Some(Language::Rust));
+//! assert_eq!(Language::Rust.extension(), "rs");
+//! ```
+//!
+//! Turn on [`PasteBuilder::detect_language`](crate::PasteBuilder::detect_language) to
+//! append the extension of the detected language to filenames that have none.
+
+use std::path::Path;
+
+use crate::File;
+
+/// How many lines of content are looked at when guessing a language.
+const SCANNED_LINES: usize = 200;
+
+/// A language myust can recognize.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Language {
+    Bash,
+    C,
+    Cpp,
+    Css,
+    Go,
+    Html,
+    Java,
+    JavaScript,
+    Json,
+    Markdown,
+    Php,
+    Python,
+    Ruby,
+    Rust,
+    Sql,
+    Toml,
+    TypeScript,
+    Xml,
+    Yaml,
+}
+
+/// The prefixes of lines that hint at a language, checked in order.
+const LINE_HINTS: [(Language, &[&str]); 14] = [
+    (
+        Language::Rust,
+        &[
+            "fn ",
+            "pub fn ",
+            "use std::",
+            "impl ",
+            "impl<",
+            "let mut ",
+            "#[derive",
+            "pub struct ",
+            "mod ",
+        ],
+    ),
+    (
+        Language::Python,
+        &[
+            "def ",
+            "import ",
+            "from ",
+            "elif ",
+            "print(",
+            "if __name__",
+            "async def ",
+        ],
+    ),
+    (Language::Go, &["package main", "func ", "import ("]),
+    (
+        Language::Cpp,
+        &[
+            "#include <iostream>",
+            "using namespace ",
+            "template <",
+            "std::",
+        ],
+    ),
+    (
+        Language::C,
+        &["#include ", "#define ", "int main(", "typedef "],
+    ),
+    (
+        Language::Java,
+        &[
+            "import java.",
+            "public class ",
+            "public static void ",
+            "private final ",
+        ],
+    ),
+    (
+        Language::TypeScript,
+        &["interface ", "export interface ", "type ", "export type "],
+    ),
+    (
+        Language::JavaScript,
+        &[
+            "const ",
+            "function ",
+            "console.log(",
+            "export default ",
+            "module.exports",
+        ],
+    ),
+    (
+        Language::Ruby,
+        &["require '", "puts ", "end", "attr_accessor "],
+    ),
+    (Language::Php, &["<?php", "echo ", "$this->"]),
+    (
+        Language::Sql,
+        &[
+            "SELECT ",
+            "INSERT INTO ",
+            "CREATE TABLE ",
+            "UPDATE ",
+            "DELETE FROM ",
+        ],
+    ),
+    (Language::Toml, &["[package]", "[dependencies]", "[tool."]),
+    (Language::Markdown, &["## ", "### ", "```", "- [ ] "]),
+    (Language::Css, &["@media ", "@import ", "body {", ":root {"]),
+];
+
+impl Language {
+    /// The language's name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bash => "Bash",
+            Self::C => "C",
+            Self::Cpp => "C++",
+            Self::Css => "CSS",
+            Self::Go => "Go",
+            Self::Html => "HTML",
+            Self::Java => "Java",
+            Self::JavaScript => "JavaScript",
+            Self::Json => "JSON",
+            Self::Markdown => "Markdown",
+            Self::Php => "PHP",
+            Self::Python => "Python",
+            Self::Ruby => "Ruby",
+            Self::Rust => "Rust",
+            Self::Sql => "SQL",
+            Self::Toml => "TOML",
+            Self::TypeScript => "TypeScript",
+            Self::Xml => "XML",
+            Self::Yaml => "YAML",
+        }
+    }
+
+    /// The usual file extension of the language, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Bash => "sh",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+            Self::Css => "css",
+            Self::Go => "go",
+            Self::Html => "html",
+            Self::Java => "java",
+            Self::JavaScript => "js",
+            Self::Json => "json",
+            Self::Markdown => "md",
+            Self::Php => "php",
+            Self::Python => "py",
+            Self::Ruby => "rb",
+            Self::Rust => "rs",
+            Self::Sql => "sql",
+            Self::Toml => "toml",
+            Self::TypeScript => "ts",
+            Self::Xml => "xml",
+            Self::Yaml => "yaml",
+        }
+    }
+
+    /// The language of a file extension, without the dot.
+    pub fn from_extension(extension: &str) -> Option<Language> {
+        let language = match extension.to_ascii_lowercase().as_str() {
+            "sh" | "bash" | "zsh" => Self::Bash,
+            "c" | "h" => Self::C,
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" => Self::Cpp,
+            "css" => Self::Css,
+            "go" => Self::Go,
+            "html" | "htm" => Self::Html,
+            "java" => Self::Java,
+            "js" | "mjs" | "cjs" | "jsx" => Self::JavaScript,
+            "json" => Self::Json,
+            "md" | "markdown" => Self::Markdown,
+            "php" => Self::Php,
+            "py" | "pyw" => Self::Python,
+            "rb" => Self::Ruby,
+            "rs" => Self::Rust,
+            "sql" => Self::Sql,
+            "toml" => Self::Toml,
+            "ts" | "tsx" => Self::TypeScript,
+            "xml" | "svg" => Self::Xml,
+            "yaml" | "yml" => Self::Yaml,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    /// The language of a name such as `Rust` or `c++`, case-insensitively, or of an
+    /// extension.
+    pub fn from_name(name: &str) -> Option<Language> {
+        let language = match name.to_ascii_lowercase().as_str() {
+            "shell" => Self::Bash,
+            "c++" => Self::Cpp,
+            "golang" => Self::Go,
+            "javascript" => Self::JavaScript,
+            "python" => Self::Python,
+            "ruby" => Self::Ruby,
+            "rust" => Self::Rust,
+            "typescript" => Self::TypeScript,
+            name => return Self::from_extension(name),
+        };
+        Some(language)
+    }
+
+    /// The language of a filename, from its extension.
+    pub fn from_filename(filename: &str) -> Option<Language> {
+        Path::new(filename)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Guess the language of some content, or `None` if nothing hints at one.
+    pub fn detect(content: &str) -> Option<Language> {
+        let content = content.trim_start();
+        let first_line = content.lines().next()?;
+        if let Some(interpreter) = first_line.strip_prefix("#!") {
+            return Self::from_interpreter(interpreter);
+        }
+        let lowercase = first_line.to_ascii_lowercase();
+        if lowercase.starts_with("<?php") {
+            return Some(Self::Php);
+        }
+        if lowercase.starts_with("<!doctype html") || lowercase.starts_with("<html") {
+            return Some(Self::Html);
+        }
+        if lowercase.starts_with("<?xml") {
+            return Some(Self::Xml);
+        }
+        if (content.starts_with('{') || content.starts_with('['))
+            && serde_json::from_str::<serde::de::IgnoredAny>(content).is_ok()
+        {
+            return Some(Self::Json);
+        }
+        if first_line == "---" {
+            return Some(Self::Yaml);
+        }
+
+        let mut scores = [0usize; LINE_HINTS.len()];
+        for line in content.lines().take(SCANNED_LINES) {
+            let line = line.trim_start();
+            for (score, (_, prefixes)) in scores.iter_mut().zip(LINE_HINTS.iter()) {
+                if prefixes.iter().any(|prefix| line.starts_with(prefix)) {
+                    *score += 1;
+                }
+            }
+        }
+        // the first language wins ties, as the hints are ordered by specificity
+        let (index, best) = scores
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, score)| **score)?;
+        let (language, _) = LINE_HINTS.get(index)?;
+        (*best > 0).then_some(*language)
+    }
+
+    fn from_interpreter(interpreter: &str) -> Option<Language> {
+        let mut words = interpreter.split_whitespace();
+        let mut program = words.next()?.rsplit('/').next()?;
+        if program == "env" {
+            program = words.find(|word| !word.starts_with('-'))?;
+        }
+        let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        let language = match program {
+            "sh" | "bash" | "zsh" | "dash" | "ksh" => Self::Bash,
+            "python" => Self::Python,
+            "node" | "deno" => Self::JavaScript,
+            "ruby" => Self::Ruby,
+            "php" => Self::Php,
+            _ => return None,
+        };
+        Some(language)
+    }
+}
+
+impl File {
+    /// The file's language, from its extension or else guessed from its content.
+    pub fn language(&self) -> Option<Language> {
+        Language::from_filename(&self.filename).or_else(|| Language::detect(&self.content))
+    }
+}
+
+/// Append the extension of the detected language to a filename without one.
+pub(crate) fn with_extension(filename: String, content: &str) -> String {
+    if Path::new(&filename).extension().is_some() {
+        return filename;
+    }
+    match Language::detect(content) {
+        Some(language) => format!("{}.{}", filename, language.extension()),
+        None => filename,
+    }
+}
@@ -1,5 +1,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
-#![allow(clippy::needless_doctest_main, clippy::clone_double_ref)]
+#![allow(clippy::needless_doctest_main)]
+#![deny(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::string_slice
+)]
 
 //! A rich and hybrid [mystb.in] API wrapper for Rust 🦀
 //!
@@ -15,18 +22,19 @@
 //!
 //! ## Authentication
 //!
-//! You can authenticate with the API using the `auth` method with your
+//! You can authenticate with the API using the `try_auth` method with your
 //! [mystb.in] API token, example:
 //!
-//! ```rust
+//! ```rust,ignore
 //! use myust::{Client, SyncClient};
 //!
-//! let client = Client::new().auth("YOUR_MYSTBIN_TOKEN").await;
+//! let client = Client::new().try_auth("YOUR_MYSTBIN_TOKEN").await?;
 //! // or using synchronous client,
-//! let client = SyncClient::new().auth("YOUR_MYSTBIN_TOKEN");
+//! let client = SyncClient::new().try_auth("YOUR_MYSTBIN_TOKEN")?;
 //! ```
 //!
-//! This method will panic if the provided token is invalid.
+//! It fails if the token is rejected or can't be checked. The `auth` method panics
+//! instead when the token is rejected, and keeps a token that can't be checked.
 //!
 //! ## Installation
 //!
@@ -48,7 +56,7 @@
 //! ## Usage Examples
 //!
 //! Asynchronously creating a paste with tomorrow expiration date, with error handling:
-//! ```rust
+//! ```rust,no_run
 //! use myust::{Client, Expiry};
 //!
 //! #[tokio::main]
@@ -73,14 +81,14 @@
 //!             println!("Result: {}", url)
 //!         }
 //!         Err(_) => {
-//!             println!("Error code: {}", result.unwrap_err().code)
+//!             println!("Error: {}", result.unwrap_err())
 //!         }
 //!     }
 //! }
 //! ```
 //!
 //! Asynchronously deleting a paste (you must own the paste):
-//! ```rust
+//! ```rust,no_run
 //! use myust::Client;
 //!
 //! #[tokio::main]
@@ -92,14 +100,14 @@
 //!    match result {
 //!        Ok(_) => println!("Successfully deleted the paste."),
 //!        Err(_) => {
-//!            println!("Error code: {}", result.unwrap_err().code)
+//!            println!("Error code: {:?}", result.unwrap_err().status())
 //!        }
 //!    }
 //! }
 //! ```
 //!
 //! Synchronously creating a multifile paste with a password (you must have the [`sync`] feature enabled):
-//! ```rust
+//! ```rust,no_run
 //! use myust::SyncClient;
 //!
 //! fn main() {
@@ -119,6 +127,20 @@
 //! }
 //! ```
 //!
+//! ## Errors
+//!
+//! Public methods don't panic: network failures, responses that aren't what the API
+//! documents, invalid builder input and missing fields are all returned as an
+//! [`Error`]. The only exceptions are documented on the methods, i.e. `auth` with a
+//! rejected token and `http_options`, which have fallible `try_auth` and
+//! `try_http_options` versions, and `new` when the TLS backend can't be initialized.
+//!
+//! ## Testing
+//!
+//! The `test-server` feature provides [`test_server::TestServer`], an in-process fake of the
+//! mystb.in API. Point a client at it with `base_url` to run end-to-end tests without a token
+//! or internet access.
+//!
 //! You can check for another example snippets in [the test folder](https://github.com/danrfq/myust/blob/main/tests/).
 //!
 //! ## Help & Contributing
@@ -127,14 +149,101 @@
 //!
 //! [mystb.in]: https://mystb.in
 mod r#async;
+#[cfg(feature = "browser")]
+mod browser;
 mod builders;
+mod cache;
+mod hooks;
+mod limiter;
+mod options;
+mod parse;
+mod paste_id;
+mod provider;
+mod routes;
+mod stream;
 mod structs;
+mod tokens;
 mod traits;
 mod utils;
+pub use builders::*;
+pub use cache::*;
+pub use hooks::*;
+pub use limiter::RequestLimiter;
+pub use options::*;
+pub use paste_id::{IntoPasteId, PasteId};
+pub use provider::*;
 pub use r#async::Client;
+pub use reqwest::{
+    header::{HeaderName, HeaderValue},
+    Certificate, Method, StatusCode, Url,
+};
+pub use routes::ApiVersion;
 pub use structs::*;
+pub use tokens::TokenRotation;
+pub use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "sync")]
 pub mod sync;
 #[cfg(feature = "sync")]
 pub use sync::SyncClient;
+
+#[cfg(feature = "admin")]
+pub mod admin;
+
+#[cfg(feature = "backup")]
+pub mod backup;
+
+pub mod collection;
+
+#[cfg(feature = "config")]
+pub mod config;
+
+#[cfg(feature = "diff")]
+pub mod diff;
+
+#[cfg(feature = "encryption")]
+pub mod encryption;
+
+#[cfg(feature = "gist")]
+pub mod gist;
+
+#[cfg(feature = "haste")]
+pub mod haste;
+
+#[cfg(feature = "import")]
+pub mod import;
+
+pub mod journal;
+
+#[cfg(feature = "keyring")]
+pub mod keyring;
+
+#[cfg(feature = "language-detection")]
+pub mod language;
+
+#[cfg(feature = "pastebin")]
+pub mod pastebin;
+
+#[cfg(feature = "queue")]
+pub mod queue;
+
+#[cfg(feature = "reminders")]
+pub mod reminders;
+
+pub mod report;
+
+pub mod retention;
+
+#[cfg(feature = "signing")]
+pub mod signing;
+
+#[cfg(feature = "store")]
+pub mod store;
+
+pub mod template;
+
+#[cfg(feature = "test-server")]
+pub mod test_server;
+
+#[cfg(feature = "watch")]
+pub mod watch;
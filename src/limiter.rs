@@ -0,0 +1,202 @@
+use std::{
+    collections::BTreeSet,
+    fmt,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+use tokio::sync::Notify;
+
+use crate::utils::lock;
+
+/// A limit on how many requests are in flight at once, shared by every client it's
+/// registered with, e.g. with the client's `request_limiter` method.
+///
+/// A request holds its slot until the response headers are received. Clones of a
+/// limiter share the same slots.
+///
+/// ```rust
+/// use myust::{Client, RequestLimiter};
+///
+/// let limiter = RequestLimiter::fifo(8);
+/// let client = Client::new().request_limiter(limiter.clone());
+/// let other_account = client.share_pool().request_limiter(limiter);
+/// ```
+#[derive(Clone)]
+pub struct RequestLimiter {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    max: usize,
+    fifo: bool,
+    slots: Mutex<Slots>,
+    async_waiters: Notify,
+    sync_waiters: Condvar,
+}
+
+#[derive(Default)]
+struct Slots {
+    in_flight: usize,
+    waiting: usize,
+    next_ticket: u64,
+    serving: u64,
+    /// The tickets of async waiters that stopped waiting before their turn.
+    abandoned: BTreeSet<u64>,
+}
+
+impl RequestLimiter {
+    /// A limiter letting at most `max` requests through at once, at least one.
+    ///
+    /// When a slot is freed, whichever waiting request gets to it first goes through.
+    pub fn new(max: usize) -> Self {
+        Self::with_order(max, false)
+    }
+
+    /// Like [`new`](Self::new), letting waiting requests through in the order they
+    /// started waiting, so none of them waits much longer than the others.
+    pub fn fifo(max: usize) -> Self {
+        Self::with_order(max, true)
+    }
+
+    fn with_order(max: usize, fifo: bool) -> Self {
+        RequestLimiter {
+            inner: Arc::new(Inner {
+                max: max.max(1),
+                fifo,
+                slots: Mutex::default(),
+                async_waiters: Notify::new(),
+                sync_waiters: Condvar::new(),
+            }),
+        }
+    }
+
+    /// The most requests in flight at once.
+    pub fn max(&self) -> usize {
+        self.inner.max
+    }
+
+    /// The number of requests in flight.
+    pub fn in_flight(&self) -> usize {
+        self.slots().in_flight
+    }
+
+    /// The number of requests waiting for a slot.
+    pub fn waiting(&self) -> usize {
+        self.slots().waiting
+    }
+
+    fn slots(&self) -> MutexGuard<'_, Slots> {
+        lock(&self.inner.slots)
+    }
+
+    /// Take a ticket, so FIFO waiters can be served in order.
+    fn ticket(&self, slots: &mut Slots) -> Option<u64> {
+        slots.waiting += 1;
+        self.inner.fifo.then(|| {
+            slots.next_ticket += 1;
+            slots.next_ticket - 1
+        })
+    }
+
+    /// Take a slot if one is free and it's this ticket's turn.
+    fn try_admit(&self, slots: &mut Slots, ticket: Option<u64>) -> bool {
+        if slots.in_flight >= self.inner.max {
+            return false;
+        }
+        if let Some(ticket) = ticket {
+            while slots.abandoned.remove(&slots.serving) {
+                slots.serving += 1;
+            }
+            if slots.serving != ticket {
+                return false;
+            }
+            slots.serving += 1;
+        }
+        slots.in_flight += 1;
+        slots.waiting -= 1;
+        true
+    }
+
+    /// Wait for a slot, freed when the permit is dropped.
+    pub(crate) async fn acquire(&self) -> Permit {
+        let mut waiter = Waiter {
+            limiter: self,
+            ticket: None,
+            admitted: false,
+        };
+        waiter.ticket = self.ticket(&mut self.slots());
+        loop {
+            let notified = self.inner.async_waiters.notified();
+            tokio::pin!(notified);
+            // registered before checking, so a slot freed in between isn't missed
+            notified.as_mut().enable();
+            if self.try_admit(&mut self.slots(), waiter.ticket) {
+                waiter.admitted = true;
+                return Permit(self.clone());
+            }
+            notified.await;
+        }
+    }
+
+    /// Block the thread until a slot is free, freed when the permit is dropped.
+    #[cfg_attr(not(feature = "sync"), allow(dead_code))]
+    pub(crate) fn acquire_blocking(&self) -> Permit {
+        let mut slots = self.slots();
+        let ticket = self.ticket(&mut slots);
+        while !self.try_admit(&mut slots, ticket) {
+            slots = self
+                .inner
+                .sync_waiters
+                .wait(slots)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        Permit(self.clone())
+    }
+
+    fn wake(&self) {
+        self.inner.async_waiters.notify_waiters();
+        self.inner.sync_waiters.notify_all();
+    }
+}
+
+impl fmt::Debug for RequestLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestLimiter")
+            .field("max", &self.inner.max)
+            .field("fifo", &self.inner.fifo)
+            .field("in_flight", &self.in_flight())
+            .finish()
+    }
+}
+
+/// An async waiter, giving its ticket up if it's dropped before getting a slot.
+struct Waiter<'a> {
+    limiter: &'a RequestLimiter,
+    ticket: Option<u64>,
+    admitted: bool,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        if self.admitted {
+            return;
+        }
+        let mut slots = self.limiter.slots();
+        slots.waiting -= 1;
+        if let Some(ticket) = self.ticket {
+            slots.abandoned.insert(ticket);
+        }
+        drop(slots);
+        self.limiter.wake();
+    }
+}
+
+/// A slot taken by a request in flight.
+pub(crate) struct Permit(RequestLimiter);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.0.slots().in_flight -= 1;
+        self.0.wake();
+    }
+}
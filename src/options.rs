@@ -0,0 +1,162 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+
+use reqwest::Certificate;
+
+/// Options for the underlying HTTP connections.
+///
+/// By default every option is left to reqwest. For bulk operations such as
+/// deleting or fetching many pastes, keeping connections alive and pooled
+/// avoids paying a new TCP + TLS handshake on each request, as measured by
+/// `benches/connection_pool.rs`:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use myust::{Client, HttpOptions};
+///
+/// let client = Client::new().http_options(HttpOptions {
+///     tcp_keepalive: Some(Duration::from_secs(60)),
+///     pool_idle_timeout: Some(Duration::from_secs(120)),
+///     pool_max_idle_per_host: Some(32),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HttpOptions {
+    /// Only speak HTTP/2, without negotiating it first.
+    ///
+    /// This only works against servers known to support HTTP/2.
+    pub http2_prior_knowledge: bool,
+    /// Let HTTP/2 connections adapt their flow-control window to the throughput.
+    pub http2_adaptive_window: bool,
+    /// Send TCP keep-alive probes at this interval.
+    pub tcp_keepalive: Option<Duration>,
+    /// Whether to disable Nagle's algorithm.
+    pub tcp_nodelay: Option<bool>,
+    /// How long idle connections are kept in the pool.
+    pub pool_idle_timeout: Option<Duration>,
+    /// How many idle connections are kept per host.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Fail a request with [`Error::Timeout`](crate::Error::Timeout) if it takes
+    /// longer than this, from connecting to receiving the whole response.
+    pub timeout: Option<Duration>,
+    /// Fail a request with [`Error::Connect`](crate::Error::Connect) if connecting
+    /// takes longer than this.
+    pub connect_timeout: Option<Duration>,
+    /// How many redirects are followed, [`DEFAULT_MAX_REDIRECTS`] by default. With
+    /// `Some(0)`, redirects aren't followed and are returned as API errors.
+    ///
+    /// The token is not sent again when redirected to another host. The final URL is
+    /// available with `last_url`, and on [`ErrorResponse`](crate::ErrorResponse).
+    pub max_redirects: Option<usize>,
+    /// Connect to these addresses instead of resolving the hostnames with DNS, e.g.
+    /// for split-horizon DNS or to send `api.mystb.in` requests to a local instance.
+    ///
+    /// The port still comes from the URL. TLS certificates are checked against the
+    /// hostname, not the address.
+    pub resolve: HashMap<String, Vec<IpAddr>>,
+    /// Only trust these root certificates, instead of the system's, e.g. the
+    /// self-signed certificate of a self-hosted instance, or the CA that issues the
+    /// certificates of `api.mystb.in`.
+    ///
+    /// This restricts the trust anchors, it doesn't pin the server's certificate:
+    /// any certificate for the hostname issued by one of them is accepted. The
+    /// connection fails during the TLS handshake, before anything is sent, if the
+    /// server's certificate isn't one of them or issued by one of them.
+    pub trusted_roots: Vec<Certificate>,
+    /// Accept any TLS certificate, even expired, self-signed or for another
+    /// hostname, e.g. for a mystbin instance running locally over HTTPS.
+    ///
+    /// This also disables [`trusted_roots`](Self::trusted_roots), and
+    /// lets anyone in the middle read the token and the pastes, so never enable it
+    /// outside of development.
+    #[cfg(feature = "danger-accept-invalid-certs")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "danger-accept-invalid-certs")))]
+    pub danger_accept_invalid_certs: bool,
+    /// Advertise gzip and brotli with `Accept-Encoding` and transparently
+    /// decompress responses.
+    #[cfg(feature = "compression")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+    pub decompress_responses: bool,
+    /// Gzip request bodies larger than this many bytes, e.g. large log pastes.
+    ///
+    /// Only enable this against servers that accept `Content-Encoding: gzip`.
+    #[cfg(feature = "compression")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+    pub compress_requests_over: Option<usize>,
+}
+
+/// How many redirects are followed unless [`HttpOptions::max_redirects`] is set.
+pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Apply [`HttpOptions`] to either an async or a blocking reqwest `ClientBuilder`,
+/// which share method names but not a trait.
+macro_rules! apply_http_options {
+    ($options:expr, $builder:expr) => {{
+        let options: &$crate::HttpOptions = $options;
+        let mut builder = $builder;
+        if options.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if options.http2_adaptive_window {
+            builder = builder.http2_adaptive_window(true);
+        }
+        if let Some(interval) = options.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        if let Some(nodelay) = options.tcp_nodelay {
+            builder = builder.tcp_nodelay(nodelay);
+        }
+        if let Some(timeout) = options.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = options.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = options.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = options.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        for (host, addrs) in &options.resolve {
+            builder = builder.resolve_to_addrs(host, &$crate::options::socket_addrs(addrs));
+        }
+        if !options.trusted_roots.is_empty() {
+            builder = builder.tls_built_in_root_certs(false);
+            for certificate in &options.trusted_roots {
+                builder = builder.add_root_certificate(certificate.clone());
+            }
+        }
+        #[cfg(feature = "danger-accept-invalid-certs")]
+        {
+            builder = builder.danger_accept_invalid_certs(options.danger_accept_invalid_certs);
+        }
+        builder = builder.redirect(
+            match options
+                .max_redirects
+                .unwrap_or($crate::DEFAULT_MAX_REDIRECTS)
+            {
+                0 => reqwest::redirect::Policy::none(),
+                max => reqwest::redirect::Policy::limited(max),
+            },
+        );
+        #[cfg(feature = "compression")]
+        {
+            builder = builder
+                .gzip(options.decompress_responses)
+                .brotli(options.decompress_responses);
+        }
+        builder
+    }};
+}
+
+pub(crate) use apply_http_options;
+
+/// The addresses of a DNS override, whose port is ignored by reqwest.
+pub(crate) fn socket_addrs(addrs: &[IpAddr]) -> Vec<SocketAddr> {
+    addrs.iter().map(|addr| SocketAddr::new(*addr, 0)).collect()
+}
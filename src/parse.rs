@@ -0,0 +1,137 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{Error, File, ParseWarning, ParseWarnings};
+
+/// A JSON backend used to parse response bodies.
+pub(crate) trait JsonBackend {
+    fn parse(body: &[u8]) -> Option<Value>;
+}
+
+/// The default backend, using `serde_json`.
+#[cfg_attr(feature = "simd-json", allow(dead_code))]
+pub(crate) struct SerdeJson;
+
+impl JsonBackend for SerdeJson {
+    fn parse(body: &[u8]) -> Option<Value> {
+        serde_json::from_slice(body).ok()
+    }
+}
+
+/// A SIMD-accelerated backend for large responses, using `simd-json`.
+#[cfg(feature = "simd-json")]
+pub(crate) struct SimdJson;
+
+#[cfg(feature = "simd-json")]
+impl JsonBackend for SimdJson {
+    fn parse(body: &[u8]) -> Option<Value> {
+        // simd-json parses in place, so it needs its own copy of the body
+        let mut body = body.to_vec();
+        simd_json::serde::from_slice(&mut body).ok()
+    }
+}
+
+#[cfg(not(feature = "simd-json"))]
+type Backend = SerdeJson;
+#[cfg(feature = "simd-json")]
+type Backend = SimdJson;
+
+/// Parse a response body with the backend selected by the enabled features.
+pub(crate) fn parse_json(body: &[u8]) -> Option<Value> {
+    Backend::parse(body)
+}
+
+/// The JSON body of a successful response, which the API always sends.
+pub(crate) fn json_body(json: Option<Value>) -> Result<Value, Error> {
+    json.ok_or_else(|| Error::InvalidResponse("the body isn't JSON".to_string()))
+}
+
+/// A string field of a response object.
+pub(crate) fn str_field(value: &Value, field: &str) -> Result<String, Error> {
+    value[field]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| Error::InvalidResponse(format!("missing string field `{}`", field)))
+}
+
+/// Deserialize a field of a response object.
+#[cfg_attr(not(feature = "admin"), allow(dead_code))]
+pub(crate) fn from_field<T: DeserializeOwned>(value: &mut Value, field: &str) -> Result<T, Error> {
+    serde_json::from_value(value[field].take())
+        .map_err(|err| Error::InvalidResponse(format!("invalid field `{}`: {}", field, err)))
+}
+
+/// An array field of a response object.
+pub(crate) fn array_field<'a>(value: &'a Value, field: &str) -> Result<&'a Vec<Value>, Error> {
+    value[field]
+        .as_array()
+        .ok_or_else(|| Error::InvalidResponse(format!("missing array field `{}`", field)))
+}
+
+/// Parses the fields of a response that may be missing or odd, filling in defaults
+/// and reporting them to the client's [`ParseWarnings`] hook, so one odd paste
+/// doesn't fail a whole listing.
+pub(crate) struct Lenient<'a>(pub(crate) Option<&'a dyn ParseWarnings>);
+
+impl Lenient<'_> {
+    fn warn(&self, field: &'static str, paste_id: Option<&str>, skipped: bool) {
+        let warning = ParseWarning {
+            field,
+            paste_id: paste_id.map(|id| id.to_string()),
+            skipped,
+        };
+        #[cfg(feature = "tracing")]
+        tracing::warn!(field, paste_id, skipped, "odd response data: {}", warning);
+        if let Some(hook) = self.0 {
+            hook.on_parse_warning(&warning);
+        }
+    }
+
+    /// The ID of a listed paste, or `None` with a warning if it has none, in which
+    /// case it's left out.
+    pub(crate) fn id(&self, value: &Value) -> Option<String> {
+        let id = value["id"].as_str().map(|id| id.to_string());
+        if id.is_none() {
+            self.warn("id", None, true);
+        }
+        id
+    }
+
+    /// A string field of a paste, or an empty string with a warning.
+    pub(crate) fn str_field(&self, value: &Value, field: &'static str, paste_id: &str) -> String {
+        match value[field].as_str() {
+            Some(s) => s.to_string(),
+            None => {
+                self.warn(field, Some(paste_id), false);
+                String::new()
+            }
+        }
+    }
+
+    /// The files of a paste, which must be an array. Files that aren't objects are
+    /// left out, and a missing filename or content is left empty, with a warning.
+    pub(crate) fn files(&self, value: &mut Value, paste_id: &str) -> Result<Vec<File>, Error> {
+        let files = match value["files"].take() {
+            Value::Array(files) => files,
+            _ => {
+                return Err(Error::InvalidResponse(
+                    "missing array field `files`".to_string(),
+                ))
+            }
+        };
+        Ok(files
+            .into_iter()
+            .filter_map(|file| {
+                if !file.is_object() {
+                    self.warn("files", Some(paste_id), true);
+                    return None;
+                }
+                Some(File {
+                    filename: self.str_field(&file, "filename", paste_id),
+                    content: self.str_field(&file, "content", paste_id),
+                    syntax: None,
+                })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,168 @@
+use std::{fmt, str::FromStr};
+
+use reqwest::Url;
+
+use crate::ValidationError;
+
+/// A checked paste ID, such as `EquipmentMovingExpensive`.
+///
+/// It can be parsed from the ID itself or from a paste URL, with or without the
+/// scheme, e.g. `https://mystb.in/EquipmentMovingExpensive`. IDs only have ASCII
+/// letters, digits, `-` and `_`, so they can't change the path of a request.
+///
+/// ```rust
+/// use myust::PasteId;
+///
+/// let id = PasteId::try_from("mystb.in/EquipmentMovingExpensive").unwrap();
+/// assert_eq!(id.as_str(), "EquipmentMovingExpensive");
+/// assert!(PasteId::try_from("../users/@me").is_err());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PasteId(String);
+
+impl PasteId {
+    /// Parse an ID or a paste URL.
+    pub fn parse(input: &str) -> Result<Self, ValidationError> {
+        if !input.contains('/') {
+            return Self::check(input);
+        }
+        let url = match input.contains("://") {
+            true => Url::parse(input),
+            false => Url::parse(&format!("https://{}", input)),
+        };
+        match url {
+            Ok(url) => Self::from_url(&url),
+            Err(_) => Err(ValidationError::InvalidPasteId(input.to_string())),
+        }
+    }
+
+    /// The ID in the last segment of a paste URL's path, ignoring the query and
+    /// fragment.
+    pub fn from_url(url: &Url) -> Result<Self, ValidationError> {
+        url.path_segments()
+            .and_then(|segments| segments.rev().find(|segment| !segment.is_empty()))
+            .ok_or_else(|| ValidationError::InvalidPasteId(url.to_string()))
+            .and_then(Self::check)
+    }
+
+    /// Check a bare ID, which is all that's ever put in the path of a request.
+    pub(crate) fn check(id: &str) -> Result<Self, ValidationError> {
+        let valid = !id.is_empty()
+            && id
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_');
+        match valid {
+            true => Ok(PasteId(id.to_string())),
+            false => Err(ValidationError::InvalidPasteId(id.to_string())),
+        }
+    }
+
+    /// The ID.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The ID, as an owned string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for PasteId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for PasteId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<PasteId> for String {
+    fn from(id: PasteId) -> Self {
+        id.0
+    }
+}
+
+impl FromStr for PasteId {
+    type Err = ValidationError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input)
+    }
+}
+
+impl TryFrom<&str> for PasteId {
+    type Error = ValidationError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Self::parse(input)
+    }
+}
+
+impl TryFrom<String> for PasteId {
+    type Error = ValidationError;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        Self::parse(&input)
+    }
+}
+
+impl TryFrom<&Url> for PasteId {
+    type Error = ValidationError;
+
+    fn try_from(url: &Url) -> Result<Self, Self::Error> {
+        Self::from_url(url)
+    }
+}
+
+/// What [`GetPasteBuilder::id`](crate::GetPasteBuilder::id) accepts: an ID or paste
+/// URL as a `&str` or `String`, a [`PasteId`], or a [`Url`].
+pub trait IntoPasteId {
+    /// Parse and check the ID.
+    fn into_paste_id(self) -> Result<PasteId, ValidationError>;
+}
+
+impl IntoPasteId for PasteId {
+    fn into_paste_id(self) -> Result<PasteId, ValidationError> {
+        Ok(self)
+    }
+}
+
+impl IntoPasteId for &PasteId {
+    fn into_paste_id(self) -> Result<PasteId, ValidationError> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoPasteId for &str {
+    fn into_paste_id(self) -> Result<PasteId, ValidationError> {
+        PasteId::parse(self)
+    }
+}
+
+impl IntoPasteId for String {
+    fn into_paste_id(self) -> Result<PasteId, ValidationError> {
+        PasteId::parse(&self)
+    }
+}
+
+impl IntoPasteId for &String {
+    fn into_paste_id(self) -> Result<PasteId, ValidationError> {
+        PasteId::parse(self)
+    }
+}
+
+impl IntoPasteId for Url {
+    fn into_paste_id(self) -> Result<PasteId, ValidationError> {
+        PasteId::from_url(&self)
+    }
+}
+
+impl IntoPasteId for &Url {
+    fn into_paste_id(self) -> Result<PasteId, ValidationError> {
+        PasteId::from_url(self)
+    }
+}
@@ -0,0 +1,237 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "pastebin")))]
+
+//! A [`PasteProvider`] for [pastebin.com](https://pastebin.com).
+//!
+//! Requests are built with myust's own builders:
+//!
+//! ```rust,no_run
+//! use myust::{
+//!     pastebin::{PastebinProvider, Privacy},
+//!     CreatePasteRequest, Expiry, PasteBuilder, PasteProvider,
+//! };
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let pastebin = PastebinProvider::new("dev key").privacy(Privacy::Unlisted);
+//! let mut paste = PasteBuilder::default();
+//! paste.filename("notes.txt").content("Hello from myust!").expires(Expiry {
+//!     days: 1,
+//!     ..Default::default()
+//! });
+//! let paste = pastebin.create(CreatePasteRequest::from(paste)).await?;
+//! println!("{}", pastebin.url_for(&paste.id));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! pastebin.com stores one file per paste and has no passwords. Its fixed expiration
+//! periods are mapped from [`Expiry`](crate::Expiry) by rounding up to the next one,
+//! capped at a year.
+
+use std::{fmt, time::SystemTime};
+
+use async_trait::async_trait;
+use reqwest::StatusCode;
+
+use crate::{
+    provider::{send_text, single_file},
+    utils::{format_timestamp, redact_option, REDACTED},
+    CreatePasteRequest, Error, Expiry, File, PasteProvider, PasteResult,
+};
+
+/// The default URL of pastebin.com.
+pub const PASTEBIN_URL: &str = "https://pastebin.com";
+
+/// pastebin.com's expiration periods, in seconds, from shortest to longest.
+const EXPIRATIONS: [(u64, &str); 8] = [
+    (10 * 60, "10M"),
+    (60 * 60, "1H"),
+    (24 * 60 * 60, "1D"),
+    (7 * 24 * 60 * 60, "1W"),
+    (14 * 24 * 60 * 60, "2W"),
+    (30 * 24 * 60 * 60, "1M"),
+    (182 * 24 * 60 * 60, "6M"),
+    (365 * 24 * 60 * 60, "1Y"),
+];
+
+/// Who can see a paste created on pastebin.com.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Privacy {
+    /// Listed publicly.
+    Public,
+    /// Reachable by anyone with the link.
+    #[default]
+    Unlisted,
+    /// Only visible to its author, requires a user key.
+    Private,
+}
+
+impl Privacy {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Public => "0",
+            Self::Unlisted => "1",
+            Self::Private => "2",
+        }
+    }
+}
+
+/// The pastebin.com expiration period lasting at least `expiry`.
+fn expire_date(expiry: &Expiry) -> &'static str {
+    let seconds = expiry.total().as_secs();
+    let [.., (_, longest)] = EXPIRATIONS;
+    EXPIRATIONS
+        .iter()
+        .find(|(period, _)| seconds <= *period)
+        .map_or(longest, |(_, date)| *date)
+}
+
+/// A client for pastebin.com.
+#[derive(Clone)]
+pub struct PastebinProvider {
+    inner: reqwest::Client,
+    dev_key: String,
+    user_key: Option<String>,
+    base_url: String,
+    privacy: Privacy,
+}
+
+impl PastebinProvider {
+    /// Instantiate a provider with a developer API key.
+    pub fn new(dev_key: impl Into<String>) -> Self {
+        PastebinProvider {
+            inner: reqwest::Client::new(),
+            dev_key: dev_key.into(),
+            user_key: None,
+            base_url: PASTEBIN_URL.to_string(),
+            privacy: Privacy::default(),
+        }
+    }
+
+    /// Act as a user, to create private pastes and delete pastes.
+    ///
+    /// A user key is obtained from pastebin.com's login API.
+    pub fn user_key(mut self, user_key: impl Into<String>) -> Self {
+        self.user_key = Some(user_key.into());
+        self
+    }
+
+    /// Who can see the pastes created. Defaults to [`Privacy::Unlisted`].
+    pub fn privacy(mut self, privacy: Privacy) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
+    /// Use a different URL, e.g. a local test server.
+    ///
+    /// Defaults to [`PASTEBIN_URL`].
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    async fn post(&self, mut form: Vec<(&str, String)>) -> Result<String, Error> {
+        form.push(("api_dev_key", self.dev_key.clone()));
+        if let Some(user_key) = &self.user_key {
+            form.push(("api_user_key", user_key.clone()));
+        }
+        let request = self
+            .inner
+            .post(format!("{}/api/api_post.php", self.base_url))
+            .form(&form);
+        let body = send_text("pastebin", request).await?;
+        // errors are sometimes reported with a successful status
+        match body.starts_with("Bad API request") {
+            true => Err(Error::Provider {
+                provider: "pastebin",
+                code: StatusCode::OK,
+                message: Some(body.trim().to_string()),
+            }),
+            false => Ok(body),
+        }
+    }
+}
+
+#[async_trait]
+impl PasteProvider for PastebinProvider {
+    fn name(&self) -> &str {
+        "pastebin"
+    }
+
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        let expires = request
+            .expires
+            .as_ref()
+            .filter(|expiry| !expiry.is_never())
+            .map(expire_date)
+            .unwrap_or("N");
+        let file = single_file(request)?;
+        let url = self
+            .post(vec![
+                ("api_option", "paste".to_string()),
+                ("api_paste_code", file.content.clone()),
+                ("api_paste_name", file.filename.clone()),
+                ("api_paste_private", self.privacy.code().to_string()),
+                ("api_paste_expire_date", expires.to_string()),
+            ])
+            .await?;
+        let id = url.trim().rsplit('/').next().unwrap_or_default();
+        Ok(PasteResult {
+            created_at: format_timestamp(humantime::format_rfc3339(SystemTime::now()))
+                .unwrap_or_default(),
+            expires: None,
+            files: vec![file],
+            id: id.to_string(),
+            idempotency_key: None,
+            line_endings: None,
+        })
+    }
+
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error> {
+        if password.is_some() {
+            return Err(Error::Unsupported("this provider can't set a password"));
+        }
+        let request = self.inner.get(format!("{}/raw/{}", self.base_url, id));
+        let content = send_text("pastebin", request).await?;
+        Ok(PasteResult {
+            created_at: String::new(),
+            expires: None,
+            files: vec![File {
+                filename: id.to_string(),
+                content,
+                syntax: None,
+            }],
+            id: id.to_string(),
+            idempotency_key: None,
+            line_endings: None,
+        })
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        if self.user_key.is_none() {
+            return Err(Error::Unsupported(
+                "deleting a pastebin.com paste requires a user key",
+            ));
+        }
+        self.post(vec![
+            ("api_option", "delete".to_string()),
+            ("api_paste_key", id.to_string()),
+        ])
+        .await
+        .map(|_| ())
+    }
+
+    fn url_for(&self, id: &str) -> String {
+        format!("{}/{}", self.base_url, id)
+    }
+}
+
+impl fmt::Debug for PastebinProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PastebinProvider")
+            .field("base_url", &self.base_url)
+            .field("dev_key", &REDACTED)
+            .field("user_key", &redact_option(&self.user_key))
+            .field("privacy", &self.privacy)
+            .finish()
+    }
+}
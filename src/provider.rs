@@ -0,0 +1,383 @@
+use std::{
+    collections::HashMap,
+    fmt, io,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use reqwest::{header, RequestBuilder, StatusCode};
+use serde_json::Value;
+
+use crate::{utils::lock, CreatePasteRequest, Error, File, PasteResult};
+
+/// A paste service, so applications can work with several backends through one
+/// interface.
+///
+/// [`Client`](crate::Client) is the mystb.in implementation:
+///
+/// ```rust,no_run
+/// use myust::{Client, CreatePasteRequest, File, PasteProvider};
+///
+/// # async fn run() -> Result<(), myust::Error> {
+/// let provider: Box<dyn PasteProvider> = Box::new(Client::new());
+/// let paste = provider
+///     .create(CreatePasteRequest {
+///         files: vec![File {
+///             filename: "myust.txt".to_string(),
+///             content: "Hello from myust!".to_string(),
+///             ..Default::default()
+///         }],
+///         ..Default::default()
+///     })
+///     .await?;
+/// println!("{}", provider.url_for(&paste.id));
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait PasteProvider: Send + Sync {
+    /// A short name of the service, e.g. `mystbin`.
+    fn name(&self) -> &str;
+
+    /// Create a paste.
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error>;
+
+    /// Get a paste, with its password if it has one.
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error>;
+
+    /// Delete a paste.
+    async fn delete(&self, id: &str) -> Result<(), Error>;
+
+    /// The URL where a paste can be viewed in a browser.
+    fn url_for(&self, id: &str) -> String;
+}
+
+#[async_trait]
+impl<T: PasteProvider + ?Sized> PasteProvider for Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        (**self).create(request).await
+    }
+
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error> {
+        (**self).get(id, password).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        (**self).delete(id).await
+    }
+
+    fn url_for(&self, id: &str) -> String {
+        (**self).url_for(id)
+    }
+}
+
+#[async_trait]
+impl<T: PasteProvider + ?Sized> PasteProvider for Box<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        (**self).create(request).await
+    }
+
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error> {
+        (**self).get(id, password).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        (**self).delete(id).await
+    }
+
+    fn url_for(&self, id: &str) -> String {
+        (**self).url_for(id)
+    }
+}
+
+/// A paste created through a [`FallbackProvider`], with the provider that served it.
+#[derive(Clone, Debug)]
+pub struct ServedPaste {
+    /// The name of the provider that created the paste.
+    pub provider: String,
+    /// The URL where the paste can be viewed.
+    pub url: String,
+    /// The created paste.
+    pub paste: PasteResult,
+}
+
+/// A provider trying several others in order, moving on to the next one when a
+/// provider is unavailable: it returns a 5xx status or the request fails to be sent,
+/// e.g. on a timeout.
+///
+/// Other errors, such as a rejected paste, are returned right away. Pastes are
+/// remembered with the provider that created them, so `get`, `delete` and `url_for`
+/// reach the right service.
+///
+/// ```rust
+/// use myust::{Client, FallbackProvider};
+///
+/// let provider = FallbackProvider::new()
+///     .provider(Client::new())
+///     .provider(Client::new().base_url("https://mystbin.internal.example"));
+/// ```
+#[derive(Clone, Default)]
+pub struct FallbackProvider {
+    providers: Vec<Arc<dyn PasteProvider>>,
+    served: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+/// Whether an error means the provider is unavailable, rather than the request
+/// being wrong.
+fn is_unavailable(err: &Error) -> bool {
+    match err {
+        Error::Api(err) => err.code.is_server_error(),
+        Error::Provider { code, .. } => code.is_server_error(),
+        Error::Timeout(_) | Error::Connect(_) | Error::Http(_) | Error::Io(_) => true,
+        _ => false,
+    }
+}
+
+fn is_not_found(err: &Error) -> bool {
+    match err {
+        Error::Api(err) => err.code == StatusCode::NOT_FOUND,
+        Error::Provider { code, .. } => *code == StatusCode::NOT_FOUND,
+        _ => false,
+    }
+}
+
+impl FallbackProvider {
+    /// Instantiate a provider without any provider to fall back on.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider, tried after the ones added before.
+    pub fn provider(mut self, provider: impl PasteProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    /// Create a paste with the first available provider.
+    pub async fn create_paste(&self, request: CreatePasteRequest) -> Result<ServedPaste, Error> {
+        let mut last = Error::Unsupported("no provider is configured");
+        for (index, provider) in self.providers.iter().enumerate() {
+            match provider.create(request.clone()).await {
+                Ok(paste) => {
+                    lock(&self.served).insert(paste.id.clone(), index);
+                    return Ok(ServedPaste {
+                        provider: provider.name().to_string(),
+                        url: provider.url_for(&paste.id),
+                        paste,
+                    });
+                }
+                Err(err) if is_unavailable(&err) => last = err,
+                Err(err) => return Err(err),
+            }
+        }
+        Err(last)
+    }
+
+    /// The name of the provider that served a paste, if it was created or fetched
+    /// through this one.
+    pub fn served_by(&self, id: &str) -> Option<&str> {
+        self.route(id).map(|provider| provider.name())
+    }
+
+    fn route(&self, id: &str) -> Option<&Arc<dyn PasteProvider>> {
+        let index = *lock(&self.served).get(id)?;
+        self.providers.get(index)
+    }
+}
+
+#[async_trait]
+impl PasteProvider for FallbackProvider {
+    fn name(&self) -> &str {
+        "fallback"
+    }
+
+    async fn create(&self, request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        self.create_paste(request).await.map(|served| served.paste)
+    }
+
+    /// Get a paste from the provider that served it, or else from the first
+    /// provider that has it.
+    async fn get(&self, id: &str, password: Option<&str>) -> Result<PasteResult, Error> {
+        if let Some(provider) = self.route(id) {
+            return provider.get(id, password).await;
+        }
+        let mut last = Error::Unsupported("no provider is configured");
+        for (index, provider) in self.providers.iter().enumerate() {
+            match provider.get(id, password).await {
+                Ok(paste) => {
+                    lock(&self.served).insert(id.to_string(), index);
+                    return Ok(paste);
+                }
+                Err(err) if is_unavailable(&err) || is_not_found(&err) => last = err,
+                Err(err) => return Err(err),
+            }
+        }
+        Err(last)
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        let provider = match self.route(id) {
+            Some(provider) => provider,
+            None => self
+                .providers
+                .first()
+                .ok_or(Error::Unsupported("no provider is configured"))?,
+        };
+        provider.delete(id).await?;
+        lock(&self.served).remove(id);
+        Ok(())
+    }
+
+    /// The URL from the provider that served the paste, or else the first one.
+    fn url_for(&self, id: &str) -> String {
+        self.route(id)
+            .or_else(|| self.providers.first())
+            .map(|provider| provider.url_for(id))
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for FallbackProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FallbackProvider")
+            .field(
+                "providers",
+                &self.providers.iter().map(|p| p.name()).collect::<Vec<_>>(),
+            )
+            .field("served", &lock(&self.served).len())
+            .finish()
+    }
+}
+
+/// A provider [`mirror_paste`] failed to publish to.
+#[derive(Debug)]
+pub struct MirrorFailure {
+    /// The name of the provider.
+    pub provider: String,
+    /// Why the paste couldn't be created.
+    pub error: Error,
+}
+
+/// The outcome of [`mirror_paste`], in the order of the providers.
+#[derive(Debug, Default)]
+pub struct MirrorResult {
+    /// The copies that were created.
+    pub succeeded: Vec<ServedPaste>,
+    /// The providers that failed.
+    pub failed: Vec<MirrorFailure>,
+}
+
+impl MirrorResult {
+    /// The URLs of every copy created.
+    pub fn urls(&self) -> Vec<&str> {
+        self.succeeded
+            .iter()
+            .map(|served| served.url.as_str())
+            .collect()
+    }
+}
+
+/// Publish the same paste to several providers concurrently, e.g. mystb.in and a
+/// backup gist.
+///
+/// A provider failing doesn't stop the others, every failure is reported in the result.
+///
+/// ```rust,no_run
+/// use myust::{mirror_paste, Client, CreatePasteRequest, PasteProvider};
+///
+/// # async fn run(backup: &dyn PasteProvider, request: CreatePasteRequest) {
+/// let mystbin = Client::new();
+/// let mirrored = mirror_paste(&[&mystbin, backup], request).await;
+/// for url in mirrored.urls() {
+///     println!("{url}");
+/// }
+/// # }
+/// ```
+pub async fn mirror_paste(
+    providers: &[&dyn PasteProvider],
+    request: CreatePasteRequest,
+) -> MirrorResult {
+    let results = join_all(providers.iter().map(|provider| {
+        let request = request.clone();
+        async move { (provider, provider.create(request).await) }
+    }))
+    .await;
+    let mut mirrored = MirrorResult::default();
+    for (provider, result) in results {
+        match result {
+            Ok(paste) => mirrored.succeeded.push(ServedPaste {
+                provider: provider.name().to_string(),
+                url: provider.url_for(&paste.id),
+                paste,
+            }),
+            Err(error) => mirrored.failed.push(MirrorFailure {
+                provider: provider.name().to_string(),
+                error,
+            }),
+        }
+    }
+    mirrored
+}
+
+/// Report a request that failed to be sent or read by a provider using its own
+/// HTTP client.
+#[cfg_attr(
+    not(any(feature = "gist", feature = "haste", feature = "pastebin")),
+    allow(dead_code)
+)]
+pub(crate) fn http_error(err: reqwest::Error) -> Error {
+    Error::Io(io::Error::other(err.without_url()))
+}
+
+/// Take the only file of a request, for services storing one file per paste.
+#[cfg_attr(not(any(feature = "haste", feature = "pastebin")), allow(dead_code))]
+pub(crate) fn single_file(request: CreatePasteRequest) -> Result<File, Error> {
+    if request.password.is_some() {
+        return Err(Error::Unsupported("this provider can't set a password"));
+    }
+    let mut files = request.files.into_iter();
+    match (files.next(), files.next()) {
+        (Some(file), None) => Ok(file),
+        _ => Err(Error::Unsupported(
+            "this provider stores exactly one file per paste",
+        )),
+    }
+}
+
+/// Send a provider's request, returning the body of a successful response.
+#[cfg_attr(not(any(feature = "haste", feature = "pastebin")), allow(dead_code))]
+pub(crate) async fn send_text(
+    provider: &'static str,
+    request: RequestBuilder,
+) -> Result<String, Error> {
+    let response = request
+        .header(header::USER_AGENT, "myust")
+        .send()
+        .await
+        .map_err(http_error)?;
+    let status = response.status();
+    let body = response.text().await.map_err(http_error)?;
+    match status.is_success() {
+        true => Ok(body),
+        false => Err(Error::Provider {
+            provider,
+            code: status,
+            message: match serde_json::from_str::<Value>(&body) {
+                Ok(json) => json
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .map(|m| m.to_string()),
+                Err(_) => Some(body.trim().to_string()).filter(|m| !m.is_empty()),
+            },
+        }),
+    }
+}
@@ -0,0 +1,370 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+
+//! Recording operations attempted while offline, and running them once the API is
+//! reachable again.
+//!
+//! ```rust,no_run
+//! use myust::{queue::{Operation, OperationQueue}, Client, File};
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new().auth("YOUR_MYSTBIN_TOKEN").await;
+//! let queue = OperationQueue::open("queue.json")?;
+//! let operation = Operation::Create {
+//!     files: vec![File { filename: "notes.txt".into(), content: "...".into(), ..Default::default() }],
+//!     password: None,
+//!     expires: None,
+//! };
+//! // runs now if the API is reachable, or later with `flush_queue`
+//! client.run_or_queue(operation, &queue).await?;
+//!
+//! let flushed = client
+//!     .flush_queue(&queue, |o| o.on_conflict(|op, err| eprintln!("{:?}: {}", op, err)))
+//!     .await?;
+//! println!("{} operations left", flushed.remaining);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    utils::{lock, new_idempotency_key},
+    Client, CreatePasteRequest, Error, Expiry, File, PasteProvider, PasteResult,
+};
+
+/// An operation that can be queued.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    /// Create a paste.
+    Create {
+        /// The paste's files.
+        files: Vec<File>,
+        /// The paste's password, if any.
+        password: Option<String>,
+        /// The paste's expiration date, if any.
+        expires: Option<Expiry>,
+    },
+    /// Delete a paste.
+    Delete {
+        /// The paste's ID.
+        id: String,
+    },
+    /// Bookmark a paste.
+    AddBookmark {
+        /// The paste's ID.
+        id: String,
+    },
+    /// Remove a paste from the bookmarks.
+    RemoveBookmark {
+        /// The paste's ID.
+        id: String,
+    },
+}
+
+impl From<CreatePasteRequest> for Operation {
+    /// The files are sent as they are, compression isn't kept.
+    fn from(request: CreatePasteRequest) -> Self {
+        Operation::Create {
+            files: request.files,
+            password: request.password,
+            expires: request.expires,
+        }
+    }
+}
+
+/// A queue of operations, kept in memory or in a JSON file.
+pub struct OperationQueue {
+    path: Option<PathBuf>,
+    operations: Mutex<Vec<Operation>>,
+}
+
+impl OperationQueue {
+    /// A queue stored at `path`, loading the operations it already holds.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let operations = match fs::read(&path) {
+            Ok(content) => serde_json::from_slice(&content)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => vec![],
+            Err(err) => return Err(err.into()),
+        };
+        Ok(OperationQueue {
+            path: Some(path),
+            operations: Mutex::new(operations),
+        })
+    }
+
+    /// A queue kept in memory, lost when dropped.
+    pub fn in_memory() -> Self {
+        OperationQueue {
+            path: None,
+            operations: Mutex::default(),
+        }
+    }
+
+    fn operations_mut(&self) -> MutexGuard<'_, Vec<Operation>> {
+        lock(&self.operations)
+    }
+
+    /// The queued operations, oldest first.
+    pub fn operations(&self) -> Vec<Operation> {
+        self.operations_mut().clone()
+    }
+
+    /// The number of queued operations.
+    pub fn len(&self) -> usize {
+        self.operations_mut().len()
+    }
+
+    /// Whether no operation is queued.
+    pub fn is_empty(&self) -> bool {
+        self.operations_mut().is_empty()
+    }
+
+    /// Queue an operation.
+    pub fn push(&self, operation: Operation) -> Result<(), Error> {
+        let mut operations = self.operations_mut();
+        operations.push(operation);
+        self.save(&operations)
+    }
+
+    fn front(&self) -> Option<Operation> {
+        self.operations_mut().first().cloned()
+    }
+
+    fn pop_front(&self) -> Result<(), Error> {
+        let mut operations = self.operations_mut();
+        if !operations.is_empty() {
+            operations.remove(0);
+        }
+        self.save(&operations)
+    }
+
+    /// Write the operations to a temporary file first, so the queue isn't lost if
+    /// writing is interrupted.
+    fn save(&self, operations: &[Operation]) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let temporary = path.with_extension("tmp");
+        fs::write(
+            &temporary,
+            serde_json::to_vec(operations).map_err(std::io::Error::from)?,
+        )?;
+        fs::rename(temporary, path)?;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for OperationQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OperationQueue")
+            .field("path", &self.path)
+            .field("operations", &self.len())
+            .finish()
+    }
+}
+
+type ConflictCallback<'a> = Box<dyn FnMut(&Operation, &Error) + Send + 'a>;
+
+/// The options of [`Client::flush_queue`].
+pub struct FlushOptions<'a> {
+    retries: u32,
+    retry_delay: Duration,
+    on_conflict: Option<ConflictCallback<'a>>,
+}
+
+impl<'a> FlushOptions<'a> {
+    /// How many times an operation is retried after an error
+    /// [retryable](crate::Error::is_retryable_for) for its HTTP method before
+    /// flushing stops. Defaults to 3.
+    pub fn retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+
+    /// How long to wait before the first retry, doubled on each retry. Defaults to
+    /// one second.
+    pub fn retry_delay(&mut self, delay: Duration) -> &mut Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// (optional) A callback receiving the operations rejected by the API, e.g. the
+    /// deletion of a paste that's already gone. Rejected operations are dropped from
+    /// the queue.
+    pub fn on_conflict(
+        &mut self,
+        on_conflict: impl FnMut(&Operation, &Error) + Send + 'a,
+    ) -> &mut Self {
+        self.on_conflict = Some(Box::new(on_conflict));
+        self
+    }
+}
+
+impl Default for FlushOptions<'_> {
+    fn default() -> Self {
+        FlushOptions {
+            retries: 3,
+            retry_delay: Duration::from_secs(1),
+            on_conflict: None,
+        }
+    }
+}
+
+impl fmt::Debug for FlushOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlushOptions")
+            .field("retries", &self.retries)
+            .field("retry_delay", &self.retry_delay)
+            .field("on_conflict", &self.on_conflict.is_some())
+            .finish()
+    }
+}
+
+/// The outcome of [`Client::flush_queue`].
+#[derive(Debug, Default)]
+pub struct FlushResult {
+    /// The operations that ran, in order.
+    pub completed: Vec<Operation>,
+    /// The pastes created by the completed operations.
+    pub created: Vec<PasteResult>,
+    /// The operations rejected by the API, with the reason.
+    pub conflicts: Vec<(Operation, Error)>,
+    /// The number of operations still queued, because the API became unreachable or
+    /// kept failing.
+    pub remaining: usize,
+}
+
+impl Client {
+    /// Run an operation. A `Create` is sent with `idempotency_key`, so running it
+    /// again with the same key can't create the paste twice.
+    async fn run_operation(
+        &self,
+        operation: &Operation,
+        idempotency_key: Option<String>,
+    ) -> Result<Option<PasteResult>, Error> {
+        match operation.clone() {
+            Operation::Create {
+                files,
+                password,
+                expires,
+            } => {
+                // other fields depend on the enabled features
+                #[allow(clippy::needless_update)]
+                let request = CreatePasteRequest {
+                    files,
+                    password,
+                    expires,
+                    idempotency_key,
+                    ..Default::default()
+                };
+                self.create(request).await.map(Some)
+            }
+            Operation::Delete { id } => self.delete(&id).await.map(|_| None),
+            Operation::AddBookmark { id } => Ok(self.create_bookmark(&id).await.map(|_| None)?),
+            Operation::RemoveBookmark { id } => {
+                Ok(self.delete_bookmark(&id).await.map(|_| None)?)
+            }
+        }
+    }
+
+    /// The HTTP method an operation is sent with.
+    fn operation_method(&self, operation: &Operation) -> Method {
+        let routes = self.routes();
+        match operation {
+            Operation::Create { .. } => &routes.create_paste,
+            Operation::Delete { .. } => &routes.delete_paste,
+            Operation::AddBookmark { .. } => &routes.create_bookmark,
+            Operation::RemoveBookmark { .. } => &routes.delete_bookmark,
+        }
+        .method
+        .clone()
+    }
+
+    /// Run an operation if the API is reachable and nothing is queued before it,
+    /// or else queue it.
+    ///
+    /// Returns the created paste of a `Create` that ran, and `None` otherwise.
+    pub async fn run_or_queue(
+        &self,
+        operation: Operation,
+        queue: &OperationQueue,
+    ) -> Result<Option<PasteResult>, Error> {
+        if !queue.is_empty() || self.ping().await.is_err() {
+            queue.push(operation)?;
+            return Ok(None);
+        }
+        self.run_operation(&operation, None).await
+    }
+
+    /// Run the queued operations in order, stopping when the API is unreachable.
+    ///
+    /// Failed connections, timeouts, server errors and rate limits are retried when
+    /// it's safe for the operation's HTTP method, see [`FlushOptions`] and
+    /// [`Error::is_retryable_for`]. Operations
+    /// the API rejects are passed to the `on_conflict` callback and dropped.
+    pub async fn flush_queue<'a, F>(
+        &self,
+        queue: &OperationQueue,
+        options: F,
+    ) -> Result<FlushResult, Error>
+    where
+        F: for<'b> FnOnce(&'b mut FlushOptions<'a>) -> &'b mut FlushOptions<'a>,
+    {
+        let mut options_builder = FlushOptions::default();
+        let options = options(&mut options_builder);
+        let mut result = FlushResult::default();
+        'queue: while let Some(operation) = queue.front() {
+            if self.ping().await.is_err() {
+                break;
+            }
+            let mut delay = options.retry_delay;
+            let mut attempt = 0;
+            // retries reuse the key, in case a failed attempt created the paste
+            let idempotency_key = new_idempotency_key();
+            loop {
+                match self
+                    .run_operation(&operation, Some(idempotency_key.clone()))
+                    .await
+                {
+                    Ok(paste) => {
+                        result.created.extend(paste);
+                        result.completed.push(operation);
+                        break;
+                    }
+                    Err(err) if err.is_retryable_for(&self.operation_method(&operation)) => {
+                        if attempt == options.retries {
+                            break 'queue;
+                        }
+                        attempt += 1;
+                        tokio::time::sleep(delay).await;
+                        delay = delay.saturating_mul(2);
+                    }
+                    Err(err) => {
+                        if let Some(on_conflict) = &mut options.on_conflict {
+                            on_conflict(&operation, &err);
+                        }
+                        result.conflicts.push((operation, err));
+                        break;
+                    }
+                }
+            }
+            queue.pop_front()?;
+        }
+        result.remaining = queue.len();
+        Ok(result)
+    }
+}
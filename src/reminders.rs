@@ -0,0 +1,91 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "reminders")))]
+
+//! Being reminded of pastes about to expire, e.g. temporary handoffs that should be
+//! saved elsewhere before they're gone.
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use futures_util::StreamExt;
+//! use myust::Client;
+//!
+//! # async fn run() {
+//! let client = Client::new().auth("YOUR_MYSTBIN_TOKEN").await;
+//! let hour = Duration::from_secs(60 * 60);
+//! let mut reminders = Box::pin(client.expiry_reminders(hour, Duration::from_secs(5 * 60)));
+//! while let Some(paste) = reminders.next().await {
+//!     match paste {
+//!         Ok(paste) => println!("{} expires at {:?}", paste.id, paste.expires),
+//!         Err(err) => eprintln!("listing pastes failed: {}", err),
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::{
+    collections::{HashSet, VecDeque},
+    time::Duration,
+};
+
+use futures_util::{stream, Stream};
+
+use crate::{Client, Error, UserPaste};
+
+struct Reminders {
+    client: Client,
+    window: Duration,
+    interval: Duration,
+    reminded: HashSet<String>,
+    pending: VecDeque<Result<UserPaste, Error>>,
+    started: bool,
+}
+
+impl Reminders {
+    async fn next(mut self) -> Option<(Result<UserPaste, Error>, Self)> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some((item, self));
+            }
+            if self.started {
+                tokio::time::sleep(self.interval).await;
+            }
+            self.started = true;
+            match self.client.expiring_within(self.window).await {
+                Ok(pastes) => {
+                    let listed: HashSet<String> = pastes.iter().map(|p| p.id.clone()).collect();
+                    for paste in pastes {
+                        if self.reminded.insert(paste.id.clone()) {
+                            self.pending.push_back(Ok(paste));
+                        }
+                    }
+                    // forget pastes that expired or were deleted
+                    self.reminded.retain(|id| listed.contains(id));
+                }
+                Err(err) => self.pending.push_back(Err(err)),
+            }
+        }
+    }
+}
+
+impl Client {
+    /// A stream yielding each paste of the authenticated user once, when it comes to
+    /// expire within `window`.
+    ///
+    /// The pastes are listed right away, then every `interval`. Listing errors are
+    /// yielded too, and the stream goes on after them. It never ends, so drop it to
+    /// stop.
+    pub fn expiry_reminders(
+        &self,
+        window: Duration,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<UserPaste, Error>> + Send + 'static {
+        let reminders = Reminders {
+            client: self.clone(),
+            window,
+            interval,
+            reminded: HashSet::new(),
+            pending: VecDeque::new(),
+            started: false,
+        };
+        stream::unfold(reminders, Reminders::next)
+    }
+}
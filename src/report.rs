@@ -0,0 +1,136 @@
+//! Serializable summaries of operations, for scripts and structured logs.
+//!
+//! The `myust` binary prints these with `--json`, and library callers can log them
+//! the same way:
+//!
+//! ```rust,no_run
+//! use myust::{report::CreateReport, Client};
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new();
+//! let paste = client
+//!     .create_paste(|p| p.filename("myust.txt").content("Hello from myust!"))
+//!     .await?;
+//! let report = CreateReport::new(&paste, client.paste_url(&paste.id));
+//! println!("{}", serde_json::to_string(&report).unwrap());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{DeleteResult, PasteResult, UserPaste};
+
+/// A created paste.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CreateReport {
+    /// The paste's ID.
+    pub id: String,
+    /// The URL where the paste can be viewed.
+    pub url: String,
+    /// The paste's creation date.
+    pub created_at: String,
+    /// The paste's expiration date, if any.
+    pub expires: Option<String>,
+    /// The paste's filenames.
+    pub files: Vec<String>,
+    /// A hash of the paste's files and password, recorded by a
+    /// [`Journal`](crate::journal::Journal) to find duplicate uploads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+impl CreateReport {
+    /// Summarize a paste viewed at `url`, e.g. from [`Client::paste_url`](crate::Client::paste_url).
+    pub fn new(paste: &PasteResult, url: impl Into<String>) -> Self {
+        CreateReport {
+            id: paste.id.clone(),
+            url: url.into(),
+            created_at: paste.created_at.clone(),
+            expires: paste.expires.clone(),
+            files: paste.files.iter().map(|f| f.filename.clone()).collect(),
+            content_hash: None,
+        }
+    }
+}
+
+/// Deleted pastes.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DeleteReport {
+    /// The IDs of the deleted pastes.
+    pub deleted: Vec<String>,
+    /// The IDs of the pastes that couldn't be deleted.
+    pub failed: Vec<String>,
+}
+
+impl From<DeleteResult> for DeleteReport {
+    fn from(result: DeleteResult) -> Self {
+        DeleteReport {
+            deleted: result.succeeded.unwrap_or_default(),
+            failed: result.failed.unwrap_or_default(),
+        }
+    }
+}
+
+/// An item an operation failed on, with the reason.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Failure {
+    /// The ID or path of the item.
+    pub id: String,
+    /// Why it failed.
+    pub error: String,
+}
+
+/// Pastes exported to disk, e.g. by [`Client::backup_account`](crate::Client::backup_account).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExportReport {
+    /// Where the pastes were written.
+    pub path: String,
+    /// The IDs of the exported pastes.
+    pub pastes: Vec<String>,
+    /// The IDs of the exported bookmarks.
+    pub bookmarks: Vec<String>,
+    /// The pastes that couldn't be exported.
+    pub failed: Vec<Failure>,
+}
+
+#[cfg(feature = "backup")]
+impl ExportReport {
+    /// Summarize a backup written to `path`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "backup")))]
+    pub fn from_backup(path: impl Into<String>, backup: &crate::backup::BackupResult) -> Self {
+        ExportReport {
+            path: path.into(),
+            pastes: backup.pastes.clone(),
+            bookmarks: backup.bookmarks.clone(),
+            failed: backup
+                .failed
+                .iter()
+                .map(|(id, err)| Failure {
+                    id: id.clone(),
+                    error: err.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The usage of an account, see [`Client::account_stats`](crate::Client::account_stats).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AccountStats {
+    /// The number of pastes.
+    pub pastes: usize,
+    /// The number of files of the fetched pastes.
+    pub files: usize,
+    /// The size of the fetched pastes' content, in bytes.
+    pub total_size: usize,
+    /// The IDs of the pastes whose files couldn't be fetched, e.g. because of a
+    /// password. They aren't counted in `files` and `total_size`.
+    pub unfetched: Vec<String>,
+    /// The number of pastes created each month, keyed by `YYYY-MM`.
+    pub per_month: BTreeMap<String, usize>,
+    /// The paste expiring soonest, if any expires.
+    pub soonest_expiry: Option<UserPaste>,
+}
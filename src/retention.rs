@@ -0,0 +1,151 @@
+//! Deleting old pastes of an account according to rules, e.g. to keep a long-lived
+//! bot account tidy.
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use myust::{retention::RetentionPolicy, Client};
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new().auth("YOUR_MYSTBIN_TOKEN").await;
+//! let policy = RetentionPolicy::new()
+//!     .max_age(Duration::from_secs(30 * 24 * 60 * 60))
+//!     .max_count(200)
+//!     .dry_run(true);
+//! let report = client.apply_retention(&policy).await?;
+//! println!("would delete {:?}", report.deleted);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{utils::parse_timestamp, Client, Error};
+
+/// Rules deciding which pastes of an account are deleted by
+/// [`Client::apply_retention`].
+///
+/// A paste is deleted if any rule matches it. Without rules, nothing is deleted.
+#[derive(Clone, Debug, Default)]
+pub struct RetentionPolicy {
+    max_age: Option<Duration>,
+    max_count: Option<usize>,
+    keep_bookmarked: bool,
+    dry_run: bool,
+}
+
+impl RetentionPolicy {
+    /// A policy without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delete the pastes created more than `age` ago.
+    pub fn max_age(mut self, age: Duration) -> Self {
+        self.max_age = Some(age);
+        self
+    }
+
+    /// Keep only the `count` most recent pastes.
+    pub fn max_count(mut self, count: usize) -> Self {
+        self.max_count = Some(count);
+        self
+    }
+
+    /// Never delete pastes the user bookmarked. They don't count towards
+    /// [`max_count`](Self::max_count) either.
+    pub fn keep_bookmarked(mut self, keep: bool) -> Self {
+        self.keep_bookmarked = keep;
+        self
+    }
+
+    /// Only report the pastes that would be deleted.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// The outcome of [`Client::apply_retention`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RetentionReport {
+    /// Whether nothing was actually deleted.
+    pub dry_run: bool,
+    /// The IDs of the kept pastes, newest first.
+    pub kept: Vec<String>,
+    /// The IDs of the deleted pastes, or of those that would be deleted in a dry run.
+    pub deleted: Vec<String>,
+    /// The IDs of the pastes that couldn't be deleted.
+    pub failed: Vec<String>,
+}
+
+impl Client {
+    /// Delete the pastes of the authenticated user matched by a policy.
+    ///
+    /// Pastes are listed page by page and deleted in batches. Pastes whose creation
+    /// date can't be read are always kept.
+    pub async fn apply_retention(
+        &self,
+        policy: &RetentionPolicy,
+    ) -> Result<RetentionReport, Error> {
+        let mut pastes: Vec<(Option<SystemTime>, String)> = self
+            .all_user_pastes()
+            .await?
+            .into_iter()
+            .map(|paste| (parse_timestamp(&paste.created_at), paste.id))
+            .collect();
+        // newest first, undated pastes before any other
+        pastes.sort_by(|(a, _), (b, _)| match (a, b) {
+            (Some(a), Some(b)) => b.cmp(a),
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        });
+        let bookmarked: HashSet<String> = match policy.keep_bookmarked {
+            true => self
+                .get_user_bookmarks()
+                .await?
+                .into_iter()
+                .map(|bookmark| bookmark.id)
+                .collect(),
+            false => HashSet::new(),
+        };
+
+        let now = SystemTime::now();
+        let mut report = RetentionReport {
+            dry_run: policy.dry_run,
+            ..Default::default()
+        };
+        let mut counted = 0;
+        for (created_at, id) in pastes {
+            if bookmarked.contains(&id) {
+                report.kept.push(id);
+                continue;
+            }
+            counted += 1;
+            let too_old = match (created_at, policy.max_age) {
+                (Some(created_at), Some(max_age)) => now
+                    .duration_since(created_at)
+                    .is_ok_and(|age| age > max_age),
+                _ => false,
+            };
+            let too_many =
+                created_at.is_some() && policy.max_count.is_some_and(|max| counted > max);
+            match too_old || too_many {
+                true => report.deleted.push(id),
+                false => report.kept.push(id),
+            }
+        }
+
+        if !policy.dry_run && !report.deleted.is_empty() {
+            let result = self
+                .delete_pastes(report.deleted.iter().map(String::as_str).collect())
+                .await?;
+            report.deleted = result.succeeded.unwrap_or_default();
+            report.failed = result.failed.unwrap_or_default();
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,131 @@
+use reqwest::Method;
+
+use crate::{
+    utils::{BOOKMARK_ENDPOINT, PASTE_ENDPOINT, SELF_ENDPOINT, USER_PASTES_ENDPOINT},
+    PasteId, ValidationError,
+};
+
+/// The version of the mystb.in API the client talks to, which decides the routes
+/// requests are sent to.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ApiVersion {
+    /// The current API, with pastes at `/paste` and bookmarks at `/users/bookmarks`.
+    #[default]
+    V3,
+}
+
+impl ApiVersion {
+    /// The routes of this version.
+    pub(crate) fn routes(self) -> &'static Routes {
+        match self {
+            ApiVersion::V3 => &V3_ROUTES,
+        }
+    }
+}
+
+/// The method and path of an API route. `{id}` in the path is replaced with the
+/// paste ID, or the user ID for admin routes.
+#[derive(Clone, Debug)]
+pub(crate) struct Endpoint {
+    pub(crate) method: Method,
+    pub(crate) path: &'static str,
+    /// Whether the route acts on the authenticated user's account.
+    pub(crate) user_scoped: bool,
+}
+
+impl Endpoint {
+    const fn new(method: Method, path: &'static str) -> Self {
+        Endpoint {
+            method,
+            path,
+            user_scoped: false,
+        }
+    }
+
+    const fn user(method: Method, path: &'static str) -> Self {
+        Endpoint {
+            method,
+            path,
+            user_scoped: true,
+        }
+    }
+
+    /// The full URL of the route for a paste, followed by `query`.
+    ///
+    /// Fails with [`ValidationError::InvalidPasteId`] if `id` isn't a bare
+    /// [`PasteId`], so it can't change the path of the request.
+    pub(crate) fn url(
+        &self,
+        base_url: &str,
+        id: Option<&str>,
+        query: &str,
+    ) -> Result<String, ValidationError> {
+        Ok(match id {
+            Some(id) => {
+                let id = PasteId::check(id)?;
+                format!(
+                    "{}{}{}",
+                    base_url,
+                    self.path.replace("{id}", id.as_str()),
+                    query
+                )
+            }
+            None => format!("{}{}{}", base_url, self.path, query),
+        })
+    }
+
+    /// The method and the route template, e.g. `GET /paste/{id}`.
+    pub(crate) fn label(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+}
+
+/// Every route used by the clients, so supporting another API version only takes
+/// another table.
+#[derive(Debug)]
+pub(crate) struct Routes {
+    pub(crate) ping: Endpoint,
+    pub(crate) current_user: Endpoint,
+    pub(crate) create_paste: Endpoint,
+    pub(crate) get_paste: Endpoint,
+    pub(crate) delete_paste: Endpoint,
+    pub(crate) delete_pastes: Endpoint,
+    pub(crate) user_pastes: Endpoint,
+    pub(crate) create_bookmark: Endpoint,
+    pub(crate) delete_bookmark: Endpoint,
+    pub(crate) bookmarks: Endpoint,
+    #[cfg(feature = "admin")]
+    pub(crate) admin_delete_paste: Endpoint,
+    #[cfg(feature = "admin")]
+    pub(crate) admin_users: Endpoint,
+    #[cfg(feature = "admin")]
+    pub(crate) admin_ban_user: Endpoint,
+    #[cfg(feature = "admin")]
+    pub(crate) admin_unban_user: Endpoint,
+    #[cfg(feature = "admin")]
+    pub(crate) admin_stats: Endpoint,
+}
+
+static V3_ROUTES: Routes = Routes {
+    ping: Endpoint::new(Method::GET, "/"),
+    current_user: Endpoint::user(Method::GET, SELF_ENDPOINT),
+    create_paste: Endpoint::new(Method::PUT, PASTE_ENDPOINT),
+    get_paste: Endpoint::new(Method::GET, "/paste/{id}"),
+    delete_paste: Endpoint::user(Method::DELETE, "/paste/{id}"),
+    delete_pastes: Endpoint::user(Method::DELETE, PASTE_ENDPOINT),
+    user_pastes: Endpoint::user(Method::GET, USER_PASTES_ENDPOINT),
+    create_bookmark: Endpoint::user(Method::PUT, BOOKMARK_ENDPOINT),
+    delete_bookmark: Endpoint::user(Method::DELETE, BOOKMARK_ENDPOINT),
+    bookmarks: Endpoint::user(Method::GET, BOOKMARK_ENDPOINT),
+    #[cfg(feature = "admin")]
+    admin_delete_paste: Endpoint::user(Method::DELETE, "/admin/paste/{id}"),
+    #[cfg(feature = "admin")]
+    admin_users: Endpoint::user(Method::GET, "/admin/users"),
+    #[cfg(feature = "admin")]
+    admin_ban_user: Endpoint::user(Method::PUT, "/admin/users/{id}/ban"),
+    #[cfg(feature = "admin")]
+    admin_unban_user: Endpoint::user(Method::DELETE, "/admin/users/{id}/ban"),
+    #[cfg(feature = "admin")]
+    admin_stats: Endpoint::user(Method::GET, "/admin/stats"),
+};
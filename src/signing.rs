@@ -0,0 +1,84 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "signing")))]
+
+//! Ed25519 signatures proving who uploaded a paste and that it wasn't changed since.
+//!
+//! A client with a signing key attaches a detached signature of every paste it
+//! creates as an extra file, [`SIGNATURE_FILENAME`]. Readers holding the author's
+//! public key check it with `get_paste_verified`:
+//!
+//! ```rust,no_run
+//! # async fn run(signing_key: myust::signing::SigningKey) {
+//! use myust::Client;
+//!
+//! let verifying_key = signing_key.verifying_key();
+//! let author = Client::new().signing_key(signing_key);
+//! let paste = author
+//!     .create_paste(|p| p.filename("install.sh").content("echo hi"))
+//!     .await
+//!     .unwrap();
+//!
+//! let reader = Client::new();
+//! let verified = reader
+//!     .get_paste_verified(|p| p.id(&paste.id), &verifying_key)
+//!     .await
+//!     .unwrap();
+//! # }
+//! ```
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signature, Signer, Verifier};
+
+/// ed25519 keys, to sign pastes and to verify them.
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use crate::{Error, File};
+
+/// The name of the file holding a paste's signature.
+pub const SIGNATURE_FILENAME: &str = "myust.sig";
+
+const SIGNATURE_PREFIX: &str = "myust-signature-v1 ";
+
+/// The bytes that are signed: every file's name and content, in order.
+fn message(files: &[File]) -> Vec<u8> {
+    let files: Vec<(&str, &str)> = files
+        .iter()
+        .map(|file| (file.filename.as_str(), file.content.as_str()))
+        .collect();
+    // a list of strings always serializes
+    #[allow(clippy::unwrap_used)]
+    serde_json::to_vec(&files).unwrap()
+}
+
+/// Append a signature file covering `files`.
+pub(crate) fn sign(key: &SigningKey, mut files: Vec<File>) -> Vec<File> {
+    let signature = key.sign(&message(&files));
+    files.push(File {
+        filename: SIGNATURE_FILENAME.to_string(),
+        content: format!(
+            "{}{}",
+            SIGNATURE_PREFIX,
+            STANDARD.encode(signature.to_bytes())
+        ),
+        syntax: None,
+    });
+    files
+}
+
+/// Check and remove the signature file of a fetched paste.
+pub(crate) fn verify(key: &VerifyingKey, mut files: Vec<File>) -> Result<Vec<File>, Error> {
+    let index = files
+        .iter()
+        .rposition(|file| file.filename == SIGNATURE_FILENAME)
+        .ok_or(Error::Unsigned)?;
+    let signature_file = files.remove(index);
+    let signature = signature_file
+        .content
+        .trim_end()
+        .strip_prefix(SIGNATURE_PREFIX)
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or(Error::BadSignature)?;
+    key.verify(&message(&files), &signature)
+        .map_err(|_| Error::BadSignature)?;
+    Ok(files)
+}
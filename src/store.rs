@@ -0,0 +1,468 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "store")))]
+
+//! A local SQLite index of the authenticated user's pastes and bookmarks, for instant
+//! listing and search without a round trip to the API.
+//!
+//! The API has no tags, so pastes can be tagged locally with [`PasteIndex::tag`]; tags
+//! are kept in the index.
+//!
+//! ```rust,no_run
+//! use myust::{store::PasteIndex, Client};
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new().auth("YOUR_MYSTBIN_TOKEN").await;
+//! let index = PasteIndex::open("pastes.sqlite3")?;
+//! client.sync_index(&index).await?;
+//! for paste in index.search("TODO")? {
+//!     println!("{} {:?}", paste.id, paste.files);
+//! }
+//! index.tag("EquipmentMovingExpensive", "k8s")?;
+//! let k8s = index.tagged("k8s")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashSet,
+    fmt, io,
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::{
+    utils::{lock, USER_PASTES_PAGE_SIZE},
+    Bookmark, Client, Error, File, PasteResult, UserPaste,
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS pastes (
+        id TEXT PRIMARY KEY,
+        created_at TEXT NOT NULL,
+        expires TEXT,
+        fetched INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE TABLE IF NOT EXISTS files (
+        paste_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        filename TEXT NOT NULL,
+        content TEXT NOT NULL,
+        PRIMARY KEY (paste_id, position)
+    );
+    CREATE TABLE IF NOT EXISTS tags (
+        paste_id TEXT NOT NULL,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (paste_id, tag)
+    );
+    CREATE TABLE IF NOT EXISTS bookmarks (
+        id TEXT PRIMARY KEY,
+        created_at TEXT NOT NULL,
+        expires TEXT,
+        bookmarked_at TEXT,
+        origin TEXT
+    );
+";
+
+/// The columns added to the bookmarks table since it was first released.
+const BOOKMARK_COLUMNS: [&str; 2] = ["bookmarked_at", "origin"];
+
+fn store_error(err: rusqlite::Error) -> Error {
+    Error::Io(io::Error::other(err))
+}
+
+/// A paste of the index.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IndexedPaste {
+    /// The paste's ID.
+    pub id: String,
+    /// The paste's creation date.
+    pub created_at: String,
+    /// The paste's expiration date, if any.
+    pub expires: Option<String>,
+    /// The paste's filenames, empty if its files couldn't be fetched yet.
+    pub files: Vec<String>,
+    /// The paste's local tags, sorted.
+    pub tags: Vec<String>,
+}
+
+/// The outcome of [`Client::sync_index`].
+#[derive(Debug, Default)]
+pub struct IndexSync {
+    /// The IDs of the pastes added to the index.
+    pub added: Vec<String>,
+    /// The IDs of the pastes removed from the index, e.g. deleted or expired.
+    pub removed: Vec<String>,
+    /// The number of bookmarks.
+    pub bookmarks: usize,
+    /// The pastes whose files couldn't be fetched, e.g. because of a password. They
+    /// are retried on the next sync.
+    pub failed: Vec<(String, Error)>,
+}
+
+/// A local SQLite index of pastes and bookmarks, refreshed with [`Client::sync_index`].
+pub struct PasteIndex {
+    connection: Mutex<Connection>,
+}
+
+impl PasteIndex {
+    /// Open the index stored at `path`, creating it if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::init(Connection::open(path).map_err(store_error)?)
+    }
+
+    /// Open an index kept in memory, lost when dropped.
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::init(Connection::open_in_memory().map_err(store_error)?)
+    }
+
+    fn init(connection: Connection) -> Result<Self, Error> {
+        connection.execute_batch(SCHEMA).map_err(store_error)?;
+        let columns: HashSet<String> = connection
+            .prepare("SELECT name FROM pragma_table_info('bookmarks')")
+            .and_then(|mut statement| statement.query_map([], |row| row.get(0))?.collect())
+            .map_err(store_error)?;
+        for column in BOOKMARK_COLUMNS {
+            if !columns.contains(column) {
+                connection
+                    .execute(
+                        &format!("ALTER TABLE bookmarks ADD COLUMN {} TEXT", column),
+                        [],
+                    )
+                    .map_err(store_error)?;
+            }
+        }
+        Ok(PasteIndex {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        lock(&self.connection)
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<IndexedPaste>, Error> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(sql).map_err(store_error)?;
+        let pastes = statement
+            .query_map(params, |row| {
+                Ok(IndexedPaste {
+                    id: row.get(0)?,
+                    created_at: row.get(1)?,
+                    expires: row.get(2)?,
+                    files: vec![],
+                    tags: vec![],
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(store_error)?;
+        let mut filenames = connection
+            .prepare("SELECT filename FROM files WHERE paste_id = ?1 ORDER BY position")
+            .map_err(store_error)?;
+        let mut tags = connection
+            .prepare("SELECT tag FROM tags WHERE paste_id = ?1 ORDER BY tag")
+            .map_err(store_error)?;
+        pastes
+            .into_iter()
+            .map(|mut paste| {
+                paste.files = filenames
+                    .query_map([&paste.id], |row| row.get(0))
+                    .and_then(|rows| rows.collect())
+                    .map_err(store_error)?;
+                paste.tags = tags
+                    .query_map([&paste.id], |row| row.get(0))
+                    .and_then(|rows| rows.collect())
+                    .map_err(store_error)?;
+                Ok(paste)
+            })
+            .collect()
+    }
+
+    /// Every indexed paste, newest first.
+    pub fn pastes(&self) -> Result<Vec<IndexedPaste>, Error> {
+        self.query(
+            "SELECT id, created_at, expires FROM pastes ORDER BY created_at DESC, id",
+            [],
+        )
+    }
+
+    /// The indexed pastes whose ID, filenames or content contain `text`, ignoring
+    /// ASCII case, newest first.
+    pub fn search(&self, text: &str) -> Result<Vec<IndexedPaste>, Error> {
+        let pattern = format!(
+            "%{}%",
+            text.replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        self.query(
+            "SELECT id, created_at, expires FROM pastes WHERE id LIKE ?1 ESCAPE '\\'
+             OR id IN (SELECT paste_id FROM files
+                       WHERE filename LIKE ?1 ESCAPE '\\' OR content LIKE ?1 ESCAPE '\\')
+             ORDER BY created_at DESC, id",
+            [pattern],
+        )
+    }
+
+    /// An indexed paste with its files, if they were fetched.
+    pub fn get(&self, id: &str) -> Result<Option<PasteResult>, Error> {
+        let connection = self.connection();
+        let paste = connection
+            .query_row(
+                "SELECT created_at, expires FROM pastes WHERE id = ?1",
+                [id],
+                |row| {
+                    Ok(PasteResult {
+                        created_at: row.get(0)?,
+                        expires: row.get(1)?,
+                        files: vec![],
+                        id: id.to_string(),
+                        idempotency_key: None,
+                        line_endings: None,
+                    })
+                },
+            )
+            .optional()
+            .map_err(store_error)?;
+        let Some(mut paste) = paste else {
+            return Ok(None);
+        };
+        paste.files = connection
+            .prepare("SELECT filename, content FROM files WHERE paste_id = ?1 ORDER BY position")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([id], |row| {
+                        Ok(File {
+                            filename: row.get(0)?,
+                            content: row.get(1)?,
+                            syntax: None,
+                        })
+                    })?
+                    .collect()
+            })
+            .map_err(store_error)?;
+        Ok(Some(paste))
+    }
+
+    /// Tag a paste. Tagging it twice with the same tag does nothing.
+    ///
+    /// Tags are kept until the paste is removed from the index, and only indexed
+    /// pastes are returned by [`tagged`](Self::tagged).
+    pub fn tag(&self, id: &str, tag: &str) -> Result<(), Error> {
+        self.connection()
+            .execute(
+                "INSERT OR IGNORE INTO tags (paste_id, tag) VALUES (?1, ?2)",
+                [id, tag],
+            )
+            .map(|_| ())
+            .map_err(store_error)
+    }
+
+    /// Remove a tag from a paste, returning whether it was tagged with it.
+    pub fn untag(&self, id: &str, tag: &str) -> Result<bool, Error> {
+        self.connection()
+            .execute(
+                "DELETE FROM tags WHERE paste_id = ?1 AND tag = ?2",
+                [id, tag],
+            )
+            .map(|removed| removed > 0)
+            .map_err(store_error)
+    }
+
+    /// The indexed pastes tagged with `tag`, newest first.
+    pub fn tagged(&self, tag: &str) -> Result<Vec<IndexedPaste>, Error> {
+        self.query(
+            "SELECT id, created_at, expires FROM pastes
+             WHERE id IN (SELECT paste_id FROM tags WHERE tag = ?1)
+             ORDER BY created_at DESC, id",
+            [tag],
+        )
+    }
+
+    /// Every tag in use, with the number of pastes tagged with it, sorted by tag.
+    pub fn tags(&self) -> Result<Vec<(String, usize)>, Error> {
+        let connection = self.connection();
+        let mut statement = connection
+            .prepare("SELECT tag, COUNT(*) FROM tags GROUP BY tag ORDER BY tag")
+            .map_err(store_error)?;
+        let tags = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .and_then(|rows| rows.collect())
+            .map_err(store_error)?;
+        Ok(tags)
+    }
+
+    /// The indexed bookmarks, newest first.
+    pub fn bookmarks(&self) -> Result<Vec<Bookmark>, Error> {
+        let connection = self.connection();
+        let mut statement = connection
+            .prepare(
+                "SELECT id, created_at, expires, bookmarked_at, origin FROM bookmarks
+                 ORDER BY created_at DESC, id",
+            )
+            .map_err(store_error)?;
+        let bookmarks = statement
+            .query_map([], |row| {
+                Ok(Bookmark {
+                    id: row.get(0)?,
+                    created_at: row.get(1)?,
+                    expires: row.get(2)?,
+                    bookmarked_at: row.get(3)?,
+                    origin: row.get(4)?,
+                })
+            })
+            .and_then(|rows| rows.collect())
+            .map_err(store_error)?;
+        Ok(bookmarks)
+    }
+
+    /// Whether a paste is among the indexed bookmarks.
+    pub fn is_bookmarked(&self, id: &str) -> Result<bool, Error> {
+        self.connection()
+            .query_row("SELECT 1 FROM bookmarks WHERE id = ?1", [id], |_| Ok(()))
+            .optional()
+            .map(|found| found.is_some())
+            .map_err(store_error)
+    }
+
+    /// Add listed pastes, returning the IDs of those whose files still need fetching.
+    fn upsert(&self, pastes: &[UserPaste], added: &mut Vec<String>) -> Result<Vec<String>, Error> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction().map_err(store_error)?;
+        let mut unfetched = vec![];
+        for paste in pastes {
+            let fetched: Option<bool> = transaction
+                .query_row(
+                    "SELECT fetched FROM pastes WHERE id = ?1",
+                    [&paste.id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(store_error)?;
+            transaction
+                .execute(
+                    "INSERT INTO pastes (id, created_at, expires) VALUES (?1, ?2, ?3)
+                     ON CONFLICT (id) DO UPDATE SET created_at = ?2, expires = ?3",
+                    params![paste.id, paste.created_at, paste.expires],
+                )
+                .map_err(store_error)?;
+            if fetched.is_none() {
+                added.push(paste.id.clone());
+            }
+            if fetched != Some(true) {
+                unfetched.push(paste.id.clone());
+            }
+        }
+        transaction.commit().map_err(store_error)?;
+        Ok(unfetched)
+    }
+
+    fn store_files(&self, paste: &PasteResult) -> Result<(), Error> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction().map_err(store_error)?;
+        transaction
+            .execute("DELETE FROM files WHERE paste_id = ?1", [&paste.id])
+            .map_err(store_error)?;
+        for (position, file) in paste.files.iter().enumerate() {
+            transaction
+                .execute(
+                    "INSERT INTO files (paste_id, position, filename, content)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![paste.id, position, file.filename, file.content],
+                )
+                .map_err(store_error)?;
+        }
+        transaction
+            .execute("UPDATE pastes SET fetched = 1 WHERE id = ?1", [&paste.id])
+            .map_err(store_error)?;
+        transaction.commit().map_err(store_error)
+    }
+
+    /// Remove the pastes that weren't listed, returning their IDs.
+    fn remove_unlisted(&self, listed: &HashSet<String>) -> Result<Vec<String>, Error> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction().map_err(store_error)?;
+        let indexed: Vec<String> = transaction
+            .prepare("SELECT id FROM pastes")
+            .and_then(|mut statement| statement.query_map([], |row| row.get(0))?.collect())
+            .map_err(store_error)?;
+        let removed: Vec<String> = indexed
+            .into_iter()
+            .filter(|id| !listed.contains(id))
+            .collect();
+        for id in &removed {
+            transaction
+                .execute("DELETE FROM files WHERE paste_id = ?1", [id])
+                .and_then(|_| transaction.execute("DELETE FROM tags WHERE paste_id = ?1", [id]))
+                .and_then(|_| transaction.execute("DELETE FROM pastes WHERE id = ?1", [id]))
+                .map_err(store_error)?;
+        }
+        transaction.commit().map_err(store_error)?;
+        Ok(removed)
+    }
+
+    fn replace_bookmarks(&self, bookmarks: &[Bookmark]) -> Result<(), Error> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction().map_err(store_error)?;
+        transaction
+            .execute("DELETE FROM bookmarks", [])
+            .map_err(store_error)?;
+        for bookmark in bookmarks {
+            transaction
+                .execute(
+                    "INSERT OR REPLACE INTO bookmarks
+                     (id, created_at, expires, bookmarked_at, origin)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        bookmark.id,
+                        bookmark.created_at,
+                        bookmark.expires,
+                        bookmark.bookmarked_at,
+                        bookmark.origin
+                    ],
+                )
+                .map_err(store_error)?;
+        }
+        transaction.commit().map_err(store_error)
+    }
+}
+
+impl fmt::Debug for PasteIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasteIndex").finish_non_exhaustive()
+    }
+}
+
+impl Client {
+    /// Refresh a [`PasteIndex`] with the pastes and bookmarks of the authenticated user.
+    ///
+    /// Pastes are listed page by page, and only the files of pastes new to the index
+    /// are downloaded. Pastes that are no longer listed are removed from the index.
+    pub async fn sync_index(&self, index: &PasteIndex) -> Result<IndexSync, Error> {
+        let mut result = IndexSync::default();
+        let mut listed = HashSet::new();
+        let mut unfetched = vec![];
+        for page in 1.. {
+            let pastes = self
+                .get_user_pastes(|p| p.limit(USER_PASTES_PAGE_SIZE).page(page))
+                .await?;
+            listed.extend(pastes.iter().map(|paste| paste.id.clone()));
+            unfetched.extend(index.upsert(&pastes, &mut result.added)?);
+            if pastes.len() < USER_PASTES_PAGE_SIZE as usize {
+                break;
+            }
+        }
+        for id in unfetched {
+            match self.get_paste(|p| p.id(&id)).await {
+                Ok(paste) => index.store_files(&paste)?,
+                Err(err) => result.failed.push((id, err)),
+            }
+        }
+        result.removed = index.remove_unlisted(&listed)?;
+
+        let bookmarks = self.get_user_bookmarks().await?;
+        index.replace_bookmarks(&bookmarks)?;
+        result.bookmarks = bookmarks.len();
+        Ok(result)
+    }
+}
@@ -0,0 +1,271 @@
+//! Request bodies whose file content is read from a reader while being sent.
+
+use std::{
+    fmt,
+    io::{self, Cursor, Read},
+    mem::take,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+use futures_util::{stream, Stream, StreamExt};
+use tokio::io::AsyncRead;
+
+use crate::{structs::CreatePaste, Error, Expiry, PasteBuilder, ValidationError};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Where a file's content is read from when it's not a `String`.
+pub(crate) enum ContentReader {
+    Async(Pin<Box<dyn AsyncRead + Send>>),
+    Blocking(Box<dyn Read + Send>),
+}
+
+impl fmt::Debug for ContentReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Async(_) => f.write_str("Async"),
+            Self::Blocking(_) => f.write_str("Blocking"),
+        }
+    }
+}
+
+/// A file whose content is either already in memory or still to be read.
+pub(crate) struct StreamedFile {
+    pub(crate) filename: String,
+    pub(crate) content: String,
+    pub(crate) reader: Option<ContentReader>,
+}
+
+impl From<&mut PasteBuilder> for StreamedFile {
+    fn from(paste: &mut PasteBuilder) -> Self {
+        StreamedFile {
+            filename: paste.take_filename(),
+            content: take(&mut paste.content),
+            reader: paste.reader.take(),
+        }
+    }
+}
+
+enum Piece {
+    Bytes(Vec<u8>),
+    Reader(ContentReader),
+}
+
+/// A create paste body split into serialized JSON and readers, in order.
+pub(crate) struct StreamedBody {
+    pieces: Vec<Piece>,
+}
+
+impl StreamedBody {
+    pub(crate) fn new(
+        files: Vec<StreamedFile>,
+        password: Option<&str>,
+        expires: Option<&Expiry>,
+    ) -> Result<Self, ValidationError> {
+        let rest = CreatePaste::new(&[], password, expires)?;
+        // the body serializes to an object starting with the files, and strings
+        // always serialize
+        #[allow(clippy::unwrap_used)]
+        let rest = serde_json::to_string(&rest).unwrap();
+        #[allow(clippy::unwrap_used)]
+        let rest = rest.strip_prefix(r#"{"files":[]"#).unwrap();
+
+        let mut pieces = vec![];
+        let mut json = String::from(r#"{"files":["#);
+        for (index, file) in files.into_iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str(r#"{"filename":"#);
+            json.push_str(&quote(&file.filename));
+            json.push_str(r#","content":"#);
+            match file.reader {
+                Some(reader) => {
+                    json.push('"');
+                    pieces.push(Piece::Bytes(take(&mut json).into_bytes()));
+                    pieces.push(Piece::Reader(reader));
+                    json.push('"');
+                }
+                None => json.push_str(&quote(&file.content)),
+            }
+            json.push('}');
+        }
+        json.push(']');
+        json.push_str(rest);
+        pieces.push(Piece::Bytes(json.into_bytes()));
+        Ok(StreamedBody { pieces })
+    }
+
+    /// The body as a stream of JSON chunks. Blocking readers are read on the
+    /// polling task.
+    pub(crate) fn into_stream(self) -> impl Stream<Item = io::Result<Vec<u8>>> + Send + Sync {
+        let stream = stream::iter(self.pieces).flat_map(|piece| match piece {
+            Piece::Bytes(bytes) => stream::once(async { Ok(bytes) }).boxed(),
+            Piece::Reader(reader) => escaped_stream(reader).boxed(),
+        });
+        SyncStream(Mutex::new(stream.boxed()))
+    }
+
+    /// The body as a blocking reader, for the sync client.
+    ///
+    /// Fails if a file's content comes from an async reader.
+    #[cfg_attr(not(feature = "sync"), allow(dead_code))]
+    pub(crate) fn into_reader(self) -> Result<impl Read + Send, Error> {
+        let mut body = Box::new(io::empty()) as Box<dyn Read + Send>;
+        for piece in self.pieces {
+            body = match piece {
+                Piece::Bytes(bytes) => Box::new(body.chain(Cursor::new(bytes))),
+                Piece::Reader(ContentReader::Blocking(reader)) => {
+                    Box::new(body.chain(EscapingReader::new(reader)))
+                }
+                Piece::Reader(ContentReader::Async(_)) => {
+                    return Err(Error::Unsupported(
+                        "the sync client can't read content from an async reader",
+                    ))
+                }
+            };
+        }
+        Ok(body)
+    }
+}
+
+/// Makes a stream `Sync` as required by reqwest, without requiring it of the readers.
+/// The stream is only ever polled through `&mut`, so the lock is never contended.
+struct SyncStream<S>(Mutex<S>);
+
+impl<S: Stream + Unpin> Stream for SyncStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        match self.get_mut().0.get_mut() {
+            Ok(stream) => stream.poll_next_unpin(cx),
+            Err(_) => Poll::Ready(None),
+        }
+    }
+}
+
+fn escaped_stream(reader: ContentReader) -> impl Stream<Item = io::Result<Vec<u8>>> + Send {
+    let state = Some((reader, JsonEscaper::default()));
+    stream::unfold(state, |state| async move {
+        let (mut reader, mut escaper) = state?;
+        let mut buf = vec![0; CHUNK_SIZE];
+        let read = match &mut reader {
+            ContentReader::Async(reader) => tokio::io::AsyncReadExt::read(reader, &mut buf).await,
+            ContentReader::Blocking(reader) => reader.read(&mut buf),
+        };
+        match read {
+            Ok(0) => Some((Ok(escaper.finish()), None)),
+            Ok(n) => {
+                buf.truncate(n);
+                Some((Ok(escaper.encode(&buf)), Some((reader, escaper))))
+            }
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+    .filter(|chunk| std::future::ready(!matches!(chunk, Ok(bytes) if bytes.is_empty())))
+}
+
+/// Escapes raw bytes into the inside of a JSON string, keeping UTF-8 sequences
+/// split across chunks intact and replacing invalid ones with U+FFFD.
+#[derive(Default)]
+struct JsonEscaper {
+    pending: Vec<u8>,
+}
+
+impl JsonEscaper {
+    fn encode(&mut self, chunk: &[u8]) -> Vec<u8> {
+        self.pending.extend_from_slice(chunk);
+        let mut out = vec![];
+        loop {
+            match std::str::from_utf8(&self.pending) {
+                Ok(text) => {
+                    escape_into(text, &mut out);
+                    self.pending.clear();
+                    return out;
+                }
+                Err(err) => {
+                    let valid = err.valid_up_to();
+                    let text = self.pending.get(..valid).unwrap_or_default();
+                    escape_into(&String::from_utf8_lossy(text), &mut out);
+                    match err.error_len() {
+                        Some(len) => {
+                            escape_into("\u{FFFD}", &mut out);
+                            self.pending.drain(..valid + len);
+                        }
+                        None => {
+                            self.pending.drain(..valid);
+                            return out;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Vec<u8> {
+        let mut out = vec![];
+        if !self.pending.is_empty() {
+            escape_into("\u{FFFD}", &mut out);
+            self.pending.clear();
+        }
+        out
+    }
+}
+
+fn escape_into(text: &str, out: &mut Vec<u8>) {
+    if text.is_empty() {
+        return;
+    }
+    let quoted = quote(text);
+    if let Some(inner) = quoted.strip_prefix('"').and_then(|q| q.strip_suffix('"')) {
+        out.extend_from_slice(inner.as_bytes());
+    }
+}
+
+/// A string as a JSON string literal.
+fn quote(text: &str) -> String {
+    // serializing a string can't fail
+    #[allow(clippy::unwrap_used)]
+    serde_json::to_string(text).unwrap()
+}
+
+struct EscapingReader<R> {
+    inner: R,
+    escaper: JsonEscaper,
+    out: Cursor<Vec<u8>>,
+    done: bool,
+}
+
+impl<R: Read> EscapingReader<R> {
+    fn new(inner: R) -> Self {
+        EscapingReader {
+            inner,
+            escaper: JsonEscaper::default(),
+            out: Cursor::default(),
+            done: false,
+        }
+    }
+}
+
+impl<R: Read> Read for EscapingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let n = self.out.read(buf)?;
+            if n > 0 || self.done || buf.is_empty() {
+                return Ok(n);
+            }
+            let mut chunk = vec![0; CHUNK_SIZE];
+            let read = self.inner.read(&mut chunk)?;
+            let escaped = if read == 0 {
+                self.done = true;
+                self.escaper.finish()
+            } else {
+                chunk.truncate(read);
+                self.escaper.encode(&chunk)
+            };
+            self.out = Cursor::new(escaped);
+        }
+    }
+}
@@ -1,11 +1,23 @@
-use std::time::{Duration, SystemTime};
+use std::{
+    collections::HashSet,
+    fmt,
+    time::{Duration, SystemTime},
+};
 
-use serde::{Deserialize, Serialize};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{header::HeaderMap, Method, StatusCode, Url};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
 
+use crate::utils::{format_timestamp, redact_error, redact_json};
+
 pub(super) mod response {
+    use reqwest::{header::HeaderMap, Url};
     use serde_json::Value;
 
+    use super::ErrorResponse;
+    use crate::parse::parse_json;
+
     #[derive(Debug)]
     /// Custom response to provide just useful data.
     pub struct MyustResponse {
@@ -13,6 +25,38 @@ pub(super) mod response {
         pub json: Option<Value>,
         /// The status code.
         pub status_code: u16,
+        /// The ID of the request, as echoed by the API or generated by myust.
+        pub request_id: String,
+        /// The response headers.
+        pub headers: HeaderMap,
+        /// The raw response, kept if the status is unsuccessful or the body isn't JSON.
+        pub raw: Option<ErrorResponse>,
+        /// The index of the pooled token the request was sent with, if any.
+        pub token: Option<usize>,
+    }
+
+    impl MyustResponse {
+        pub(crate) fn new(
+            status_code: u16,
+            headers: HeaderMap,
+            url: Url,
+            request_id: String,
+            body: &[u8],
+        ) -> Self {
+            let json = parse_json(body);
+            let raw = match (200..300).contains(&status_code) && json.is_some() {
+                true => None,
+                false => Some(ErrorResponse::new(&headers, url, json.as_ref(), body)),
+            };
+            MyustResponse {
+                json,
+                status_code,
+                request_id,
+                headers,
+                raw,
+                token: None,
+            }
+        }
     }
 }
 
@@ -20,13 +64,715 @@ pub(super) mod response {
 #[derive(Debug, Default)]
 pub struct MystbinError {
     /// The status code.
-    pub code: u16,
+    pub code: StatusCode,
     /// The error message, if any.
     pub error: Option<String>,
     /// The notice message, if any.
     pub notice: Option<String>,
     /// The detail of the error, if any.
     pub detail: Option<Value>,
+    /// The ID of the failed request, to correlate it with server-side logs.
+    pub request_id: Option<String>,
+    /// How long to wait before retrying, from the `Retry-After` or rate limit
+    /// headers, if any.
+    pub retry_after: Option<Duration>,
+    /// The body and headers of the response, e.g. to debug an error page that isn't
+    /// JSON.
+    pub response: Option<Box<ErrorResponse>>,
+}
+
+/// The most bytes of a response body kept in an [`ErrorResponse`].
+pub const ERROR_BODY_LIMIT: usize = 4096;
+
+/// The headers kept in an [`ErrorResponse`].
+const ERROR_HEADERS: [&str; 10] = [
+    "cf-ray",
+    "content-length",
+    "content-type",
+    "date",
+    "retry-after",
+    "server",
+    "via",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+    "x-request-id",
+];
+
+/// The body and headers of an unsuccessful or unreadable response.
+#[derive(Clone, Debug, Default)]
+pub struct ErrorResponse {
+    /// The body text, cut after [`ERROR_BODY_LIMIT`] bytes. A JSON body has its
+    /// sensitive data redacted.
+    pub body: String,
+    /// Whether the body was cut.
+    pub truncated: bool,
+    /// The headers useful for debugging, like `content-type` or `server`.
+    pub headers: HeaderMap,
+    /// The URL of the response, after following redirects, without the paste
+    /// password.
+    pub url: Option<Url>,
+}
+
+impl ErrorResponse {
+    fn new(headers: &HeaderMap, url: Url, json: Option<&Value>, body: &[u8]) -> Self {
+        let body = match json {
+            Some(json) => {
+                let mut json = json.clone();
+                redact_json(&mut json);
+                json.to_string()
+            }
+            None => String::from_utf8_lossy(body).into_owned(),
+        };
+        let truncated = body.len() > ERROR_BODY_LIMIT;
+        let mut end = body.len().min(ERROR_BODY_LIMIT);
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        ErrorResponse {
+            body: body.get(..end).unwrap_or_default().to_string(),
+            truncated,
+            headers: headers
+                .iter()
+                .filter(|(name, _)| ERROR_HEADERS.contains(&name.as_str()))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            url: Some(url),
+        }
+    }
+}
+
+impl MystbinError {
+    /// Build the error from an unsuccessful response, redacting any sensitive data
+    /// the API echoed back.
+    pub(crate) fn from_response(response: response::MyustResponse) -> Self {
+        let retry_after = retry_after(&response.headers);
+        // the code was read from a `StatusCode` in the first place
+        let code =
+            StatusCode::from_u16(response.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        match response.json {
+            Some(data) => MystbinError {
+                code,
+                error: data
+                    .get("error")
+                    .and_then(Value::as_str)
+                    .map(|s| s.to_string()),
+                notice: data
+                    .get("notice")
+                    .and_then(Value::as_str)
+                    .map(|s| s.to_string()),
+                detail: data
+                    .get("detail")
+                    .cloned()
+                    .filter(|detail| detail.is_object() || detail.is_array())
+                    .map(|mut detail| {
+                        redact_json(&mut detail);
+                        detail
+                    }),
+                request_id: Some(response.request_id),
+                retry_after,
+                response: response.raw.map(Box::new),
+            },
+            None => MystbinError {
+                code,
+                request_id: Some(response.request_id),
+                retry_after,
+                response: response.raw.map(Box::new),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Whether retrying the same request later may succeed: a timeout, a rate limit
+    /// or a server error.
+    pub fn is_retryable(&self) -> bool {
+        is_retryable_status(self.code)
+    }
+
+    /// Whether the token is missing, invalid or not allowed to do this.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self.code, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+    }
+
+    /// Whether the API rejected the request itself, with a 4xx status.
+    pub fn is_client_error(&self) -> bool {
+        self.code.is_client_error()
+    }
+
+    /// Whether the paste exists but the password is missing or wrong. The API
+    /// answers either with 401 or 403, or with a 404 whose message mentions the
+    /// password.
+    fn is_password_error(&self) -> bool {
+        let mentions_password = || {
+            [&self.error, &self.notice]
+                .into_iter()
+                .flatten()
+                .any(|message| message.to_ascii_lowercase().contains("password"))
+        };
+        self.is_auth_error() || (self.code == StatusCode::NOT_FOUND && mentions_password())
+    }
+
+    /// The fields the API rejected, parsed from [`detail`](Self::detail).
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        self.detail
+            .as_ref()
+            .map(FieldError::parse)
+            .unwrap_or_default()
+    }
+}
+
+/// A field of the request the API rejected, e.g. the content of an empty file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FieldError {
+    /// The path to the field, e.g. `["body", "files", "0", "content"]`.
+    pub location: Vec<String>,
+    /// The name of the field, the last named part of the location, if any.
+    pub field: Option<String>,
+    /// Why the field was rejected.
+    pub message: String,
+}
+
+impl FieldError {
+    /// Parse the errors of a `detail` structure, a list of objects with a `loc` path
+    /// and a `msg`. A `detail` that isn't a list gives no errors.
+    pub fn parse(detail: &Value) -> Vec<Self> {
+        let Some(errors) = detail.as_array() else {
+            return vec![];
+        };
+        errors
+            .iter()
+            .filter(|error| error.is_object())
+            .map(|error| {
+                let location: Vec<String> = error["loc"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|part| match part {
+                        Value::String(part) => part.clone(),
+                        part => part.to_string(),
+                    })
+                    .collect();
+                let field = error["loc"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .rev()
+                    .find_map(|part| part.as_str())
+                    .map(|part| part.to_string());
+                FieldError {
+                    location,
+                    field,
+                    message: error["msg"].as_str().unwrap_or_default().to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// The index of the file the error is about, if it's about one.
+    pub fn file_index(&self) -> Option<usize> {
+        match self.location.iter().position(|part| part == "files") {
+            Some(files) => self.location.get(files + 1)?.parse().ok(),
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.location.join("."), self.message),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+impl fmt::Display for MystbinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            StatusCode::UNAUTHORIZED => write!(f, "the token is missing or invalid")?,
+            StatusCode::FORBIDDEN => write!(f, "not allowed to access this resource")?,
+            StatusCode::NOT_FOUND => write!(f, "paste not found")?,
+            StatusCode::UNPROCESSABLE_ENTITY => write!(f, "the API rejected the request")?,
+            StatusCode::TOO_MANY_REQUESTS => match self.retry_after {
+                Some(retry_after) => {
+                    write!(f, "rate limited, retry in {}s", retry_after.as_secs())?
+                }
+                None => write!(f, "rate limited")?,
+            },
+            code => write!(f, "the API returned {}", code)?,
+        }
+        if let Some(message) = self.error.as_ref().or(self.notice.as_ref()) {
+            write!(f, ": {}", message)?;
+        }
+        for field_error in self.field_errors() {
+            write!(f, "; {}", field_error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MystbinError {}
+
+fn is_retryable_status(code: StatusCode) -> bool {
+    matches!(
+        code,
+        StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS
+    ) || code.is_server_error()
+}
+
+/// How long to wait before retrying, in seconds in `Retry-After`, or until the
+/// rate limit window resets.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    if let Some(seconds) = seconds {
+        return Some(Duration::from_secs(seconds));
+    }
+    let rate_limit = RateLimit::from_headers(headers)?;
+    match rate_limit.remaining {
+        0 => rate_limit
+            .reset
+            .map(|reset| reset.duration_since(SystemTime::now()).unwrap_or_default()),
+        _ => None,
+    }
+}
+
+/// What the API tells about a paste with a password before it's given, see
+/// [`Error::PasteRequiresPassword`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LockedPaste {
+    /// The paste's ID.
+    pub id: String,
+    /// The paste's creation date, if told.
+    pub created_at: Option<String>,
+    /// The paste's expiration date, if any and told.
+    pub expires: Option<String>,
+    /// The paste's files, if told.
+    pub files: Vec<LockedFile>,
+}
+
+/// A file of a [`LockedPaste`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LockedFile {
+    /// The file's name.
+    pub filename: String,
+    /// The number of characters in the file, if told.
+    pub size: Option<usize>,
+}
+
+impl LockedPaste {
+    /// Read whatever metadata an error response holds.
+    fn from_json(id: &str, json: Option<&Value>) -> Self {
+        let Some(json) = json else {
+            return LockedPaste {
+                id: id.to_string(),
+                ..Default::default()
+            };
+        };
+        LockedPaste {
+            id: id.to_string(),
+            created_at: json["created_at"].as_str().map(|d| d.to_string()),
+            expires: json["expires"].as_str().map(|d| d.to_string()),
+            files: json["files"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|file| {
+                    Some(LockedFile {
+                        filename: file["filename"].as_str()?.to_string(),
+                        size: file["size"].as_u64().map(|size| size as usize),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The rate limit state reported by the API on the latest response.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    /// The number of requests allowed in the current window, if reported.
+    pub limit: Option<u64>,
+    /// The number of requests left in the current window.
+    pub remaining: u64,
+    /// When the current window resets, if reported.
+    pub reset: Option<SystemTime>,
+}
+
+impl RateLimit {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<f64>().ok())
+        };
+        let remaining = header("x-ratelimit-remaining")?;
+        // a reset that isn't a representable time, e.g. `inf` or `1e30`, is dropped
+        let reset = header("x-ratelimit-reset").and_then(|reset| {
+            // the reset is either a Unix timestamp or a number of seconds from now
+            if reset > 1_000_000_000.0 {
+                SystemTime::UNIX_EPOCH.checked_add(Duration::try_from_secs_f64(reset).ok()?)
+            } else {
+                SystemTime::now().checked_add(Duration::try_from_secs_f64(reset.max(0.0)).ok()?)
+            }
+        });
+        Some(RateLimit {
+            limit: header("x-ratelimit-limit").map(|l| l as u64),
+            remaining: remaining as u64,
+            reset,
+        })
+    }
+}
+
+/// A reason the provided paste data was rejected before being sent to the API.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// One of the [`Expiry`] fields is negative.
+    NegativeExpiry {
+        /// The name of the negative field.
+        field: &'static str,
+        /// The negative value.
+        value: i32,
+    },
+    /// Every [`Expiry`] field is zero, while [`Expiry::never()`] was probably meant.
+    ZeroExpiry,
+    /// A file has no content.
+    EmptyContent,
+    /// A multifile paste has no files.
+    NoFiles,
+    /// A filename contains a path separator or a control character.
+    InvalidFilename(String),
+    /// A paste ID or URL doesn't hold a valid paste ID, see [`PasteId`](crate::PasteId).
+    InvalidPasteId(String),
+    /// An expiration timestamp isn't an RFC 3339 UTC timestamp in the future, see
+    /// [`Expiry::try_from_rfc3339`], an expiration period doesn't parse, see
+    /// [`Expiry::parse`], or it's longer than [`Expiry::MAX_PERIOD`].
+    InvalidExpiry(String),
+    /// A file has more characters than the API accepts, see [`MAX_FILE_CHARACTERS`].
+    ContentTooLarge {
+        /// The name of the file.
+        file: String,
+        /// The number of characters in the file.
+        size: usize,
+        /// The most characters allowed.
+        max: usize,
+    },
+    /// A paste has more files than the API accepts, see [`MAX_FILES`].
+    TooManyFiles {
+        /// The number of files.
+        count: usize,
+        /// The most files allowed.
+        max: usize,
+    },
+    /// A file's content has a character the API may reject, see
+    /// [`InvalidCharacters`](crate::InvalidCharacters).
+    InvalidCharacter {
+        /// The name of the file.
+        file: String,
+        /// The character.
+        character: char,
+        /// Its byte offset in the content.
+        position: usize,
+    },
+    /// Two files of a paste have the same name, see
+    /// [`DuplicateFilenames`](crate::DuplicateFilenames).
+    DuplicateFilename(String),
+    /// A paste has no password while its template requires one, see
+    /// [`PasswordPolicy`](crate::template::PasswordPolicy).
+    PasswordRequired,
+    /// A paste has a password while its template forbids one.
+    PasswordForbidden,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NegativeExpiry { field, value } => {
+                write!(f, "{} can not be negative, value: {}", field, value)
+            }
+            Self::ZeroExpiry => write!(
+                f,
+                "an expiry can not be zero, use Expiry::never() for no expiration"
+            ),
+            Self::EmptyContent => write!(f, "content can not be empty"),
+            Self::NoFiles => write!(f, "a paste needs at least one file"),
+            Self::InvalidFilename(name) => write!(f, "invalid filename: {:?}", name),
+            Self::InvalidPasteId(id) => write!(f, "invalid paste ID: {:?}", id),
+            Self::InvalidExpiry(expiry) => write!(f, "invalid expiration: {:?}", expiry),
+            Self::ContentTooLarge { file, size, max } => write!(
+                f,
+                "{:?} has {} characters, more than the {} allowed",
+                file, size, max
+            ),
+            Self::TooManyFiles { count, max } => {
+                write!(
+                    f,
+                    "a paste can have at most {} files, got {}; split them across several pastes",
+                    max, count
+                )
+            }
+            Self::InvalidCharacter {
+                file,
+                character,
+                position,
+            } => write!(
+                f,
+                "{:?} has the invalid character {:?} at byte {}",
+                file, character, position
+            ),
+            Self::DuplicateFilename(name) => {
+                write!(f, "more than one file is named {:?}", name)
+            }
+            Self::PasswordRequired => write!(f, "the paste needs a password"),
+            Self::PasswordForbidden => write!(f, "the paste can not have a password"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// An error from an operation that does more than one API call, or that also
+/// reads or writes local data.
+#[derive(Debug)]
+pub enum Error {
+    /// The API rejected the request.
+    Api(Box<MystbinError>),
+    /// The request couldn't be sent or its response couldn't be received, for
+    /// another reason than a [timeout](Self::Timeout) or a
+    /// [failed connection](Self::Connect), e.g. a redirect loop.
+    Http(reqwest::Error),
+    /// The request timed out once connected, so the API may have received it.
+    Timeout(reqwest::Error),
+    /// The connection to the API failed, e.g. because the network is down, so the
+    /// request wasn't received.
+    Connect(reqwest::Error),
+    /// The API answered successfully but its response can't be understood, e.g. a
+    /// body that isn't JSON or a missing field.
+    InvalidResponse(String),
+    /// Reading or writing local data, or the response stream, failed.
+    Io(std::io::Error),
+    /// A file has more characters than the API accepts, see [`MAX_FILE_CHARACTERS`].
+    ContentTooLarge {
+        /// The name of the file.
+        file: String,
+        /// The number of characters in the file.
+        size: usize,
+        /// The most characters allowed.
+        max: usize,
+    },
+    /// A paste has more files than the API accepts, see [`MAX_FILES`].
+    TooManyFiles {
+        /// The number of files.
+        count: usize,
+        /// The most files allowed.
+        max: usize,
+    },
+    /// The paste data was rejected before being sent.
+    Invalid(ValidationError),
+    /// Encrypting or decrypting a file failed, e.g. because of a wrong key.
+    Encryption(Box<dyn std::error::Error + Send + Sync>),
+    /// A paste expected to be signed has no signature file.
+    Unsigned,
+    /// A paste's signature doesn't match its files or the expected author.
+    BadSignature,
+    /// The client's configuration doesn't support this operation.
+    Unsupported(&'static str),
+    /// The paste exists but has a password, and none was given, see
+    /// `get_paste_with_password_prompt`.
+    PasteRequiresPassword(Box<LockedPaste>),
+    /// The paste exists but the password is wrong, while [`Api`](Self::Api) with a
+    /// 404 status means it doesn't exist.
+    WrongPassword(String),
+    /// The paste has no file with the name selected with
+    /// [`GetPasteBuilder::file`](crate::GetPasteBuilder::file).
+    FileNotFound(String),
+    /// Another paste service behind a [`PasteProvider`](crate::PasteProvider) rejected
+    /// the request.
+    Provider {
+        /// The name of the provider, e.g. `gist`.
+        provider: &'static str,
+        /// The status code.
+        code: StatusCode,
+        /// The error message, if any.
+        message: Option<String>,
+    },
+    /// The operation was stopped through its
+    /// [`CancellationToken`](crate::CancellationToken).
+    Cancelled,
+}
+
+impl Error {
+    /// The error of a request for the paste `id`, sent with `password`.
+    pub(crate) fn from_paste_response(
+        response: response::MyustResponse,
+        id: &str,
+        password: Option<&str>,
+    ) -> Self {
+        let locked = LockedPaste::from_json(id, response.json.as_ref());
+        let err = MystbinError::from_response(response);
+        match (err.is_password_error(), password) {
+            (true, None) => Error::PasteRequiresPassword(Box::new(locked)),
+            (true, Some(_)) => Error::WrongPassword(id.to_string()),
+            (false, _) => err.into(),
+        }
+    }
+
+    /// The status code of the response, if the error comes from the API or a
+    /// provider.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Api(err) => Some(err.code),
+            Self::Provider { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Whether retrying the same request later may succeed: a timeout, a failed
+    /// connection, a rate limit or a server error. The built-in retries, e.g. when
+    /// flushing an operation queue, use it too.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Timeout(_) | Self::Connect(_) => true,
+            err => err.status().is_some_and(is_retryable_status),
+        }
+    }
+
+    /// Whether retrying a request sent with `method` may succeed and is safe, see
+    /// [`is_retryable`](Self::is_retryable).
+    ///
+    /// Failed connections and rate limits are retryable for every method, as the
+    /// API didn't act on the request. Timeouts and server errors are only
+    /// retryable for idempotent methods such as `GET`, `PUT` and `DELETE`, since a
+    /// `POST` may have been applied already.
+    pub fn is_retryable_for(&self, method: &Method) -> bool {
+        match self {
+            Self::Connect(_) => true,
+            err if err.status() == Some(StatusCode::TOO_MANY_REQUESTS) => true,
+            err => err.is_retryable() && method.is_idempotent(),
+        }
+    }
+
+    /// Whether the token is missing, invalid or not allowed to do this.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self.status(),
+            Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+        )
+    }
+
+    /// Whether the request itself is wrong: rejected by the API with a 4xx status,
+    /// or before being sent.
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            Self::ContentTooLarge { .. }
+            | Self::TooManyFiles { .. }
+            | Self::Invalid(_)
+            | Self::PasteRequiresPassword(_)
+            | Self::WrongPassword(_) => true,
+            err => err.status().is_some_and(|code| code.is_client_error()),
+        }
+    }
+
+    /// Whether the paste, or the selected file of it, doesn't exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::FileNotFound(_)) || self.status() == Some(StatusCode::NOT_FOUND)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Api(err) => err.fmt(f),
+            Self::Http(err) => write!(f, "request failed: {}", err),
+            Self::Timeout(err) => write!(f, "request timed out: {}", err),
+            Self::Connect(err) => write!(f, "connection failed: {}", err),
+            Self::InvalidResponse(reason) => write!(f, "invalid response: {}", reason),
+            Self::Io(err) => write!(f, "I/O error: {}", err),
+            Self::ContentTooLarge { file, size, max } => ValidationError::ContentTooLarge {
+                file: file.clone(),
+                size: *size,
+                max: *max,
+            }
+            .fmt(f),
+            Self::TooManyFiles { count, max } => ValidationError::TooManyFiles {
+                count: *count,
+                max: *max,
+            }
+            .fmt(f),
+            Self::Invalid(err) => err.fmt(f),
+            Self::Encryption(err) => write!(f, "encryption failed: {}", err),
+            Self::Unsigned => write!(f, "the paste is not signed"),
+            Self::BadSignature => write!(f, "the paste's signature is invalid"),
+            Self::Unsupported(reason) => write!(f, "unsupported: {}", reason),
+            Self::PasteRequiresPassword(paste) => {
+                write!(f, "paste {} requires a password", paste.id)
+            }
+            Self::WrongPassword(id) => write!(f, "wrong password for paste {}", id),
+            Self::FileNotFound(filename) => write!(f, "the paste has no file named {:?}", filename),
+            Self::Provider {
+                provider,
+                code,
+                message: Some(message),
+            } => write!(f, "{} returned {}: {}", provider, code, message),
+            Self::Provider { provider, code, .. } => write!(f, "{} returned {}", provider, code),
+            Self::Cancelled => write!(f, "the operation was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Http(err) | Self::Timeout(err) | Self::Connect(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::Invalid(err) => Some(err),
+            Self::Encryption(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<MystbinError> for Error {
+    fn from(err: MystbinError) -> Self {
+        Self::Api(Box::new(err))
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(err: ValidationError) -> Self {
+        match err {
+            ValidationError::ContentTooLarge { file, size, max } => {
+                Self::ContentTooLarge { file, size, max }
+            }
+            ValidationError::TooManyFiles { count, max } => Self::TooManyFiles { count, max },
+            err => Self::Invalid(err),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    /// A connection that timed out is a [`Connect`](Self::Connect) error, as the
+    /// request was never received.
+    fn from(err: reqwest::Error) -> Self {
+        let err = redact_error(err);
+        if err.is_connect() {
+            Self::Connect(err)
+        } else if err.is_timeout() {
+            Self::Timeout(err)
+        } else {
+            Self::Http(err)
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
 }
 
 /// The paste's expiration time.
@@ -40,7 +786,15 @@ pub struct MystbinError {
 /// - 1 hour, 20 minutes and 40 seconds:
 ///
 /// `Expiry { hours: 1, minutes: 20, seconds: 40, ..default::Default() }`
-#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+///
+/// - Common periods: [`Expiry::ONE_HOUR`], [`Expiry::ONE_DAY`] and
+///   [`Expiry::ONE_WEEK`].
+///
+/// - No expiration, e.g. to override the client's default expiry:
+///   [`Expiry::never()`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct Expiry {
     /// The expiration days.
     pub days: i32,
@@ -50,26 +804,85 @@ pub struct Expiry {
     pub minutes: i32,
     /// The expiration seconds.
     pub seconds: i32,
+    /// Never expire, ignoring the other fields, see [`never`](Self::never).
+    pub never: bool,
 }
 
 impl Expiry {
-    fn total(&self) -> Duration {
-        let days = self.days * 24 * 60 * 60;
-        let hours = self.hours * 60 * 60;
-        let minutes = self.minutes * 60;
-        Duration::from_secs((days + hours + minutes + self.seconds) as u64)
+    /// Expire in an hour.
+    pub const ONE_HOUR: Expiry = Expiry::days_hours(0, 1);
+    /// Expire in a day.
+    pub const ONE_DAY: Expiry = Expiry::days_hours(1, 0);
+    /// Expire in a week.
+    pub const ONE_WEEK: Expiry = Expiry::days_hours(7, 0);
+    /// The longest expiration period, 7000 years of 365 days, which ends before the
+    /// year 9999 RFC 3339 can't represent as long as the clock is before 2999.
+    pub const MAX_PERIOD: Duration = Duration::from_secs(7000 * 365 * 24 * 60 * 60);
+
+    const fn days_hours(days: i32, hours: i32) -> Self {
+        Expiry {
+            days,
+            hours,
+            minutes: 0,
+            seconds: 0,
+            never: false,
+        }
+    }
+
+    /// Never expire. It's sent as no expiration, so the paste is kept even when the
+    /// client has a default expiry.
+    pub const fn never() -> Self {
+        Expiry {
+            never: true,
+            ..Self::days_hours(0, 0)
+        }
+    }
+
+    /// Whether this is [`Expiry::never()`].
+    pub fn is_never(&self) -> bool {
+        self.never
     }
 
-    fn add(&self) -> SystemTime {
-        let current_time = SystemTime::now();
-        match current_time.checked_add(self.total()) {
-            Some(new_time) => new_time,
-            None => current_time, // handle overflow case
+    pub(crate) fn total(&self) -> Duration {
+        let days = i64::from(self.days) * 24 * 60 * 60;
+        let hours = i64::from(self.hours) * 60 * 60;
+        let minutes = i64::from(self.minutes) * 60;
+        let seconds = days + hours + minutes + i64::from(self.seconds);
+        Duration::from_secs(seconds.max(0) as u64)
+    }
+
+    /// The same period with every field in range, e.g. 90 minutes becomes an hour
+    /// and 30 minutes.
+    ///
+    /// Negative fields are subtracted from the others, a negative total becoming
+    /// zero. [`Expiry::never()`] is kept as is.
+    pub fn normalized(&self) -> Self {
+        match self.never {
+            true => self.clone(),
+            false => self.total().into(),
         }
     }
 
-    fn to_vec(&self) -> Vec<(&str, i32)> {
-        vec![
+    /// Parse a period such as `2d 4h`, `90min` or `1week`, in any format
+    /// [humantime](https://docs.rs/humantime) accepts, or `never`.
+    ///
+    /// The result is normalized, and is the inverse of the `Display` impl.
+    pub fn parse(input: &str) -> Result<Self, ValidationError> {
+        if input.trim().eq_ignore_ascii_case("never") {
+            return Ok(Self::never());
+        }
+        match humantime::parse_duration(input) {
+            Ok(duration) => Ok(duration.into()),
+            Err(_) => Err(ValidationError::InvalidExpiry(input.to_string())),
+        }
+    }
+
+    fn add(&self) -> Option<SystemTime> {
+        SystemTime::now().checked_add(self.total())
+    }
+
+    fn to_array(&self) -> [(&'static str, i32); 4] {
+        [
             ("days", self.days),
             ("hours", self.hours),
             ("minutes", self.minutes),
@@ -77,37 +890,462 @@ impl Expiry {
         ]
     }
 
-    pub(crate) fn invalid_field(&self) -> (String, i32) {
-        let expiry_vec = self.to_vec();
-        for field in &expiry_vec {
-            if field.1 < 0 {
-                return (field.0.to_string(), field.1);
+    /// Whether every field is non-negative.
+    pub fn valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Check that every field is non-negative, returning the first negative one
+    /// otherwise, that they aren't all zero, unless it never expires, and that the
+    /// period isn't longer than [`MAX_PERIOD`](Self::MAX_PERIOD).
+    ///
+    /// This doesn't depend on the clock, so an expiry valid once stays valid.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.never {
+            return Ok(());
+        }
+        if let Some((field, value)) = self.to_array().into_iter().find(|field| field.1 < 0) {
+            return Err(ValidationError::NegativeExpiry { field, value });
+        }
+        if self.to_array().iter().all(|field| field.1 == 0) {
+            return Err(ValidationError::ZeroExpiry);
+        }
+        match self.total() <= Self::MAX_PERIOD {
+            true => Ok(()),
+            false => Err(ValidationError::InvalidExpiry(self.to_string())),
+        }
+    }
+
+    /// The expiration timestamp, or `None` past the year 9999, which RFC 3339 can't
+    /// represent.
+    pub(crate) fn to_rfc3339(&self) -> Option<String> {
+        let form = format_timestamp(humantime::format_rfc3339(self.add()?))?;
+        Some(form.replace("00Z", "+00:00"))
+    }
+
+    /// The time left until an RFC 3339 UTC timestamp, such as
+    /// `2024-05-01T12:00:00Z` or `2024-05-01T12:00:00+00:00`, rounded down to the
+    /// second.
+    ///
+    /// Fails if the timestamp doesn't parse or isn't in the future.
+    pub fn try_from_rfc3339(timestamp: &str) -> Result<Self, ValidationError> {
+        let invalid = || ValidationError::InvalidExpiry(timestamp.to_string());
+        let utc = match timestamp.strip_suffix("+00:00") {
+            Some(time) => format!("{}Z", time),
+            None => timestamp.to_string(),
+        };
+        let time = humantime::parse_rfc3339(&utc).map_err(|_| invalid())?;
+        match time.duration_since(SystemTime::now()) {
+            Ok(left) if left.as_secs() > 0 => Ok(left.into()),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Expiry {
+    /// Write the non-zero fields, such as `1d 12h`, `never` or `0s` when all are
+    /// zero. Call [`normalized`](Expiry::normalized) first to carry overflowing
+    /// fields over.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.never {
+            return f.write_str("never");
+        }
+        let units = [
+            (self.days, "d"),
+            (self.hours, "h"),
+            (self.minutes, "m"),
+            (self.seconds, "s"),
+        ];
+        let mut fields = units.iter().filter(|(value, _)| *value != 0).peekable();
+        if fields.peek().is_none() {
+            return f.write_str("0s");
+        }
+        for (i, (value, unit)) in fields.enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
             }
+            write!(f, "{}{}", value, unit)?;
         }
-        (expiry_vec[0].0.to_string(), expiry_vec[0].1)
+        Ok(())
     }
+}
+
+impl std::str::FromStr for Expiry {
+    type Err = ValidationError;
 
-    pub(crate) fn valid(&self) -> bool {
-        self.days >= 0 && self.hours >= 0 && self.minutes >= 0 && self.seconds >= 0
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input)
     }
+}
 
-    pub(crate) fn is_default(&self) -> bool {
-        *self == Self::default()
+impl From<Duration> for Expiry {
+    /// Split a duration into days, hours, minutes and seconds, dropping the
+    /// fraction of a second. Durations too long for the `days` field are capped.
+    fn from(duration: Duration) -> Self {
+        let seconds = duration.as_secs();
+        Expiry {
+            days: i32::try_from(seconds / 86_400).unwrap_or(i32::MAX),
+            hours: (seconds / 3_600 % 24) as i32,
+            minutes: (seconds / 60 % 60) as i32,
+            seconds: (seconds % 60) as i32,
+            never: false,
+        }
     }
+}
 
-    pub(crate) fn to_rfc3339(&self) -> String {
-        let form = humantime::format_rfc3339(self.add()).to_string();
-        form.replace("00Z", "+00:00")
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<chrono::Duration> for Expiry {
+    type Error = ValidationError;
+
+    /// Like the conversion from [`std::time::Duration`], failing if the duration is
+    /// negative.
+    fn try_from(duration: chrono::Duration) -> Result<Self, Self::Error> {
+        match duration.to_std() {
+            Ok(duration) => Ok(duration.into()),
+            Err(_) => Err(ValidationError::NegativeExpiry {
+                field: "seconds",
+                value: i32::try_from(duration.num_seconds()).unwrap_or(i32::MIN),
+            }),
+        }
     }
 }
 
+/// The most characters the API accepts in one file.
+pub const MAX_FILE_CHARACTERS: usize = 300_000;
+
+/// The most files the API accepts in one paste.
+pub const MAX_FILES: usize = 5;
+
+/// The suffix added to the filename of binary files, see [`File::from_bytes`].
+pub const BINARY_SUFFIX: &str = ".b64";
+
 /// The base file.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct File {
     /// The file's name.
     pub filename: String,
     /// The file's content.
     pub content: String,
+    /// The language to highlight the file as, see
+    /// [`PasteBuilder::syntax`](crate::PasteBuilder::syntax).
+    ///
+    /// The API doesn't store it, so fetched files have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub syntax: Option<String>,
+}
+
+impl File {
+    /// Check that a filename has no path separators or control characters.
+    pub fn validate_filename(filename: &str) -> Result<(), ValidationError> {
+        if filename
+            .chars()
+            .any(|c| c == '/' || c == '\\' || c.is_control())
+        {
+            Err(ValidationError::InvalidFilename(filename.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that some content is not empty.
+    pub fn validate_content(content: &str) -> Result<(), ValidationError> {
+        if content.is_empty() {
+            Err(ValidationError::EmptyContent)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that a file's content has no NUL or other control character than tabs
+    /// and line breaks, nor the replacement character left by a lossy UTF-8
+    /// conversion, see [`InvalidCharacters`](crate::InvalidCharacters).
+    pub fn validate_characters(filename: &str, content: &str) -> Result<(), ValidationError> {
+        match content
+            .char_indices()
+            .find(|(_, c)| is_invalid_character(*c))
+        {
+            Some((position, character)) => Err(ValidationError::InvalidCharacter {
+                file: filename.to_string(),
+                character,
+                position,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The content without the characters [`validate_characters`](Self::validate_characters)
+    /// rejects.
+    pub fn strip_invalid_characters(content: &str) -> String {
+        content
+            .chars()
+            .filter(|c| !is_invalid_character(*c))
+            .collect()
+    }
+
+    /// Check that a file's content fits in [`MAX_FILE_CHARACTERS`].
+    pub fn validate_size(filename: &str, content: &str) -> Result<(), ValidationError> {
+        // Counting characters is only needed when the content could be too large.
+        if content.len() <= MAX_FILE_CHARACTERS {
+            return Ok(());
+        }
+        match content.chars().count() {
+            size if size > MAX_FILE_CHARACTERS => Err(ValidationError::ContentTooLarge {
+                file: filename.to_string(),
+                size,
+                max: MAX_FILE_CHARACTERS,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that a number of files fits in [`MAX_FILES`].
+    pub fn validate_count(count: usize) -> Result<(), ValidationError> {
+        if count > MAX_FILES {
+            Err(ValidationError::TooManyFiles {
+                count,
+                max: MAX_FILES,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that no two filenames are the same, see
+    /// [`DuplicateFilenames`](crate::DuplicateFilenames).
+    pub fn validate_unique_filenames<'a>(
+        filenames: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), ValidationError> {
+        let mut seen = HashSet::new();
+        for filename in filenames {
+            if !seen.insert(filename) {
+                return Err(ValidationError::DuplicateFilename(filename.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rename the files whose name is already taken by an earlier file, numbering
+    /// them before the extension: `foo.txt`, `foo (1).txt`, `foo (2).txt`.
+    pub fn rename_duplicates(files: &mut [File]) {
+        let mut taken: HashSet<String> = files.iter().map(|f| f.filename.clone()).collect();
+        let mut seen = HashSet::new();
+        for file in files.iter_mut() {
+            if seen.insert(file.filename.clone()) {
+                continue;
+            }
+            let (stem, extension) = match file.filename.rsplit_once('.') {
+                Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+                _ => (file.filename.as_str(), String::new()),
+            };
+            let renamed = (1..)
+                .map(|n| format!("{} ({}){}", stem, n, extension))
+                .find(|name| !taken.contains(name))
+                .unwrap_or_default();
+            taken.insert(renamed.clone());
+            seen.insert(renamed.clone());
+            file.filename = renamed;
+        }
+    }
+
+    /// The number of characters in the content, which the API limits to
+    /// [`MAX_FILE_CHARACTERS`].
+    pub fn len_chars(&self) -> usize {
+        self.content.chars().count()
+    }
+
+    /// The size of the content in bytes, once encoded as UTF-8.
+    pub fn len_bytes(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Whether the content fits in [`MAX_FILE_CHARACTERS`], so the API accepts
+    /// the file's size.
+    pub fn fits_limits(&self) -> bool {
+        Self::validate_size(&self.filename, &self.content).is_ok()
+    }
+
+    /// Check the file's name, content and size.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Self::validate_filename(&self.filename)?;
+        Self::validate_content(&self.content)?;
+        Self::validate_size(&self.filename, &self.content)
+    }
+
+    /// Store binary data as base64, adding [`BINARY_SUFFIX`] to the filename.
+    ///
+    /// ```rust
+    /// use myust::File;
+    ///
+    /// let file = File::from_bytes("header.bin", &[0x7f, b'E', b'L', b'F']);
+    /// assert_eq!(file.filename, "header.bin.b64");
+    /// assert_eq!(file.decode_bytes().unwrap(), [0x7f, b'E', b'L', b'F']);
+    /// ```
+    pub fn from_bytes(filename: impl Into<String>, bytes: &[u8]) -> File {
+        File {
+            filename: format!("{}{}", filename.into(), BINARY_SUFFIX),
+            content: STANDARD.encode(bytes),
+            syntax: None,
+        }
+    }
+
+    /// Whether the file holds binary data created with [`from_bytes`](Self::from_bytes).
+    pub fn is_binary(&self) -> bool {
+        self.filename.ends_with(BINARY_SUFFIX)
+    }
+
+    /// Decode the binary data of a file created with [`from_bytes`](Self::from_bytes),
+    /// or `None` if it isn't a binary file or its content isn't valid base64.
+    pub fn decode_bytes(&self) -> Option<Vec<u8>> {
+        if !self.is_binary() {
+            return None;
+        }
+        STANDARD.decode(self.content.trim_end()).ok()
+    }
+
+    /// Split a file larger than [`MAX_FILE_CHARACTERS`] into parts that fit, cut at
+    /// line breaks when possible. The parts are numbered before the extension, e.g.
+    /// `build-1.log` and `build-2.log`.
+    ///
+    /// A file that fits is returned as is.
+    pub fn split(self) -> Vec<File> {
+        if Self::validate_size(&self.filename, &self.content).is_ok() {
+            return vec![self];
+        }
+        let mut parts = vec![String::new()];
+        let mut size = 0;
+        for line in self.content.split_inclusive('\n') {
+            if size > 0 && size + line.chars().count() > MAX_FILE_CHARACTERS {
+                parts.push(String::new());
+                size = 0;
+            }
+            // only a line longer than a whole part is cut in the middle
+            for c in line.chars() {
+                if size == MAX_FILE_CHARACTERS {
+                    parts.push(String::new());
+                    size = 0;
+                }
+                if let Some(part) = parts.last_mut() {
+                    part.push(c);
+                }
+                size += 1;
+            }
+        }
+        let (stem, extension) = match self.filename.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+            _ => (self.filename.as_str(), String::new()),
+        };
+        parts
+            .into_iter()
+            .enumerate()
+            .map(|(index, content)| File {
+                filename: format!("{}-{}{}", stem, index + 1, extension),
+                content,
+                syntax: self.syntax.clone(),
+            })
+            .collect()
+    }
+
+    /// Check the limits the API enforces on files, see [`MAX_FILES`] and
+    /// [`MAX_FILE_CHARACTERS`].
+    pub(crate) fn check_limits<'a>(
+        files: impl ExactSizeIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<(), ValidationError> {
+        Self::validate_count(files.len())?;
+        for (filename, content) in files {
+            Self::validate_size(filename, content)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_invalid_character(c: char) -> bool {
+    c == char::REPLACEMENT_CHARACTER || (c.is_control() && !matches!(c, '\t' | '\n' | '\r'))
+}
+
+/// The suffix added to the filename of files compressed with
+/// [`PasteBuilder::compress`](crate::PasteBuilder::compress).
+#[cfg(feature = "compression")]
+pub const COMPRESSED_SUFFIX: &str = ".gz.b64";
+
+#[cfg(feature = "compression")]
+impl File {
+    /// Gzip the content and encode it as base64, adding [`COMPRESSED_SUFFIX`]
+    /// to the filename.
+    pub fn compress(&self) -> File {
+        File {
+            filename: format!("{}{}", self.filename, COMPRESSED_SUFFIX),
+            content: STANDARD.encode(crate::utils::gzip(self.content.as_bytes())),
+            syntax: self.syntax.clone(),
+        }
+    }
+
+    /// Reverse [`compress`](Self::compress), or `None` if the file wasn't
+    /// compressed by myust.
+    pub fn decompress(&self) -> Option<File> {
+        let filename = self.filename.strip_suffix(COMPRESSED_SUFFIX)?;
+        let compressed = self.decode_bytes()?;
+        let content = String::from_utf8(crate::utils::gunzip(&compressed)?).ok()?;
+        Some(File {
+            filename: filename.to_string(),
+            content,
+            syntax: self.syntax.clone(),
+        })
+    }
+}
+
+/// The request body to create a paste, borrowing the files so their content
+/// is only copied once, when serialized.
+#[derive(Serialize)]
+pub(crate) struct CreatePaste<'a> {
+    #[serde(serialize_with = "api_files")]
+    files: &'a [File],
+    password: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires: Option<Option<String>>,
+}
+
+impl<'a> CreatePaste<'a> {
+    pub(crate) fn new(
+        files: &'a [File],
+        password: Option<&'a str>,
+        expires: Option<&Expiry>,
+    ) -> Result<Self, ValidationError> {
+        let expires = match expires {
+            Some(expiry) => {
+                expiry.validate()?;
+                match expiry.is_never() {
+                    true => Some(None),
+                    // only past the year 9999 with a clock set centuries ahead
+                    false => {
+                        Some(Some(expiry.to_rfc3339().ok_or_else(|| {
+                            ValidationError::InvalidExpiry(expiry.to_string())
+                        })?))
+                    }
+                }
+            }
+            None => None,
+        };
+        Ok(CreatePaste {
+            files,
+            password,
+            expires,
+        })
+    }
+}
+
+/// Serialize files with only the fields the API accepts.
+fn api_files<S: Serializer>(files: &&[File], serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct ApiFile<'a> {
+        filename: &'a str,
+        content: &'a str,
+    }
+    serializer.collect_seq(files.iter().map(|file| ApiFile {
+        filename: &file.filename,
+        content: &file.content,
+    }))
 }
 
 /// The base paste.
@@ -124,7 +1362,7 @@ pub struct Paste {
 }
 
 /// The paste result from the API.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct PasteResult {
     /// The paste's creation date.
     pub created_at: String,
@@ -134,6 +1372,41 @@ pub struct PasteResult {
     pub files: Vec<File>,
     /// The paste's ID.
     pub id: String,
+    /// The idempotency key the paste was created with, `None` for fetched pastes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// How the line endings were uploaded by a mystb.in client, `None` for fetched
+    /// pastes and content read from a reader.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_endings: Option<crate::LineEndings>,
+}
+
+impl PasteResult {
+    /// The file with this name, if any.
+    pub fn file(&self, filename: &str) -> Option<&File> {
+        self.files.iter().find(|file| file.filename == filename)
+    }
+
+    /// The content of every file in one string, each preceded by a
+    /// `==> filename <==` header and separated by a blank line, like `head` does
+    /// with several files. The content of a single file is returned as is.
+    pub fn combined_content(&self) -> String {
+        if let [file] = self.files.as_slice() {
+            return file.content.clone();
+        }
+        let mut combined = String::new();
+        for (i, file) in self.files.iter().enumerate() {
+            if i > 0 {
+                if !combined.ends_with('\n') {
+                    combined.push('\n');
+                }
+                combined.push('\n');
+            }
+            combined.push_str(&format!("==> {} <==\n", file.filename));
+            combined.push_str(&file.content);
+        }
+        combined
+    }
 }
 
 /// The result obtained from delete_paste and delete_pastes functions.
@@ -145,8 +1418,68 @@ pub struct DeleteResult {
     pub failed: Option<Vec<String>>,
 }
 
+/// The authenticated user, as told by
+/// [`Client::current_user`](crate::Client::current_user).
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct CurrentUser {
+    /// The user's ID.
+    pub id: u64,
+    /// The user's name.
+    pub username: String,
+    /// The URL of the user's avatar, if any.
+    pub avatar: Option<String>,
+    /// Whether the user is an administrator of the instance.
+    pub admin: bool,
+    /// Whether the user is a subscriber, with higher limits.
+    pub subscriber: bool,
+    /// The theme the user picked on the website, if any.
+    pub theme: Option<String>,
+    /// The other fields sent by the API, such as settings added after this version.
+    #[serde(flatten)]
+    pub settings: serde_json::Map<String, Value>,
+}
+
+/// A page of the authenticated user's pastes, see
+/// [`Client::get_user_pastes_page`](crate::Client::get_user_pastes_page).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct UserPastesPage {
+    /// The pastes of the page.
+    pub pastes: Vec<UserPaste>,
+    /// The page number, starting at 1.
+    pub page: i32,
+    /// The number of pastes across all pages, if the API reports it.
+    pub total: Option<usize>,
+}
+
+/// The outcome of [`Client::prune_bookmarks`](crate::Client::prune_bookmarks).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PruneReport {
+    /// The IDs of the pastes whose bookmark was removed.
+    pub pruned: Vec<String>,
+    /// The IDs of the pastes still bookmarked.
+    pub kept: Vec<String>,
+    /// The IDs of the dead pastes whose bookmark couldn't be removed.
+    pub failed: Vec<String>,
+}
+
+/// A bookmarked paste. This does not contain the files from the paste.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Bookmark {
+    /// The bookmarked paste's ID.
+    pub id: String,
+    /// The paste's creation date.
+    pub created_at: String,
+    /// The paste's expiration date, if any.
+    pub expires: Option<String>,
+    /// When the paste was bookmarked, if told.
+    pub bookmarked_at: Option<String>,
+    /// Where the paste was bookmarked from, such as `web` or `api`, if told.
+    pub origin: Option<String>,
+}
+
 /// The base user paste. This does not contain the files from the paste.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct UserPaste {
     /// The paste's creation date.
     pub created_at: String,
@@ -0,0 +1,177 @@
+//! Shared defaults for pastes created the same way over and over, e.g. incident logs
+//! that should be named and kept alike wherever they're pasted from.
+//!
+//! ```rust,no_run
+//! use myust::{
+//!     template::{PasswordPolicy, PasteTemplate},
+//!     Client, Expiry,
+//! };
+//!
+//! # async fn run(client: Client, log: String) -> Result<(), myust::Error> {
+//! let incidents = PasteTemplate::new()
+//!     .filename("incident-{date}-{time}.log")
+//!     .expires(Expiry::ONE_WEEK)
+//!     .password_policy(PasswordPolicy::Required)
+//!     .header("Incident log, handle with care.");
+//! let paste = client
+//!     .create_paste_with_template(&incidents, |p| p.content(log).password("hunter2"))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{mem::take, time::SystemTime};
+
+use crate::{
+    utils::format_timestamp, Client, Error, Expiry, PasteBuilder, PasteResult, ValidationError,
+};
+
+/// Whether the pastes created from a [`PasteTemplate`] have a password.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PasswordPolicy {
+    /// A password may be set or not.
+    #[default]
+    Optional,
+    /// A paste without a password is rejected with
+    /// [`ValidationError::PasswordRequired`].
+    Required,
+    /// A paste with a password is rejected with
+    /// [`ValidationError::PasswordForbidden`].
+    Forbidden,
+}
+
+/// Defaults applied to a paste by [`Client::create_paste_with_template`].
+///
+/// What the paste sets itself wins over the template's filename and expiration
+/// date. The filename pattern can hold `{date}` and `{time}`, replaced with the UTC
+/// date and time of creation, such as `2023-04-01` and `13-37-00`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PasteTemplate {
+    filename: Option<String>,
+    expires: Option<Expiry>,
+    password_policy: PasswordPolicy,
+    header: Option<String>,
+    footer: Option<String>,
+}
+
+impl PasteTemplate {
+    /// A template without defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The filename pattern of pastes created without a filename.
+    pub fn filename(mut self, pattern: impl Into<String>) -> Self {
+        self.filename = Some(pattern.into());
+        self
+    }
+
+    /// The expiration date of pastes created without one.
+    pub fn expires(mut self, expires: Expiry) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// Whether pastes must, or must not, have a password. Defaults to
+    /// [`PasswordPolicy::Optional`].
+    pub fn password_policy(mut self, policy: PasswordPolicy) -> Self {
+        self.password_policy = policy;
+        self
+    }
+
+    /// Text put on its own lines before the content.
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    /// Text put on its own lines after the content.
+    pub fn footer(mut self, footer: impl Into<String>) -> Self {
+        self.footer = Some(footer.into());
+        self
+    }
+
+    /// The filename pattern with its placeholders replaced for `time`, or `None`
+    /// without a pattern or if `time` isn't between 1970 and the year 9999.
+    pub fn filename_at(&self, time: SystemTime) -> Option<String> {
+        let pattern = self.filename.as_ref()?;
+        let timestamp = format_timestamp(humantime::format_rfc3339_seconds(time))?;
+        let (date, time) = timestamp.trim_end_matches('Z').split_once('T')?;
+        Some(
+            pattern
+                .replace("{date}", date)
+                .replace("{time}", &time.replace(':', "-")),
+        )
+    }
+
+    /// Apply the template to a paste, checking its password policy.
+    ///
+    /// Content read from a reader can't get a header or footer, and is rejected
+    /// with [`Error::Unsupported`] if the template has one.
+    pub fn apply(&self, paste: &mut PasteBuilder) -> Result<(), Error> {
+        match (self.password_policy, &paste.password) {
+            (PasswordPolicy::Required, None) => {
+                return Err(ValidationError::PasswordRequired.into())
+            }
+            (PasswordPolicy::Forbidden, Some(_)) => {
+                return Err(ValidationError::PasswordForbidden.into())
+            }
+            _ => {}
+        }
+        if paste.filename.is_empty() {
+            if let Some(filename) = self.filename_at(SystemTime::now()) {
+                paste.filename = filename;
+            }
+        }
+        if paste.expires.is_none() {
+            paste.expires = self.expires.clone();
+        }
+        if self.header.is_none() && self.footer.is_none() {
+            return Ok(());
+        }
+        if paste.reader.is_some() {
+            return Err(Error::Unsupported(
+                "content read from a reader can't have a header or footer",
+            ));
+        }
+        let mut content = String::new();
+        if let Some(header) = &self.header {
+            push_line(&mut content, header);
+        }
+        push_line(&mut content, &take(&mut paste.content));
+        if let Some(footer) = &self.footer {
+            push_line(&mut content, footer);
+        }
+        paste.content = content;
+        Ok(())
+    }
+}
+
+/// Append `text`, starting it on a new line.
+fn push_line(content: &mut String, text: &str) {
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(text);
+}
+
+impl Client {
+    /// Create a paste with the defaults of a template, see [`PasteTemplate`].
+    pub async fn create_paste_with_template<F>(
+        &self,
+        template: &PasteTemplate,
+        paste: F,
+    ) -> Result<PasteResult, Error>
+    where
+        F: FnOnce(&mut PasteBuilder) -> &mut PasteBuilder,
+    {
+        let mut builder = PasteBuilder::default();
+        paste(&mut builder);
+        template.apply(&mut builder)?;
+        self.create_paste(move |p| {
+            *p = builder;
+            p
+        })
+        .await
+    }
+}
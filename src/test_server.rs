@@ -0,0 +1,727 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "test-server")))]
+// a test helper, where failing loudly is what's wanted
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::indexing_slicing,
+    clippy::string_slice
+)]
+
+//! A tiny in-process fake of the mystb.in API, for integration tests.
+//!
+//! The server implements the subset of the API that myust uses, keeps
+//! everything in memory and runs on a background thread, so tests can
+//! run end-to-end without a token or internet access.
+//!
+//! ```rust
+//! use myust::{test_server::TestServer, Client};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let server = TestServer::start();
+//!     let client = Client::new()
+//!         .base_url(server.url())
+//!         .auth(TestServer::TOKEN)
+//!         .await;
+//!     let paste = client
+//!         .create_paste(|p| p.filename("myust.txt").content("Hello from myust!"))
+//!         .await
+//!         .unwrap();
+//!     assert_eq!(server.paste_count(), 1);
+//!     client.delete_paste(&paste.id).await.unwrap();
+//! }
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::SystemTime,
+};
+
+use serde_json::{json, Value};
+
+use crate::utils::*;
+
+/// The prefix of the admin routes.
+#[cfg(feature = "admin")]
+const ADMIN_ENDPOINT: &str = "/admin";
+
+#[derive(Clone, Debug)]
+struct StoredPaste {
+    created_at: String,
+    expires: Option<String>,
+    files: Vec<Value>,
+    owner: Option<String>,
+    password: Option<String>,
+}
+
+impl StoredPaste {
+    fn expired(&self) -> bool {
+        self.expires
+            .as_deref()
+            .and_then(parse_timestamp)
+            .is_some_and(|expires| expires <= SystemTime::now())
+    }
+
+    fn summary(&self, id: &str) -> Value {
+        json!({
+            "id": id,
+            "created_at": self.created_at,
+            "expires": self.expires,
+        })
+    }
+
+    /// What is told about a paste whose password wasn't given.
+    fn locked(&self, id: &str) -> Value {
+        let files: Vec<Value> = self
+            .files
+            .iter()
+            .map(|file| {
+                json!({
+                    "filename": file["filename"],
+                    "size": file["content"].as_str().map(|c| c.chars().count()),
+                })
+            })
+            .collect();
+        json!({
+            "error": "Unauthorized",
+            "notice": "This paste is password protected",
+            "id": id,
+            "created_at": self.created_at,
+            "expires": self.expires,
+            "files": files,
+        })
+    }
+
+    fn full(&self, id: &str) -> Value {
+        json!({
+            "id": id,
+            "created_at": self.created_at,
+            "expires": self.expires,
+            "files": self.files,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    requests: Vec<ReceivedRequest>,
+    next_id: usize,
+    pastes: HashMap<String, StoredPaste>,
+    order: Vec<String>,
+    /// The bookmarked paste IDs of each user, with when they were bookmarked.
+    bookmarks: HashMap<String, Vec<(String, String)>>,
+    /// The paste created with each idempotency key.
+    idempotency_keys: HashMap<String, String>,
+    /// The IDs of the banned users.
+    banned: HashSet<u64>,
+}
+
+/// A request the server received, as it came over the wire.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ReceivedRequest {
+    /// The HTTP method.
+    pub method: String,
+    /// The path, without the query.
+    pub path: String,
+    /// The headers, names lowercased, in the order they were sent.
+    pub headers: Vec<(String, String)>,
+}
+
+impl ReceivedRequest {
+    /// The value of the first header named `name`, case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: Vec<(String, String)>,
+    token: Option<String>,
+    request_id: Option<String>,
+    if_none_match: Option<String>,
+    idempotency_key: Option<String>,
+    accepts_gzip: bool,
+    body: Value,
+}
+
+struct Response {
+    status: u16,
+    body: Option<Value>,
+    etag: Option<String>,
+}
+
+impl Response {
+    fn json(status: u16, body: Value) -> Self {
+        Response {
+            status,
+            body: Some(body),
+            etag: None,
+        }
+    }
+
+    fn empty(status: u16) -> Self {
+        Response {
+            status,
+            body: None,
+            etag: None,
+        }
+    }
+
+    fn error(status: u16, error: &str) -> Self {
+        Self::json(status, json!({ "error": error }))
+    }
+}
+
+/// A fake mystb.in API server running on a local port.
+///
+/// The server is shut down when this value is dropped.
+pub struct TestServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl TestServer {
+    /// The token of the server's user, whose ID is 1.
+    pub const TOKEN: &'static str = "myust-test-token";
+
+    /// The token of the server's administrator, whose ID is 2. It's only accepted by
+    /// the admin routes and `/users/@me`.
+    #[cfg(feature = "admin")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "admin")))]
+    pub const ADMIN_TOKEN: &'static str = "myust-admin-token";
+
+    /// The number of requests reported as allowed in the rate limit headers.
+    pub const RATE_LIMIT: u64 = 1000;
+
+    /// Start a new server on a random local port.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind the test server");
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State::default()));
+        let running = Arc::new(AtomicBool::new(true));
+        let handle = {
+            let state = state.clone();
+            let running = running.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if !running.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let state = state.clone();
+                        thread::spawn(move || handle_connection(stream, &state));
+                    }
+                }
+            })
+        };
+        TestServer {
+            addr,
+            state,
+            running,
+            handle: Some(handle),
+        }
+    }
+
+    /// The base URL to pass to the clients' `base_url` method.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// The number of pastes currently stored on the server.
+    pub fn paste_count(&self) -> usize {
+        self.state.lock().unwrap().pastes.len()
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        // wake up the accept loop so it can see the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) {
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(s) => s,
+        Err(_) => return,
+    });
+    let request = match read_request(&mut reader) {
+        Some(request) => request,
+        None => return,
+    };
+    let (response, remaining) = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(ReceivedRequest {
+            method: request.method.clone(),
+            path: request.path.clone(),
+            headers: request.headers.clone(),
+        });
+        let remaining = TestServer::RATE_LIMIT.saturating_sub(state.requests.len() as u64);
+        (route(&request, &mut state), remaining)
+    };
+    let _ = write_response(stream, response, &request, remaining);
+}
+
+fn read_request(reader: &mut impl BufRead) -> Option<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?.to_string();
+
+    let mut content_length = 0;
+    let mut gzipped = false;
+    let mut chunked = false;
+    let mut headers = vec![];
+    let mut token = None;
+    let mut request_id = None;
+    let mut if_none_match = None;
+    let mut idempotency_key = None;
+    let mut accepts_gzip = false;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(':')?;
+        let name = name.to_ascii_lowercase();
+        let value = value.trim();
+        headers.push((name.clone(), value.to_string()));
+        match name.as_str() {
+            "content-length" => content_length = value.parse().ok()?,
+            "authorization" => token = value.strip_prefix("Bearer ").map(|t| t.to_string()),
+            "x-request-id" => request_id = Some(value.to_string()),
+            "if-none-match" => if_none_match = Some(value.to_string()),
+            "idempotency-key" => idempotency_key = Some(value.to_string()),
+            "content-encoding" => gzipped = value.eq_ignore_ascii_case("gzip"),
+            "accept-encoding" => {
+                accepts_gzip = value
+                    .split(',')
+                    .any(|encoding| encoding.trim().eq_ignore_ascii_case("gzip"))
+            }
+            "transfer-encoding" => chunked = value.eq_ignore_ascii_case("chunked"),
+            _ => {}
+        }
+    }
+
+    let mut body = if chunked {
+        read_chunked(reader)?
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).ok()?;
+        body
+    };
+    if gzipped {
+        body = gunzip(&body)?;
+    }
+    let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), parse_query(query)),
+        None => (target, HashMap::new()),
+    };
+    Some(Request {
+        method,
+        path,
+        query,
+        headers,
+        token,
+        request_id,
+        if_none_match,
+        idempotency_key,
+        accepts_gzip,
+        body,
+    })
+}
+
+fn read_chunked(reader: &mut impl BufRead) -> Option<Vec<u8>> {
+    let mut body = vec![];
+    loop {
+        let mut size = String::new();
+        reader.read_line(&mut size).ok()?;
+        let size = usize::from_str_radix(size.trim_end(), 16).ok()?;
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk).ok()?;
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
+
+#[cfg(not(feature = "compression"))]
+fn gunzip(_body: &[u8]) -> Option<Vec<u8>> {
+    None
+}
+
+/// The response body, gzipped if the client accepts it and can be answered so.
+#[cfg(feature = "compression")]
+fn encode_body(body: Vec<u8>, accepts_gzip: bool) -> (Vec<u8>, bool) {
+    match accepts_gzip && !body.is_empty() {
+        true => (gzip(&body), true),
+        false => (body, false),
+    }
+}
+
+#[cfg(not(feature = "compression"))]
+fn encode_body(body: Vec<u8>, _accepts_gzip: bool) -> (Vec<u8>, bool) {
+    (body, false)
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn write_response(
+    mut stream: TcpStream,
+    response: Response,
+    request: &Request,
+    remaining: u64,
+) -> std::io::Result<()> {
+    let body = response.body.map(|b| b.to_string()).unwrap_or_default();
+    let (body, gzipped) = encode_body(body.into_bytes(), request.accepts_gzip);
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+        response.status,
+        reason(response.status),
+        body.len(),
+    )?;
+    if gzipped {
+        write!(stream, "Content-Encoding: gzip\r\n")?;
+    }
+    write!(
+        stream,
+        "X-Ratelimit-Limit: {}\r\nX-Ratelimit-Remaining: {}\r\nX-Ratelimit-Reset: 60\r\n",
+        TestServer::RATE_LIMIT,
+        remaining
+    )?;
+    if let Some(request_id) = &request.request_id {
+        write!(stream, "{}: {}\r\n", REQUEST_ID_HEADER, request_id)?;
+    }
+    if let Some(etag) = response.etag {
+        write!(stream, "ETag: {}\r\n", etag)?;
+    }
+    write!(stream, "Connection: close\r\n\r\n")?;
+    stream.write_all(&body)?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        422 => "Unprocessable Entity",
+        _ => "Unknown",
+    }
+}
+
+fn route(request: &Request, state: &mut State) -> Response {
+    let authorized = request.token.as_deref() == Some(TestServer::TOKEN);
+    if authorized && state.banned.contains(&1) {
+        return Response::error(403, "Banned");
+    }
+    #[cfg(feature = "admin")]
+    if request.path.starts_with(ADMIN_ENDPOINT) {
+        return admin_route(request, state);
+    }
+    let paste_prefix = format!("{}/", PASTE_ENDPOINT);
+    match (request.method.as_str(), request.path.as_str()) {
+        #[cfg(feature = "admin")]
+        ("GET", SELF_ENDPOINT) if request.token.as_deref() == Some(TestServer::ADMIN_TOKEN) => {
+            Response::json(200, json!({ "id": 2, "username": "admin", "admin": true }))
+        }
+        ("GET", SELF_ENDPOINT) => {
+            if authorized {
+                Response::json(
+                    200,
+                    json!({
+                        "id": 1,
+                        "username": "myust",
+                        "admin": false,
+                        "subscriber": false,
+                        "theme": "dark",
+                        "show_line_numbers": true,
+                    }),
+                )
+            } else {
+                Response::error(401, "Unauthorized")
+            }
+        }
+        ("PUT", PASTE_ENDPOINT) => create_paste(request, state),
+        ("DELETE", PASTE_ENDPOINT) => delete_pastes(request, state),
+        ("GET", path) if path.starts_with(&paste_prefix) => {
+            get_paste(request, state, &path[paste_prefix.len()..])
+        }
+        ("DELETE", path) if path.starts_with(&paste_prefix) => {
+            match delete_paste(request, state, &path[paste_prefix.len()..]) {
+                Ok(()) => Response::json(200, json!({})),
+                Err(response) => response,
+            }
+        }
+        (_, USER_PASTES_ENDPOINT | BOOKMARK_ENDPOINT) if !authorized => {
+            Response::error(401, "Unauthorized")
+        }
+        ("GET", USER_PASTES_ENDPOINT) => {
+            let limit = request.body["limit"].as_u64().unwrap_or(50) as usize;
+            let page = request.body["page"].as_u64().unwrap_or(1).max(1) as usize;
+            let pastes: Vec<Value> = state
+                .order
+                .iter()
+                .filter(|id| state.pastes[*id].owner == request.token)
+                .skip((page - 1) * limit)
+                .take(limit)
+                .map(|id| state.pastes[id].summary(id))
+                .collect();
+            Response::json(200, json!({ "pastes": pastes }))
+        }
+        ("PUT", BOOKMARK_ENDPOINT) => {
+            let id = request.body["paste_id"].as_str().unwrap_or_default();
+            if !state.pastes.contains_key(id) {
+                return Response::error(404, "Paste not found");
+            }
+            let bookmarks = state.bookmarks.entry(owner_key(request)).or_default();
+            if !bookmarks.iter().any(|(b, _)| b == id) {
+                bookmarks.push((
+                    id.to_string(),
+                    humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+                ));
+            }
+            Response::empty(201)
+        }
+        ("DELETE", BOOKMARK_ENDPOINT) => {
+            let id = request.body["paste_id"].as_str().unwrap_or_default();
+            let bookmarks = state.bookmarks.entry(owner_key(request)).or_default();
+            match bookmarks.iter().position(|(b, _)| b == id) {
+                Some(index) => {
+                    bookmarks.remove(index);
+                    Response::empty(204)
+                }
+                None => Response::error(404, "Bookmark not found"),
+            }
+        }
+        ("GET", BOOKMARK_ENDPOINT) => {
+            let bookmarks: Vec<Value> = state
+                .bookmarks
+                .get(&owner_key(request))
+                .into_iter()
+                .flatten()
+                .filter_map(|(id, bookmarked_at)| {
+                    let mut bookmark = state.pastes.get(id)?.summary(id);
+                    bookmark["bookmarked_at"] = json!(bookmarked_at);
+                    bookmark["origin"] = json!("api");
+                    Some(bookmark)
+                })
+                .collect();
+            Response::json(200, json!({ "bookmarks": bookmarks }))
+        }
+        _ => Response::error(404, "Not Found"),
+    }
+}
+
+#[cfg(feature = "admin")]
+fn admin_route(request: &Request, state: &mut State) -> Response {
+    if request.token.as_deref() != Some(TestServer::ADMIN_TOKEN) {
+        return Response::error(403, "Forbidden");
+    }
+    let path = &request.path[ADMIN_ENDPOINT.len()..];
+    match (request.method.as_str(), path) {
+        ("GET", "/stats") => Response::json(
+            200,
+            json!({
+                "pastes": state.pastes.len(),
+                "users": 2,
+                "requests": state.requests.len(),
+            }),
+        ),
+        ("GET", "/users") => {
+            let users = match request.query.get("page").map(String::as_str) {
+                None | Some("1") => vec![
+                    json!({ "id": 1, "username": "myust", "banned": state.banned.contains(&1) }),
+                    json!({ "id": 2, "username": "admin", "admin": true }),
+                ],
+                Some(_) => vec![],
+            };
+            Response::json(200, json!({ "users": users }))
+        }
+        ("DELETE", path) if path.starts_with("/paste/") => {
+            let id = &path["/paste/".len()..];
+            match state.pastes.remove(id) {
+                Some(_) => {
+                    state.order.retain(|p| p != id);
+                    Response::empty(204)
+                }
+                None => Response::error(404, "Paste not found"),
+            }
+        }
+        (method @ ("PUT" | "DELETE"), path) => {
+            let user = path
+                .strip_prefix("/users/")
+                .and_then(|path| path.strip_suffix("/ban"))
+                .and_then(|id| id.parse::<u64>().ok());
+            match user {
+                Some(id @ (1 | 2)) => {
+                    match method {
+                        "PUT" => state.banned.insert(id),
+                        _ => state.banned.remove(&id),
+                    };
+                    Response::empty(204)
+                }
+                _ => Response::error(404, "Not Found"),
+            }
+        }
+        _ => Response::error(404, "Not Found"),
+    }
+}
+
+fn owner_key(request: &Request) -> String {
+    request.token.clone().unwrap_or_default()
+}
+
+fn create_paste(request: &Request, state: &mut State) -> Response {
+    let files = match request.body["files"].as_array() {
+        Some(files) if !files.is_empty() => files,
+        _ => {
+            return Response::json(
+                422,
+                json!({ "detail": [{ "loc": ["body", "files"], "msg": "field required", "type": "value_error.missing" }] }),
+            )
+        }
+    };
+    for (index, file) in files.iter().enumerate() {
+        if file["content"].as_str().is_none_or(str::is_empty) {
+            return Response::json(
+                422,
+                json!({ "detail": [{ "loc": ["body", "files", index, "content"], "msg": "content can not be empty", "type": "value_error" }] }),
+            );
+        }
+    }
+
+    // a replayed request gets the paste created the first time
+    let replayed = request
+        .idempotency_key
+        .as_ref()
+        .and_then(|key| state.idempotency_keys.get(key))
+        .and_then(|id| Some((id, state.pastes.get(id)?)));
+    if let Some((id, paste)) = replayed {
+        return Response::json(201, paste.full(id));
+    }
+
+    state.next_id += 1;
+    let id = format!("FakePaste{}", state.next_id);
+    let paste = StoredPaste {
+        created_at: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+        expires: request.body["expires"].as_str().map(|e| e.to_string()),
+        files: files.clone(),
+        owner: request.token.clone(),
+        password: request.body["password"].as_str().map(|p| p.to_string()),
+    };
+    let body = paste.full(&id);
+    state.pastes.insert(id.clone(), paste);
+    if let Some(key) = &request.idempotency_key {
+        state.idempotency_keys.insert(key.clone(), id.clone());
+    }
+    state.order.push(id);
+    Response::json(201, body)
+}
+
+fn get_paste(request: &Request, state: &State, id: &str) -> Response {
+    match state.pastes.get(id) {
+        None => Response::error(404, "Paste not found"),
+        Some(paste) if paste.expired() => Response::error(404, "Paste not found"),
+        Some(paste)
+            if paste.password.is_some()
+                && paste.password != request.query.get("password").cloned() =>
+        {
+            Response::json(401, paste.locked(id))
+        }
+        Some(_) if request.if_none_match.as_deref() == Some(&etag(id)) => Response {
+            etag: Some(etag(id)),
+            ..Response::empty(304)
+        },
+        Some(paste) => Response {
+            etag: Some(etag(id)),
+            ..Response::json(200, paste.full(id))
+        },
+    }
+}
+
+/// Pastes can't be edited, so the ID is enough to tell versions apart.
+fn etag(id: &str) -> String {
+    format!("\"{}\"", id)
+}
+
+fn delete_paste(request: &Request, state: &mut State, id: &str) -> Result<(), Response> {
+    match state.pastes.get(id) {
+        None => Err(Response::error(404, "Paste not found")),
+        Some(paste) if paste.owner.is_none() || paste.owner != request.token => {
+            Err(Response::error(401, "Unauthorized"))
+        }
+        Some(_) => {
+            state.pastes.remove(id);
+            state.order.retain(|p| p != id);
+            Ok(())
+        }
+    }
+}
+
+fn delete_pastes(request: &Request, state: &mut State) -> Response {
+    let ids: Vec<String> = request.body["pastes"]
+        .as_array()
+        .map(|ids| {
+            ids.iter()
+                .filter_map(|id| id.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if ids.len() > DELETE_BATCH_LIMIT {
+        return Response::error(400, "Too many pastes to delete at once");
+    }
+    let (mut succeeded, mut failed) = (vec![], vec![]);
+    for id in ids {
+        match delete_paste(request, state, &id) {
+            Ok(()) => succeeded.push(id),
+            Err(_) => failed.push(id),
+        }
+    }
+    Response::json(200, json!({ "succeeded": succeeded, "failed": failed }))
+}
@@ -0,0 +1,129 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, MutexGuard},
+    time::Instant,
+};
+
+use reqwest::StatusCode;
+
+use crate::{utils::lock, RateLimit};
+
+/// How a client picks the token of each request from its token pool, see the
+/// client's `token_pool` method.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TokenRotation {
+    /// Use each token in turn.
+    #[default]
+    RoundRobin,
+    /// Use the token that was rate limited the longest time ago, or never, taking
+    /// them in turn when several qualify.
+    LeastRecentlyLimited,
+}
+
+/// The tokens a client rotates across, shared by its clones.
+pub(crate) struct TokenPool {
+    /// The `Authorization` header values.
+    tokens: Vec<String>,
+    rotation: TokenRotation,
+    state: Mutex<PoolState>,
+}
+
+#[derive(Default)]
+struct PoolState {
+    next: usize,
+    last_limited: Vec<Option<Instant>>,
+    /// The token each paste created through the pool was created with.
+    owners: HashMap<String, usize>,
+}
+
+/// The index of the pooled token a request was sent with, kept in the response
+/// extensions.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TokenIndex(pub(crate) usize);
+
+impl TokenPool {
+    /// A pool of the tokens, or `None` if there are none.
+    pub(crate) fn new(
+        tokens: impl IntoIterator<Item = impl Into<String>>,
+        rotation: TokenRotation,
+    ) -> Option<Self> {
+        let tokens: Vec<String> = tokens
+            .into_iter()
+            .map(|token| format!("Bearer {}", token.into()))
+            .collect();
+        if tokens.is_empty() {
+            return None;
+        }
+        let state = PoolState {
+            last_limited: vec![None; tokens.len()],
+            ..Default::default()
+        };
+        Some(TokenPool {
+            tokens,
+            rotation,
+            state: Mutex::new(state),
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        lock(&self.state)
+    }
+
+    /// The `Authorization` header value of a token.
+    pub(crate) fn token(&self, index: usize) -> Option<&str> {
+        self.tokens.get(index).map(String::as_str)
+    }
+
+    /// The token to send a request with. A paste created through the pool keeps
+    /// using the token it was created with, and other user-scoped requests use the
+    /// client's own token, so they all act on the same account.
+    pub(crate) fn select(&self, user_scoped: bool, paste_id: Option<&str>) -> Option<usize> {
+        let mut state = self.state();
+        if let Some(owner) = paste_id.and_then(|id| state.owners.get(id)) {
+            return Some(*owner);
+        }
+        if user_scoped {
+            return None;
+        }
+        let count = self.tokens.len();
+        let index = match self.rotation {
+            TokenRotation::RoundRobin => state.next % count,
+            TokenRotation::LeastRecentlyLimited => (0..count)
+                .map(|offset| (state.next + offset) % count)
+                .min_by_key(|&index| state.last_limited.get(index).copied().flatten())
+                .unwrap_or_default(),
+        };
+        state.next = index + 1;
+        Some(index)
+    }
+
+    /// Remember that a token was rate limited, from the response it got.
+    pub(crate) fn observe(&self, index: usize, status: StatusCode, rate_limit: Option<RateLimit>) {
+        let limited = status == StatusCode::TOO_MANY_REQUESTS
+            || rate_limit.is_some_and(|rate_limit| rate_limit.remaining == 0);
+        if limited {
+            if let Some(last_limited) = self.state().last_limited.get_mut(index) {
+                *last_limited = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Remember the token a paste was created with.
+    pub(crate) fn created(&self, paste_id: &str, index: usize) {
+        self.state().owners.insert(paste_id.to_string(), index);
+    }
+
+    pub(crate) fn deleted(&self, paste_id: &str) {
+        self.state().owners.remove(paste_id);
+    }
+}
+
+impl fmt::Debug for TokenPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenPool")
+            .field("tokens", &self.tokens.len())
+            .field("rotation", &self.rotation)
+            .finish()
+    }
+}
@@ -1,33 +1,69 @@
-use crate::structs::response::MyustResponse;
+use crate::{
+    structs::{response::MyustResponse, CreatePaste, Error},
+    PasteId,
+};
 use async_trait::async_trait;
+use reqwest::header::HeaderMap;
 use serde_json::Value;
 
 #[async_trait]
 pub trait ClientBookmark {
-    async fn request_create_bookmark(&self, json: Value) -> MyustResponse;
-    async fn request_delete_bookmark(&self, json: Value) -> MyustResponse;
-    async fn request_get_user_bookmarks(&self) -> MyustResponse;
+    async fn request_create_bookmark(&self, json: Value) -> Result<MyustResponse, Error>;
+    async fn request_delete_bookmark(&self, json: Value) -> Result<MyustResponse, Error>;
+    async fn request_get_user_bookmarks(&self) -> Result<MyustResponse, Error>;
 }
 
 #[async_trait]
 pub trait ClientPaste {
-    async fn request_create_paste(&self, json: Value) -> MyustResponse;
-    async fn request_delete_paste(&self, paste_id: &str) -> MyustResponse;
-    async fn request_delete_pastes(&self, json: Value) -> MyustResponse;
-    async fn request_get_paste(&self, paste_id: String, password: Option<String>) -> MyustResponse;
-    async fn request_get_user_pastes(&self, json: Value) -> MyustResponse;
+    async fn request_create_paste(
+        &self,
+        body: &CreatePaste<'_>,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error>;
+    async fn request_delete_paste(&self, paste_id: &PasteId) -> Result<MyustResponse, Error>;
+    async fn request_delete_pastes(&self, json: Value) -> Result<MyustResponse, Error>;
+    async fn request_get_paste(
+        &self,
+        paste_id: String,
+        password: Option<String>,
+        etag: Option<String>,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error>;
+    async fn request_get_user_pastes(
+        &self,
+        json: Value,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error>;
 }
 
+#[cfg(feature = "sync")]
+#[allow(dead_code)] // not every endpoint is exposed by `SyncClient` yet
 pub trait SyncClientBookmark {
-    fn request_create_bookmark(&self, json: Value) -> MyustResponse;
-    fn request_delete_bookmark(&self, json: Value) -> MyustResponse;
-    fn request_get_user_bookmarks(&self) -> MyustResponse;
+    fn request_create_bookmark(&self, json: Value) -> Result<MyustResponse, Error>;
+    fn request_delete_bookmark(&self, json: Value) -> Result<MyustResponse, Error>;
+    fn request_get_user_bookmarks(&self) -> Result<MyustResponse, Error>;
 }
 
+#[cfg(feature = "sync")]
+#[allow(dead_code)] // not every endpoint is exposed by `SyncClient` yet
 pub trait SyncClientPaste {
-    fn request_create_paste(&self, json: Value) -> MyustResponse;
-    fn request_delete_paste(&self, paste_id: &str) -> MyustResponse;
-    fn request_delete_pastes(&self, json: Value) -> MyustResponse;
-    fn request_get_paste(&self, paste_id: String, password: Option<String>) -> MyustResponse;
-    fn request_get_user_pastes(&self, json: Value) -> MyustResponse;
+    fn request_create_paste(
+        &self,
+        body: &CreatePaste<'_>,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error>;
+    fn request_delete_paste(&self, paste_id: &PasteId) -> Result<MyustResponse, Error>;
+    fn request_delete_pastes(&self, json: Value) -> Result<MyustResponse, Error>;
+    fn request_get_paste(
+        &self,
+        paste_id: String,
+        password: Option<String>,
+        etag: Option<String>,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error>;
+    fn request_get_user_pastes(
+        &self,
+        json: Value,
+        headers: HeaderMap,
+    ) -> Result<MyustResponse, Error>;
 }
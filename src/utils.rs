@@ -1,4 +1,207 @@
-pub const BOOKMARK_ENDPOINT: &str = "https://api.mystb.in/users/bookmarks";
-pub const PASTE_ENDPOINT: &str = "https://api.mystb.in/paste";
-pub const SELF_ENDPOINT: &str = "https://api.mystb.in/users/@me";
-pub const USER_PASTES_ENDPOINT: &str = "https://api.mystb.in/pastes/@me";
+pub const BASE_URL: &str = "https://api.mystb.in";
+pub const WEB_URL: &str = "https://mystb.in";
+pub const BOOKMARK_ENDPOINT: &str = "/users/bookmarks";
+pub const PASTE_ENDPOINT: &str = "/paste";
+pub const SELF_ENDPOINT: &str = "/users/@me";
+pub const USER_PASTES_ENDPOINT: &str = "/pastes/@me";
+/// The most paste IDs the API accepts in one delete request.
+pub const DELETE_BATCH_LIMIT: usize = 100;
+/// How many delete batches are sent at once.
+pub const DELETE_BATCH_CONCURRENCY: usize = 4;
+/// How many user pastes are requested per page when listing all of them.
+pub const USER_PASTES_PAGE_SIZE: i32 = 50;
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Url,
+};
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::{Error, PasteResult};
+
+/// The last `ETag` and paste seen for each paste ID and password.
+pub type Validators = Arc<Mutex<HashMap<(String, Option<String>), (String, PasteResult)>>>;
+
+/// Parse an RFC 3339 timestamp in UTC, as returned by the API, e.g.
+/// `2023-05-01T12:00:00.123456+00:00`.
+pub fn parse_timestamp(timestamp: &str) -> Option<SystemTime> {
+    let timestamp = timestamp.trim();
+    let timestamp = match timestamp.strip_suffix("+00:00") {
+        Some(timestamp) => format!("{}Z", timestamp),
+        None => timestamp.to_string(),
+    };
+    humantime::parse_rfc3339_weak(&timestamp).ok()
+}
+
+/// The start of the year 10000, which RFC 3339 can't represent.
+pub const MAX_TIMESTAMP: Duration = Duration::from_secs(253_402_300_800);
+
+/// Write a timestamp, or `None` before 1970 or after the year 9999, where
+/// humantime would panic.
+pub fn format_timestamp(timestamp: humantime::Rfc3339Timestamp) -> Option<String> {
+    let since_epoch = timestamp.get_ref().duration_since(UNIX_EPOCH).ok()?;
+    (since_epoch < MAX_TIMESTAMP).then(|| timestamp.to_string())
+}
+
+/// What sensitive values are replaced with in `Debug` output and errors.
+pub const REDACTED: &str = "***";
+
+const SENSITIVE_KEYS: [&str; 3] = ["authorization", "password", "token"];
+
+/// Replace the values of sensitive keys anywhere in `value` with [`REDACTED`].
+pub fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SENSITIVE_KEYS.contains(&key.to_ascii_lowercase().as_str()) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Strip the paste password from the URL attached to a reqwest error.
+pub fn redact_error(mut err: reqwest::Error) -> reqwest::Error {
+    if let Some(url) = err.url_mut() {
+        redact_url(url);
+    }
+    err
+}
+
+/// Turn a middleware failure into an [`Error`], redacting any request URL.
+#[cfg(feature = "middleware")]
+pub fn middleware_error(err: reqwest_middleware::Error) -> Error {
+    match err {
+        reqwest_middleware::Error::Reqwest(err) => err.into(),
+        reqwest_middleware::Error::Middleware(err) => Error::Io(std::io::Error::other(err)),
+    }
+}
+
+/// Lock a mutex, ignoring the poisoning left by a thread that panicked while holding
+/// it: the guarded state is only ever replaced whole, so it stays consistent.
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Strip the paste password from a URL.
+pub fn redact_url(url: &mut Url) {
+    if url.query_pairs().any(|(key, _)| key == "password") {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(key, value)| match key.as_ref() {
+                "password" => (key.into_owned(), REDACTED.to_string()),
+                _ => (key.into_owned(), value.into_owned()),
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+}
+
+/// The query string used to get a paste.
+pub fn password_query(password: Option<&str>) -> String {
+    match password {
+        Some(password) => format!("?password={}", password),
+        None => String::new(),
+    }
+}
+
+/// Format an optional secret for `Debug` output without revealing it.
+pub fn redact_option(secret: &Option<String>) -> Option<&'static str> {
+    secret.as_ref().map(|_| REDACTED)
+}
+
+/// The names of headers for `Debug` output, leaving their values out.
+pub fn header_names(headers: &HeaderMap) -> Vec<&str> {
+    headers.keys().map(HeaderName::as_str).collect()
+}
+
+/// Whether the API rejected a token when checking it, as opposed to failing to
+/// check it.
+pub fn token_rejected(status_code: u16) -> bool {
+    matches!(status_code, 401 | 403)
+}
+
+/// Generate a reasonably unique ID to correlate a request with server-side logs.
+pub fn new_request_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("myust-{:x}-{:x}", nanos, count)
+}
+
+/// Generate an idempotency key, unique across processes too.
+pub fn new_idempotency_key() -> String {
+    format!("{}-{:x}", new_request_id(), std::process::id())
+}
+
+/// Add the idempotency key of a create request to its headers, generating one if
+/// none was given, and return it.
+pub fn idempotency_key(headers: &mut HeaderMap, key: Option<String>) -> String {
+    let key = key.unwrap_or_else(new_idempotency_key);
+    if let Ok(value) = HeaderValue::from_str(&key) {
+        headers.insert(IDEMPOTENCY_KEY_HEADER, value);
+    }
+    key
+}
+
+/// Gzip a request body.
+#[cfg(feature = "compression")]
+pub fn gzip(body: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    // writing to a Vec can't fail
+    #[allow(clippy::unwrap_used)]
+    {
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    }
+}
+
+/// Decompress a gzipped body, or `None` if it isn't valid gzip.
+#[cfg(feature = "compression")]
+pub fn gunzip(body: &[u8]) -> Option<Vec<u8>> {
+    use std::io::Read;
+
+    let mut decoded = vec![];
+    flate2::read::GzDecoder::new(body)
+        .read_to_end(&mut decoded)
+        .ok()?;
+    Some(decoded)
+}
+
+/// Run `future` unless `cancel` is cancelled first, in which case it's dropped.
+pub async fn cancellable<T, E>(
+    cancel: &CancellationToken,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T, Error>
+where
+    Error: From<E>,
+{
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => Err(Error::Cancelled),
+        result = future => Ok(result?),
+    }
+}
@@ -0,0 +1,258 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "watch")))]
+
+//! Keeping a paste in sync with a local file, e.g. to share a live log or config
+//! during a debugging session.
+//!
+//! mystb.in pastes can't be edited, so the watched paste is kept as it is and every
+//! change of the file creates a new paste, with the password, expiration date and
+//! other files of the watched one. [`FileWatch::paste_id`] returns the latest, and
+//! the one it replaces is deleted, which requires owning it.
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use myust::Client;
+//!
+//! # async fn run() -> Result<(), myust::Error> {
+//! let client = Client::new().auth("YOUR_MYSTBIN_TOKEN").await;
+//! let watch = client
+//!     .watch_file("app.log", "EquipmentMovingExpensive", |w| {
+//!         w.debounce(Duration::from_secs(2))
+//!     })
+//!     .await?;
+//! // ...
+//! println!("https://mystb.in/{}", watch.paste_id());
+//! watch.stop();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use tokio::task::JoinHandle;
+
+use crate::{
+    paste_id::IntoPasteId,
+    utils::{lock, redact_option},
+    Client, CreatePasteRequest, Error, Expiry, File, PasteProvider, PasteResult, StatusCode,
+};
+
+/// The options of [`Client::watch_file`].
+#[derive(Clone)]
+pub struct WatchOptions {
+    debounce: Duration,
+    password: Option<String>,
+}
+
+impl WatchOptions {
+    /// How long the file must stay untouched before a change is pushed, so a file
+    /// written in bursts creates one paste per burst. Defaults to two seconds.
+    pub fn debounce(&mut self, debounce: Duration) -> &mut Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// (optional) The password of the watched paste, which the new pastes get too.
+    pub fn password(&mut self, password: impl Into<String>) -> &mut Self {
+        self.password = Some(password.into());
+        self
+    }
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        WatchOptions {
+            debounce: Duration::from_secs(2),
+            password: None,
+        }
+    }
+}
+
+impl fmt::Debug for WatchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchOptions")
+            .field("debounce", &self.debounce)
+            .field("password", &redact_option(&self.password))
+            .finish()
+    }
+}
+
+/// A file being watched by [`Client::watch_file`].
+///
+/// Watching stops with [`stop`](Self::stop), or when the runtime shuts down.
+#[derive(Debug)]
+pub struct FileWatch {
+    paste_id: Arc<Mutex<String>>,
+    error: Arc<Mutex<Option<Error>>>,
+    task: JoinHandle<()>,
+}
+
+impl FileWatch {
+    /// The ID of the paste holding the latest content of the file, the watched
+    /// paste until the file changes.
+    pub fn paste_id(&self) -> String {
+        lock(&self.paste_id).clone()
+    }
+
+    /// Take the last error met while updating the paste, if any.
+    ///
+    /// Watching goes on after an error, the next change being pushed as usual. A
+    /// replaced paste that couldn't be deleted is tried again on the next change.
+    pub fn take_error(&self) -> Option<Error> {
+        lock(&self.error).take()
+    }
+
+    /// Stop watching the file. The watched paste and the current one are kept.
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+/// The modification time and length of a file, to notice changes.
+async fn version(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+impl Client {
+    /// Watch a local file, pushing its content to a new paste based on `paste_id`
+    /// whenever it changes, see the [module docs](crate::watch).
+    ///
+    /// The file is named after the local one, replacing the watched paste's file of
+    /// the same name if any. Fails if the watched paste can't be fetched. This must
+    /// be called from within a Tokio runtime.
+    pub async fn watch_file<F>(
+        &self,
+        path: impl AsRef<Path>,
+        paste_id: impl IntoPasteId,
+        options: F,
+    ) -> Result<FileWatch, Error>
+    where
+        F: FnOnce(&mut WatchOptions) -> &mut WatchOptions,
+    {
+        let mut builder = WatchOptions::default();
+        let options = options(&mut builder).clone();
+        let paste_id = paste_id.into_paste_id()?;
+        let watched = self
+            .get_paste(|p| {
+                p.id(paste_id.clone());
+                if let Some(password) = &options.password {
+                    p.password(password);
+                }
+                p
+            })
+            .await?;
+        let path = path.as_ref().to_path_buf();
+        let current = Arc::new(Mutex::new(watched.id.clone()));
+        let error = Arc::new(Mutex::new(None));
+        let task = tokio::spawn(watch(
+            Watcher {
+                client: self.clone(),
+                path,
+                watched,
+                password: options.password,
+                current: current.clone(),
+                replaced: vec![],
+            },
+            error.clone(),
+            options.debounce,
+        ));
+        Ok(FileWatch {
+            paste_id: current,
+            error,
+            task,
+        })
+    }
+}
+
+struct Watcher {
+    client: Client,
+    path: PathBuf,
+    watched: PasteResult,
+    password: Option<String>,
+    current: Arc<Mutex<String>>,
+    /// Pastes created by the watch and since replaced, left to delete.
+    replaced: Vec<String>,
+}
+
+async fn watch(mut watcher: Watcher, error: Arc<Mutex<Option<Error>>>, debounce: Duration) {
+    let interval = (debounce / 4).max(Duration::from_millis(10));
+    let mut pushed = version(&watcher.path).await;
+    let mut seen = pushed;
+    let mut changed_at = None;
+    loop {
+        tokio::time::sleep(interval).await;
+        let current = version(&watcher.path).await;
+        if current != seen {
+            seen = current;
+            changed_at = Some(tokio::time::Instant::now());
+            continue;
+        }
+        match changed_at {
+            Some(at) if at.elapsed() >= debounce && seen != pushed => {
+                changed_at = None;
+                if let Err(err) = watcher.push().await {
+                    *lock(&error) = Some(err);
+                }
+                pushed = seen;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Watcher {
+    /// Create a paste with the file's content, then delete the pastes it replaces.
+    async fn push(&mut self) -> Result<(), Error> {
+        let content = tokio::fs::read_to_string(&self.path).await?;
+        let filename = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut files = self.watched.files.clone();
+        let file = File {
+            filename,
+            content,
+            syntax: None,
+        };
+        match files.iter_mut().find(|f| f.filename == file.filename) {
+            Some(existing) => *existing = file,
+            None => files.insert(0, file),
+        }
+        let expires = match &self.watched.expires {
+            Some(timestamp) => Some(Expiry::try_from_rfc3339(timestamp)?),
+            None => Some(Expiry::never()),
+        };
+        let paste = self
+            .client
+            .create(CreatePasteRequest {
+                files,
+                password: self.password.clone(),
+                expires,
+                ..Default::default()
+            })
+            .await?;
+        let previous = std::mem::replace(&mut *lock(&self.current), paste.id);
+        if previous != self.watched.id {
+            self.replaced.push(previous);
+        }
+        let mut failed = None;
+        let mut left = vec![];
+        for id in std::mem::take(&mut self.replaced) {
+            match self.client.delete_paste(id.as_str()).await {
+                Err(err) if err.status() != Some(StatusCode::NOT_FOUND) => {
+                    left.push(id);
+                    failed.get_or_insert(err);
+                }
+                _ => {}
+            }
+        }
+        self.replaced = left;
+        failed.map_or(Ok(()), Err)
+    }
+}
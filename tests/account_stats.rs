@@ -0,0 +1,46 @@
+use myust::{test_server::TestServer, Expiry};
+
+#[tokio::test]
+async fn account_stats() {
+    let server = TestServer::start();
+    let client = myust::Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("a.txt").content("abc"));
+            p.file(|f| f.filename("b.txt").content("é"))
+        })
+        .await
+        .unwrap();
+    let soon = client
+        .create_paste(|p| {
+            p.filename("soon.txt")
+                .content("soon")
+                .expires(Expiry::ONE_HOUR)
+        })
+        .await
+        .unwrap();
+    let locked = client
+        .create_paste(|p| {
+            p.filename("locked.txt")
+                .content("secret")
+                .password("pw")
+                .expires(Expiry::ONE_DAY)
+        })
+        .await
+        .unwrap();
+
+    let stats = client.account_stats().await.unwrap();
+    assert_eq!(stats.pastes, 3);
+    assert_eq!(stats.files, 3);
+    assert_eq!(stats.total_size, "abc".len() + "é".len() + "soon".len());
+    assert_eq!(stats.unfetched, [locked.id]);
+    assert_eq!(stats.per_month.values().sum::<usize>(), 3);
+    assert!(stats
+        .per_month
+        .keys()
+        .all(|month| month.len() == "YYYY-MM".len()));
+    assert_eq!(stats.soonest_expiry.unwrap().id, soon.id);
+}
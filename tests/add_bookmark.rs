@@ -1,9 +1,59 @@
+use myust::test_server::TestServer;
+
 #[tokio::test]
 async fn add_bookmark() {
+    let server = TestServer::start();
     let client = myust::Client::new()
-        .auth(std::env::var("MYSTBIN_TOKEN").unwrap())
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
         .await;
-    client.create_bookmark("InfraredYukonEmpty").await.unwrap();
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("bookmark me"))
+        .await
+        .unwrap();
+    client.create_bookmark(&paste.id).await.unwrap();
     let bm = client.get_user_bookmarks().await.unwrap();
-    println!("{bm:#?}")
+    println!("{bm:#?}");
+    assert_eq!(bm.len(), 1);
+    assert_eq!(bm[0].id, paste.id);
+    assert!(bm[0].bookmarked_at.is_some());
+    assert_eq!(bm[0].origin.as_deref(), Some("api"));
+}
+
+#[tokio::test]
+async fn has_bookmark() {
+    let server = TestServer::start();
+    let client = myust::Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("bookmark me"))
+        .await
+        .unwrap();
+    assert!(!client.has_bookmark(&paste.id).await.unwrap());
+    client.create_bookmark(&paste.id).await.unwrap();
+    assert!(client.has_bookmark(&paste.id).await.unwrap());
+    client.delete_bookmark(&paste.id).await.unwrap();
+    assert!(!client.has_bookmark(&paste.id).await.unwrap());
+}
+
+#[tokio::test]
+async fn toggle_bookmark() {
+    let server = TestServer::start();
+    let client = myust::Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("bookmark me"))
+        .await
+        .unwrap();
+    assert!(client.toggle_bookmark(&paste.id).await.unwrap());
+    assert!(client.has_bookmark(&paste.id).await.unwrap());
+    assert!(!client.toggle_bookmark(&paste.id).await.unwrap());
+    assert!(!client.has_bookmark(&paste.id).await.unwrap());
+
+    let err = client.toggle_bookmark("MissingPaste").await.unwrap_err();
+    assert_eq!(err.status(), Some(myust::StatusCode::NOT_FOUND));
 }
@@ -0,0 +1,53 @@
+use myust::{test_server::TestServer, Client, StatusCode};
+
+#[tokio::test]
+async fn admin() {
+    let server = TestServer::start();
+    let user = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let admin = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::ADMIN_TOKEN)
+        .await;
+    let paste = user
+        .create_paste(|p| p.filename("spam.txt").content("spam"))
+        .await
+        .unwrap();
+
+    let stats = admin.admin().stats().await.unwrap();
+    assert_eq!(stats.pastes, 1);
+    assert_eq!(stats.users, 2);
+    assert!(stats.other.contains_key("requests"));
+
+    admin.admin().delete_paste(&paste.id).await.unwrap();
+    assert_eq!(server.paste_count(), 0);
+    let err = admin.admin().delete_paste(&paste.id).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+
+    let users = admin.admin().users(1).await.unwrap();
+    assert_eq!(users.len(), 2);
+    assert!(users[1].admin);
+    assert!(admin.admin().users(2).await.unwrap().is_empty());
+
+    admin.admin().ban_user(1).await.unwrap();
+    assert!(admin.admin().users(1).await.unwrap()[0].banned);
+    let err = user.current_user().await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    admin.admin().unban_user(1).await.unwrap();
+    assert!(user.current_user().await.is_ok());
+}
+
+#[tokio::test]
+async fn admin_requires_rights() {
+    let server = TestServer::start();
+    let user = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let err = user.admin().stats().await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    let err = user.admin().ban_user(2).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+}
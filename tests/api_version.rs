@@ -0,0 +1,28 @@
+use myust::{test_server::TestServer, ApiVersion, Client};
+
+#[tokio::test]
+async fn api_version() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .api_version(ApiVersion::V3);
+    assert!(format!("{client:?}").contains("api_version: V3"));
+
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi").password("myust"))
+        .await
+        .unwrap();
+    client
+        .get_paste(|p| p.id(&paste.id).password("myust"))
+        .await
+        .unwrap();
+    assert_eq!(
+        client.last_url().unwrap().path(),
+        format!("/paste/{}", paste.id)
+    );
+    client.delete_paste(&paste.id).await.unwrap_err();
+    assert_eq!(
+        client.last_url().unwrap().path(),
+        format!("/paste/{}", paste.id)
+    );
+}
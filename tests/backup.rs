@@ -0,0 +1,63 @@
+use std::fs;
+
+use myust::{backup::ConflictPolicy, test_server::TestServer, Client};
+
+#[tokio::test]
+async fn backup_and_restore() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let kept = client
+        .create_paste(|p| p.filename("kept.txt").content("kept"))
+        .await
+        .unwrap();
+    let removed = client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("a.txt").content("first"));
+            p.file(|f| f.filename("b.txt").content("second"))
+        })
+        .await
+        .unwrap();
+    let locked = client
+        .create_paste(|p| p.filename("locked.txt").content("secret").password("pw"))
+        .await
+        .unwrap();
+    client.create_bookmark(&removed.id).await.unwrap();
+
+    let root = std::env::temp_dir().join(format!("myust-backup-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let backup = client.backup_account(&root).await.unwrap();
+    assert_eq!(backup.pastes, [kept.id.clone(), removed.id.clone()]);
+    assert_eq!(backup.bookmarks, [removed.id.as_str()]);
+    assert_eq!(backup.failed.len(), 1);
+    assert_eq!(backup.failed[0].0, locked.id);
+    assert!(root.join("manifest.json").exists());
+    assert_eq!(
+        fs::read_to_string(root.join(&removed.id).join("b.txt")).unwrap(),
+        "second"
+    );
+
+    client.delete_paste(&removed.id).await.unwrap();
+    let restored = client.restore_account(&root, |r| r).await.unwrap();
+    assert_eq!(restored.skipped, [kept.id.as_str()]);
+    assert_eq!(restored.ids[&kept.id], kept.id);
+    let new_id = &restored.ids[&removed.id];
+    assert_ne!(new_id, &removed.id);
+    assert_eq!(restored.bookmarks, [new_id.as_str()]);
+    assert!(restored.failed.is_empty());
+    let paste = client.get_paste(|p| p.id(new_id)).await.unwrap();
+    assert_eq!(paste.files.len(), 2);
+    assert_eq!(server.paste_count(), 3);
+
+    let replaced = client
+        .restore_account(&root, |r| r.conflicts(ConflictPolicy::Replace))
+        .await
+        .unwrap();
+    assert!(replaced.skipped.is_empty());
+    assert_ne!(replaced.ids[&kept.id], kept.id);
+    assert_eq!(server.paste_count(), 4);
+
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,26 @@
+use myust::{test_server::TestServer, Client, File};
+
+#[tokio::test]
+async fn binary_files() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let bytes: Vec<u8> = (0..=255).collect();
+    let file = File::from_bytes("all.bin", &bytes);
+    let paste = client
+        .create_paste(|p| p.filename(&file.filename).content(&file.content))
+        .await
+        .unwrap();
+
+    let fetched = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(fetched.files[0].filename, "all.bin.b64");
+    assert!(fetched.files[0].is_binary());
+    assert_eq!(fetched.files[0].decode_bytes().unwrap(), bytes);
+
+    let text = File {
+        filename: "notes.txt".to_string(),
+        content: "aGk=".to_string(),
+        ..Default::default()
+    };
+    assert!(!text.is_binary());
+    assert_eq!(text.decode_bytes(), None);
+}
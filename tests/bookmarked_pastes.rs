@@ -0,0 +1,46 @@
+use myust::{test_server::TestServer, Error};
+
+#[tokio::test]
+async fn bookmarked_pastes() {
+    let server = TestServer::start();
+    let client = myust::Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let mut ids = Vec::new();
+    for (filename, password) in [("a.txt", None), ("b.txt", Some("secret")), ("c.txt", None)] {
+        let paste = client
+            .create_paste(|p| {
+                p.filename(filename).content(filename);
+                match password {
+                    Some(password) => p.password(password),
+                    None => p,
+                }
+            })
+            .await
+            .unwrap();
+        client.create_bookmark(&paste.id).await.unwrap();
+        ids.push(paste.id);
+    }
+
+    let result = client.get_bookmarked_pastes(|o| o).await;
+    assert!(matches!(result, Err(Error::PasteRequiresPassword(_))));
+
+    let pastes = client
+        .get_bookmarked_pastes(|o| o.concurrency(2).skip_protected(true))
+        .await
+        .unwrap();
+    let bookmarks = client.get_user_bookmarks().await.unwrap();
+    let expected: Vec<_> = bookmarks
+        .iter()
+        .map(|bookmark| &bookmark.id)
+        .filter(|id| **id != ids[1])
+        .collect();
+    assert_eq!(
+        pastes.iter().map(|paste| &paste.id).collect::<Vec<_>>(),
+        expected
+    );
+    assert!(pastes
+        .iter()
+        .all(|paste| paste.files[0].content == paste.files[0].filename));
+}
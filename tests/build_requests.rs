@@ -0,0 +1,73 @@
+use myust::{
+    test_server::TestServer, Client, Error, Expiry, GetPasteBuilder, PasteBuilder, PasteId,
+    PastesBuilder, Url, ValidationError,
+};
+
+#[tokio::test]
+async fn build_requests() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+
+    let request = PasteBuilder::default()
+        .filename("notes.txt")
+        .content("reusable")
+        .password("pw")
+        .expires(Expiry::ONE_DAY)
+        .build()
+        .unwrap();
+    assert!(!format!("{request:?}").contains("pw"));
+    let first = client
+        .create_paste_from_request(request.clone())
+        .await
+        .unwrap();
+    let second = client.create_paste_from_request(request).await.unwrap();
+    assert_ne!(first.id, second.id);
+    assert_eq!(server.paste_count(), 2);
+
+    let get = GetPasteBuilder::default()
+        .id(first.id.as_str())
+        .password("pw")
+        .build()
+        .unwrap();
+    let paste = client.get_paste_from_request(&get).await.unwrap();
+    assert_eq!(paste.files[0].content, "reusable");
+    assert_eq!(
+        client.get_paste_from_request(&get).await.unwrap().id,
+        first.id
+    );
+
+    let mut pastes = PastesBuilder::default();
+    pastes
+        .file(|f| f.filename("a.txt").content("a"))
+        .file(|f| f.filename("b.txt").content("b"));
+    let request = pastes.build().unwrap();
+    assert_eq!(request.files.len(), 2);
+    assert_eq!(pastes.files.len(), 2);
+    let paste = client.create_paste_from_request(request).await.unwrap();
+    assert_eq!(paste.files[1].filename, "b.txt");
+}
+
+#[test]
+fn build_rejects_invalid_input() {
+    let err = PasteBuilder::default()
+        .filename("a.txt")
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, Error::Invalid(ValidationError::EmptyContent)));
+    let err = PasteBuilder::default()
+        .filename("a.txt")
+        .content_reader(tokio::io::empty())
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    let err = PastesBuilder::default().build().unwrap_err();
+    assert!(matches!(err, Error::Invalid(ValidationError::NoFiles)));
+    let err = GetPasteBuilder::default()
+        .id("not/an id")
+        .build()
+        .unwrap_err();
+    assert_eq!(err, PasteId::parse("not/an id").unwrap_err());
+    let url = Url::parse("https://example.com/").unwrap();
+    let err = GetPasteBuilder::default().id(&url).build().unwrap_err();
+    assert_eq!(err, ValidationError::InvalidPasteId(url.to_string()));
+}
@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures_util::StreamExt;
+use myust::{test_server::TestServer, Client, CreatePasteRequest, Error, File, PasteBuilder};
+
+#[tokio::test]
+async fn bulk_create() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let mut requests: Vec<CreatePasteRequest> = (0..20)
+        .map(|i| {
+            let mut paste = PasteBuilder::default();
+            paste.filename("myust.txt").content(format!("paste {i}"));
+            paste.into()
+        })
+        .collect();
+    requests.push(CreatePasteRequest {
+        files: vec![File {
+            filename: "empty.txt".to_string(),
+            content: String::new(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+
+    let progress = AtomicUsize::new(0);
+    let mut results: Vec<_> = client
+        .bulk_create(requests, |bulk| {
+            bulk.concurrency(5).progress(|done, total| {
+                assert_eq!(total, 21);
+                progress.store(done, Ordering::SeqCst);
+            })
+        })
+        .collect()
+        .await;
+    results.sort_by_key(|(index, _)| *index);
+
+    assert_eq!(progress.load(Ordering::SeqCst), 21);
+    assert_eq!(results.len(), 21);
+    for (index, result) in &results[..20] {
+        let paste = result.as_ref().unwrap();
+        assert_eq!(paste.files[0].content, format!("paste {index}"));
+    }
+    assert!(matches!(&results[20].1, Err(Error::Api(err)) if err.code == 422));
+    assert_eq!(server.paste_count(), 20);
+}
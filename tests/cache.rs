@@ -0,0 +1,70 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use myust::{test_server::TestServer, CacheConfig, Client, Error, MemoryCache, PasteCache};
+
+fn counting_client(server: &TestServer, requests: &Arc<Mutex<usize>>) -> Client {
+    let recorder = requests.clone();
+    Client::new()
+        .base_url(server.url())
+        .metrics(move |endpoint: &str, _, _| {
+            if endpoint == "GET /paste/{id}" {
+                *recorder.lock().unwrap() += 1;
+            }
+        })
+}
+
+#[tokio::test]
+async fn cache() {
+    let server = TestServer::start();
+    let requests = Arc::new(Mutex::new(0));
+    let client = counting_client(&server, &requests)
+        .auth(TestServer::TOKEN)
+        .await
+        .with_cache(CacheConfig::default());
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+
+    let first = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    let second = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(*requests.lock().unwrap(), 1);
+
+    client.delete_paste(&paste.id).await.unwrap();
+    let err = client.get_paste(|p| p.id(&paste.id)).await.unwrap_err();
+    assert!(matches!(err, Error::Api(err) if err.code == 404));
+    assert_eq!(*requests.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn cache_ttl_and_capacity() {
+    let server = TestServer::start();
+    let requests = Arc::new(Mutex::new(0));
+    let cache = Arc::new(MemoryCache::new(CacheConfig {
+        capacity: 1,
+        ttl: Duration::from_millis(100),
+    }));
+    let client = counting_client(&server, &requests).cache_store(cache.clone());
+    let a = client
+        .create_paste(|p| p.filename("a.txt").content("a"))
+        .await
+        .unwrap();
+    let b = client
+        .create_paste(|p| p.filename("b.txt").content("b"))
+        .await
+        .unwrap();
+
+    client.get_paste(|p| p.id(&a.id)).await.unwrap();
+    client.get_paste(|p| p.id(&b.id)).await.unwrap();
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get(&a.id, None).is_none());
+    assert!(cache.get(&b.id, None).is_some());
+
+    std::thread::sleep(Duration::from_millis(150));
+    client.get_paste(|p| p.id(&b.id)).await.unwrap();
+    assert_eq!(*requests.lock().unwrap(), 3);
+}
@@ -0,0 +1,60 @@
+use futures_util::StreamExt;
+use myust::{test_server::TestServer, CancellationToken, Client, CreatePasteRequest, Error};
+
+fn requests(count: usize) -> Vec<CreatePasteRequest> {
+    (0..count)
+        .map(|i| {
+            let mut paste = myust::PasteBuilder::default();
+            paste.filename("myust.txt").content(format!("paste {i}"));
+            paste.into()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn cancel_bulk_create() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let cancel = CancellationToken::new();
+    let results: Vec<_> = client
+        .bulk_create(requests(3), |bulk| {
+            bulk.concurrency(1)
+                .cancellation(cancel.clone())
+                .progress(|_, _| cancel.cancel())
+        })
+        .collect()
+        .await;
+    assert_eq!(results.len(), 3);
+    assert!(results[0].1.is_ok());
+    assert!(results[1..]
+        .iter()
+        .all(|(_, result)| matches!(result, Err(Error::Cancelled))));
+    assert_eq!(server.paste_count(), 1);
+}
+
+#[tokio::test]
+async fn cancel_pagination_and_backup() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    let cancel = CancellationToken::new();
+    assert_eq!(client.get_all_user_pastes(&cancel).await.unwrap().len(), 1);
+
+    cancel.cancel();
+    assert!(matches!(
+        client.get_all_user_pastes(&cancel).await,
+        Err(Error::Cancelled)
+    ));
+    let root = std::env::temp_dir().join(format!("myust-cancelled-{}", std::process::id()));
+    assert!(matches!(
+        client.backup_account_cancellable(&root, &cancel).await,
+        Err(Error::Cancelled)
+    ));
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,110 @@
+use std::{
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use myust::{report::CreateReport, test_server::TestServer, Client, MAX_FILE_CHARACTERS};
+
+fn myust(server: &TestServer, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_myust"))
+        .args(["--base-url", &server.url()])
+        .args(args)
+        .env_remove("MYSTBIN_TOKEN")
+        .env("MYUST_CONFIG", "/nonexistent/myust.toml")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn cli_create_and_get() {
+    let server = TestServer::start();
+    let path = std::env::temp_dir().join(format!("myust-cli-{}.txt", std::process::id()));
+    fs::write(&path, "Hello from the CLI!").unwrap();
+
+    let url = myust(
+        &server,
+        &["create", path.to_str().unwrap(), "--expires", "1d"],
+    );
+    assert!(url.starts_with("https://mystb.in/"));
+    assert_eq!(server.paste_count(), 1);
+
+    let content = myust(&server, &["get", url.trim()]);
+    assert_eq!(content, "Hello from the CLI!\n");
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn cli_create_from_stdin() {
+    let server = TestServer::start();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_myust"))
+        .args(["--base-url", &server.url(), "create", "-"])
+        .env_remove("MYSTBIN_TOKEN")
+        .env("MYUST_CONFIG", "/nonexistent/myust.toml")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let line = "a".repeat(99) + "\n";
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(line.repeat(MAX_FILE_CHARACTERS / 100 + 1).as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    let url = String::from_utf8(output.stdout).unwrap();
+    let id = url.trim().rsplit('/').next().unwrap();
+    let paste = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(Client::new().base_url(server.url()).get_paste(|p| p.id(id)))
+        .unwrap();
+    let filenames: Vec<_> = paste.files.iter().map(|f| f.filename.as_str()).collect();
+    assert_eq!(filenames, ["stdin-1.txt", "stdin-2.txt"]);
+    assert_eq!(paste.files[1].content, line);
+}
+
+#[test]
+fn cli_json_output() {
+    let server = TestServer::start();
+    let path = std::env::temp_dir().join(format!("myust-cli-json-{}.txt", std::process::id()));
+    fs::write(&path, "Hello from the CLI!").unwrap();
+
+    let output = myust(&server, &["--json", "create", path.to_str().unwrap()]);
+    let report: CreateReport = serde_json::from_str(&output).unwrap();
+    assert_eq!(report.url, format!("https://mystb.in/{}", report.id));
+    assert_eq!(report.files, [path.file_name().unwrap().to_str().unwrap()]);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn cli_failures() {
+    let server = TestServer::start();
+    let output = Command::new(env!("CARGO_BIN_EXE_myust"))
+        .args(["--base-url", &server.url(), "--token", "wrong", "list"])
+        .env("MYUST_CONFIG", "/nonexistent/myust.toml")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("error: "));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_myust"))
+        .args(["--base-url", &server.url(), "--token", TestServer::TOKEN])
+        .args(["delete", "MissingPaste"])
+        .env("MYUST_CONFIG", "/nonexistent/myust.toml")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "failed to delete MissingPaste\n"
+    );
+}
@@ -0,0 +1,65 @@
+use myust::{
+    collection::{Collections, COLLECTION_FILENAME},
+    test_server::TestServer,
+    Client,
+};
+
+#[tokio::test]
+async fn paste_collections() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let logs = client
+        .create_paste(|p| p.filename("app.log").content("panic at 03:12"))
+        .await
+        .unwrap();
+    let config = client
+        .create_paste(|p| p.filename("app.toml").content("workers = 0"))
+        .await
+        .unwrap();
+
+    let path = std::env::temp_dir()
+        .join(format!("myust-collections-{}", std::process::id()))
+        .join("collections.json");
+    let collections = Collections::open(&path).unwrap();
+    collections.create("incident-42").unwrap();
+    assert!(collections.create("incident-42").is_err());
+    assert!(collections.add("incident-42", &logs.id).unwrap());
+    assert!(collections.add("incident-42", &config.id).unwrap());
+    assert!(!collections.add("incident-42", &logs.id).unwrap());
+    assert!(collections.add("incident-43", &logs.id).is_err());
+
+    let first = client
+        .mirror_collection(&collections, "incident-42")
+        .await
+        .unwrap();
+    assert!(first.files[0].content.contains(&client.paste_url(&logs.id)));
+    assert_eq!(first.files[1].filename, COLLECTION_FILENAME);
+
+    assert!(collections.remove("incident-42", &config.id).unwrap());
+    let second = client
+        .mirror_collection(&collections, "incident-42")
+        .await
+        .unwrap();
+    assert!(client.get_paste(|p| p.id(&first.id)).await.is_err());
+
+    // collections survive a restart
+    let collections = Collections::open(&path).unwrap();
+    let stored = collections.get("incident-42").unwrap();
+    assert_eq!(stored.pastes, [logs.id.as_str()]);
+    assert_eq!(stored.index_paste.as_deref(), Some(second.id.as_str()));
+    assert_eq!(collections.list().len(), 1);
+
+    let shared = Client::new()
+        .base_url(server.url())
+        .get_collection(&second.id)
+        .await
+        .unwrap();
+    assert_eq!(shared, stored);
+
+    assert!(collections.delete("incident-42").unwrap().is_some());
+    assert!(collections.list().is_empty());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "compression")]
+
+use myust::{test_server::TestServer, Client, HttpOptions};
+
+#[tokio::test]
+async fn compression() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .http_options(HttpOptions {
+            decompress_responses: true,
+            compress_requests_over: Some(1024),
+            ..Default::default()
+        });
+    let content = "a long log line\n".repeat(1000);
+    let paste = client
+        .create_paste(|p| p.filename("build.log").content(&content))
+        .await
+        .unwrap();
+    // the server gzips every response to a client that accepts it
+    let fetched = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(fetched.files[0].content, content);
+    client
+        .create_paste(|p| p.filename("short.txt").content("short"))
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    let [create, get, short] = requests.as_slice() else {
+        panic!("expected 3 requests, got {:?}", requests);
+    };
+    assert_eq!(create.header("content-encoding"), Some("gzip"));
+    for request in [create, get, short] {
+        assert!(request.header("accept-encoding").unwrap().contains("gzip"));
+    }
+    // under the threshold
+    assert_eq!(short.header("content-encoding"), None);
+
+    // the body the client decoded was sent gzipped
+    let raw = reqwest::Client::builder()
+        .gzip(false)
+        .build()
+        .unwrap()
+        .get(format!("{}/paste/{}", server.url(), paste.id))
+        .header("Accept-Encoding", "gzip")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(raw.headers()["content-encoding"], "gzip");
+    assert_eq!(&raw.bytes().await.unwrap()[..2], [0x1f, 0x8b]);
+}
+
+#[tokio::test]
+async fn compression_disabled() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .http_options(HttpOptions {
+            decompress_responses: false,
+            ..Default::default()
+        });
+    let content = "a long log line\n".repeat(1000);
+    let paste = client
+        .create_paste(|p| p.filename("build.log").content(&content))
+        .await
+        .unwrap();
+    let fetched = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(fetched.files[0].content, content);
+    for request in server.requests() {
+        assert_eq!(request.header("content-encoding"), None);
+        assert_eq!(request.header("accept-encoding"), None);
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::{Arc, Mutex};
+
+use myust::{test_server::TestServer, Client, Error};
+
+#[tokio::test]
+async fn conditional_requests() {
+    let server = TestServer::start();
+    let statuses = Arc::new(Mutex::new(vec![]));
+    let recorder = statuses.clone();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await
+        .conditional_requests(true)
+        .metrics(move |_: &str, status: u16, _| recorder.lock().unwrap().push(status));
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+
+    let first = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    let second = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(second.files[0].content, "hi");
+
+    client.delete_paste(&paste.id).await.unwrap();
+    let err = client.get_paste(|p| p.id(&paste.id)).await.unwrap_err();
+    assert!(matches!(err, Error::Api(err) if err.code == 404));
+    assert_eq!(*statuses.lock().unwrap(), vec![201, 200, 304, 200, 404]);
+}
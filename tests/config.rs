@@ -0,0 +1,100 @@
+use std::{fs, time::Duration};
+
+use myust::{config::Config, test_server::TestServer, Client, PasteProvider};
+
+#[test]
+fn parse_config() {
+    let config: Config = r#"
+        token = "secret"
+        base_url = "https://mystbin.internal.example"
+        expires = "1day 12h"
+        provider = "gist"
+
+        [providers.gist]
+        token = "ghp_token"
+    "#
+    .parse()
+    .unwrap();
+
+    assert_eq!(config.token.as_deref(), Some("secret"));
+    assert_eq!(config.expires, Some(Duration::from_secs(36 * 60 * 60)));
+    assert_eq!(config.provider_setting("gist", "token"), Some("ghp_token"));
+    assert!(!format!("{config:?}").contains("secret"));
+    assert_eq!(config.paste_provider().unwrap().name(), "gist");
+
+    assert!("expires = \"soon\"".parse::<Config>().is_err());
+    assert!("unknown = 1".parse::<Config>().is_err());
+}
+
+#[tokio::test]
+async fn client_from_config() {
+    let server = TestServer::start();
+    let path = std::env::temp_dir().join(format!("myust-config-{}.toml", std::process::id()));
+    fs::write(
+        &path,
+        format!(
+            "token = \"{}\"\nbase_url = \"{}\"\nexpires = \"1h\"\n",
+            TestServer::TOKEN,
+            server.url()
+        ),
+    )
+    .unwrap();
+
+    std::env::set_var("MYUST_WEB_URL", "https://paste.internal.example");
+    let config = Config::load_from(&path).unwrap();
+    std::env::remove_var("MYUST_WEB_URL");
+    assert_eq!(
+        config.web_url.as_deref(),
+        Some("https://paste.internal.example")
+    );
+
+    let client = Client::from_config(&config);
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("configured"))
+        .await
+        .unwrap();
+    assert!(paste.expires.is_some());
+    assert_eq!(
+        client.paste_url(&paste.id),
+        format!("https://paste.internal.example/{}", paste.id)
+    );
+    // authenticated with the configured token
+    assert_eq!(client.get_user_pastes(|o| o).await.unwrap().len(), 1);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn config_profiles() {
+    let server = TestServer::start();
+    let config: Config = format!(
+        r#"
+        token = "personal"
+        expires = "1h"
+
+        [profiles.work]
+        token = "{}"
+        base_url = "{}"
+        "#,
+        TestServer::TOKEN,
+        server.url()
+    )
+    .parse()
+    .unwrap();
+    assert!(!format!("{config:?}").contains(TestServer::TOKEN));
+    assert!(config.profile("home").is_err());
+
+    let work = config.profile("work").unwrap();
+    assert_eq!(work.token.as_deref(), Some(TestServer::TOKEN));
+    assert_eq!(work.base_url.as_deref(), Some(server.url().as_str()));
+    assert_eq!(work.expires, Some(Duration::from_secs(60 * 60)));
+
+    let shared = Client::new();
+    let anonymous = shared.with_config(&Config {
+        base_url: Some(server.url()),
+        ..Default::default()
+    });
+    let work = shared.with_config(&work);
+    assert!(anonymous.get_user_pastes(|o| o).await.is_err());
+    assert!(work.get_user_pastes(|o| o).await.is_ok());
+}
@@ -0,0 +1,40 @@
+use myust::{test_server::TestServer, Client, Error, File, COMPRESSED_SUFFIX, MAX_FILE_CHARACTERS};
+
+#[tokio::test]
+async fn content_compression() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let log = "INFO request handled in 3ms\n".repeat(20_000);
+    assert!(log.len() > MAX_FILE_CHARACTERS);
+
+    let err = client
+        .create_paste(|p| p.filename("server.log").content(&log))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ContentTooLarge { .. }));
+
+    let paste = client
+        .create_paste(|p| p.filename("server.log").content(&log).compress(true))
+        .await
+        .unwrap();
+    assert_eq!(paste.files[0].filename, "server.log");
+    assert_eq!(paste.files[0].content, log);
+
+    let stored: serde_json::Value = reqwest::get(format!("{}/paste/{}", server.url(), paste.id))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let stored: Vec<File> = serde_json::from_value(stored["files"].clone()).unwrap();
+    assert_eq!(
+        stored[0].filename,
+        format!("server.log{}", COMPRESSED_SUFFIX)
+    );
+    assert!(stored[0].content.len() < MAX_FILE_CHARACTERS);
+    assert_eq!(stored[0].decompress().unwrap().content, log);
+
+    let fetched = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(fetched.files[0].filename, "server.log");
+    assert_eq!(fetched.files[0].content, log);
+}
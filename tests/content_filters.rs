@@ -0,0 +1,30 @@
+use myust::{test_server::TestServer, Client, ContentFilter};
+
+#[tokio::test]
+async fn content_filters() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("capture.txt")
+                    .content("$ ls\t-la  \r\n\r\n\r\n\r\nab\tc\t\n")
+                    .filter(ContentFilter::ExpandTabs(4))
+                    .filter(ContentFilter::TrimTrailingWhitespace)
+                    .filter(ContentFilter::CollapseBlankLines(1))
+            });
+            p.file(|f| f.filename("other.txt").content("one \n\n\n\ntwo"))
+        })
+        .await
+        .unwrap();
+    let fetched = client.get_paste(|p| p.id(paste.id.as_str())).await.unwrap();
+    assert_eq!(fetched.files[0].content, "$ ls    -la\r\n\r\nab  c\n");
+    assert_eq!(fetched.files[1].content, "one\n\ntwo");
+
+    assert_eq!(ContentFilter::ExpandTabs(0).apply("a\tb"), "ab");
+    assert_eq!(
+        ContentFilter::CollapseBlankLines(0).apply("a\n \n\t\nb\n"),
+        "a\nb\n"
+    );
+}
@@ -1,6 +1,9 @@
+use myust::test_server::TestServer;
+
 #[tokio::test]
 async fn create_pastes() {
-    let client = myust::Client::new();
+    let server = TestServer::start();
+    let client = myust::Client::new().base_url(server.url());
     let paste = client
         .create_multifile_paste(|p| {
             p.file(|f| {
@@ -13,5 +16,28 @@ async fn create_pastes() {
         .await
         .unwrap();
     let url = format!("https://mystb.in/{}", paste.id);
-    println!("Result: {}", url)
+    println!("Result: {}", url);
+    assert_eq!(paste.files.len(), 2);
+    assert_eq!(server.paste_count(), 1);
+}
+
+#[tokio::test]
+async fn create_paste_from_file() {
+    use myust::{Error, PasteBuilder, PasteProvider};
+
+    let dir = std::env::temp_dir().join(format!("myust-from-file-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("crash.log");
+    std::fs::write(&path, "thread 'main' panicked").unwrap();
+
+    let server = TestServer::start();
+    let client = myust::Client::new().base_url(server.url());
+    let paste = PasteBuilder::from_file(&path).unwrap();
+    let result = client.create(paste.into()).await.unwrap();
+    assert_eq!(result.files[0].filename, "crash.log");
+    assert_eq!(result.files[0].content, "thread 'main' panicked");
+
+    let missing = PasteBuilder::from_file(dir.join("missing.log"));
+    assert!(matches!(missing, Err(Error::Io(_))));
+    std::fs::remove_dir_all(&dir).unwrap();
 }
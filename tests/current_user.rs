@@ -0,0 +1,20 @@
+use myust::test_server::TestServer;
+
+#[tokio::test]
+async fn current_user() {
+    let server = TestServer::start();
+    let client = myust::Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let user = client.current_user().await.unwrap();
+    assert_eq!(user.id, 1);
+    assert_eq!(user.username, "myust");
+    assert!(!user.subscriber);
+    assert_eq!(user.theme.as_deref(), Some("dark"));
+    assert_eq!(user.settings["show_line_numbers"], true);
+
+    let anonymous = myust::Client::new().base_url(server.url());
+    let err = anonymous.current_user().await.unwrap_err();
+    assert!(err.is_auth_error());
+}
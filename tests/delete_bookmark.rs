@@ -1,9 +1,19 @@
+use myust::test_server::TestServer;
+
 #[tokio::test]
 async fn delete_bookmark() {
+    let server = TestServer::start();
     let client = myust::Client::new()
-        .auth(std::env::var("MYSTBIN_TOKEN").unwrap())
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
         .await;
-    client.delete_bookmark("InfraredYukonEmpty").await.unwrap();
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("bookmark me"))
+        .await
+        .unwrap();
+    client.create_bookmark(&paste.id).await.unwrap();
+    client.delete_bookmark(&paste.id).await.unwrap();
     let bm = client.get_user_bookmarks().await.unwrap();
-    println!("{bm:#?}")
+    println!("{bm:#?}");
+    assert!(bm.is_empty());
 }
@@ -0,0 +1,27 @@
+use myust::{test_server::TestServer, Client};
+
+#[tokio::test]
+async fn delete_pastes() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let mut ids = vec![];
+    for i in 0..250 {
+        let paste = client
+            .create_paste(|p| p.filename("myust.txt").content(format!("paste {i}")))
+            .await
+            .unwrap();
+        ids.push(paste.id);
+    }
+    ids.push("MissingPaste".to_string());
+
+    let result = client
+        .delete_pastes(ids.iter().map(|id| id.as_str()).collect())
+        .await
+        .unwrap();
+    assert_eq!(result.succeeded.unwrap().len(), 250);
+    assert_eq!(result.failed.unwrap(), vec!["MissingPaste"]);
+    assert_eq!(server.paste_count(), 0);
+}
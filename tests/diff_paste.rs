@@ -0,0 +1,67 @@
+use std::fs;
+
+use myust::{
+    diff::{DiffTarget, FileStatus},
+    test_server::TestServer,
+    Client,
+};
+
+#[tokio::test]
+async fn diff_pastes() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let shared = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("main.rs")
+                    .content("fn main() {\n    run();\n}\n")
+            });
+            p.file(|f| f.filename("old.rs").content("// gone\n"))
+        })
+        .await
+        .unwrap();
+    let updated = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("main.rs")
+                    .content("fn main() {\n    run().unwrap();\n}\n")
+            });
+            p.file(|f| f.filename("new.rs").content("// added\n"))
+        })
+        .await
+        .unwrap();
+
+    let diff = client
+        .diff_paste(&shared.id, DiffTarget::paste(&updated.id))
+        .await
+        .unwrap();
+    let statuses: Vec<_> = diff.files.iter().map(|f| f.status).collect();
+    assert_eq!(
+        statuses,
+        [FileStatus::Modified, FileStatus::Removed, FileStatus::Added]
+    );
+    assert_eq!((diff.insertions(), diff.deletions()), (2, 2));
+    let printed = diff.to_string();
+    assert!(printed.contains(&format!("--- {}/main.rs", shared.id)));
+    assert!(printed.contains("-    run();\n+    run().unwrap();"));
+    assert!(printed.contains("+++ /dev/null"));
+
+    let dir = std::env::temp_dir().join(format!("myust-diff-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("main.rs");
+    fs::write(&path, "fn main() {\n    run();\n}\n").unwrap();
+    let diff = client
+        .diff_paste(&shared.id, DiffTarget::path(&path))
+        .await
+        .unwrap();
+    assert!(diff.is_unchanged());
+    assert!(diff.to_string().is_empty());
+
+    let missing = dir.join("lib.rs");
+    fs::write(&missing, "").unwrap();
+    assert!(client
+        .diff_paste(&shared.id, DiffTarget::path(&missing))
+        .await
+        .is_err());
+    fs::remove_dir_all(dir).unwrap();
+}
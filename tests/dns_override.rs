@@ -0,0 +1,27 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use myust::{test_server::TestServer, Client, HttpOptions};
+
+#[tokio::test]
+async fn dns_override() {
+    let server = TestServer::start();
+    let port = server.url().rsplit(':').next().unwrap().to_string();
+    let client = Client::new()
+        .http_options(HttpOptions {
+            resolve: [(
+                "mystbin.test".to_string(),
+                vec![IpAddr::V4(Ipv4Addr::LOCALHOST)],
+            )]
+            .into(),
+            ..Default::default()
+        })
+        .base_url(format!("http://mystbin.test:{}", port));
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    assert_eq!(server.paste_count(), 1);
+    assert_eq!(client.last_url().unwrap().host_str(), Some("mystbin.test"));
+    let fetched = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(fetched.files[0].content, "hi");
+}
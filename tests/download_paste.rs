@@ -0,0 +1,44 @@
+use myust::{test_server::TestServer, Client, Error};
+
+#[tokio::test]
+async fn download_paste() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let content = "line\n".repeat(50_000);
+    let paste = client
+        .create_paste(|p| p.filename("big.txt").content(&content).password("myust"))
+        .await
+        .unwrap();
+
+    let mut downloaded = vec![];
+    let written = client
+        .download_paste(|p| p.id(&paste.id).password("myust"), &mut downloaded)
+        .await
+        .unwrap();
+    assert_eq!(written, downloaded.len() as u64);
+    let body: serde_json::Value = serde_json::from_slice(&downloaded).unwrap();
+    assert_eq!(body["files"][0]["content"], content);
+
+    let err = client
+        .download_paste(|p| p.id(&paste.id), &mut vec![])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Api(err) if err.code == 401));
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn download_paste_sync() {
+    let server = TestServer::start();
+    let client = myust::SyncClient::new().base_url(server.url());
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .unwrap();
+
+    let mut downloaded = vec![];
+    client
+        .download_paste(|p| p.id(&paste.id), &mut downloaded)
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&downloaded).unwrap();
+    assert_eq!(body["files"][0]["content"], "hi");
+}
@@ -0,0 +1,65 @@
+use myust::{
+    test_server::TestServer, Client, DuplicateFilenames, Error, PastesBuilder, ValidationError,
+};
+
+#[tokio::test]
+async fn duplicate_filenames() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("foo.txt")
+                    .content("one")
+                    .duplicate_filenames(DuplicateFilenames::Rename)
+            });
+            p.file(|f| f.filename("foo.txt").content("two"));
+            p.file(|f| f.filename("foo (1).txt").content("three"));
+            p.file(|f| f.filename("foo.txt").content("four"))
+        })
+        .await
+        .unwrap();
+    let names: Vec<_> = paste.files.iter().map(|f| f.filename.as_str()).collect();
+    assert_eq!(
+        names,
+        ["foo.txt", "foo (2).txt", "foo (1).txt", "foo (3).txt"]
+    );
+
+    let mut pastes = PastesBuilder::default();
+    pastes
+        .file(|f| {
+            f.filename("foo.txt")
+                .content("one")
+                .duplicate_filenames(DuplicateFilenames::Reject)
+        })
+        .file(|f| f.filename("foo.txt").content("two"));
+    assert!(matches!(
+        pastes.validate(),
+        Err(ValidationError::DuplicateFilename(name)) if name == "foo.txt"
+    ));
+    let err = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("foo")
+                    .content("one")
+                    .duplicate_filenames(DuplicateFilenames::Reject)
+            });
+            p.file(|f| f.filename("foo").content("two"))
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Invalid(ValidationError::DuplicateFilename(name)) if name == "foo"
+    ));
+
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("foo.txt").content("one"));
+            p.file(|f| f.filename("foo.txt").content("two"))
+        })
+        .await
+        .unwrap();
+    assert_eq!(paste.files[1].filename, "foo.txt");
+}
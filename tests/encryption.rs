@@ -0,0 +1,51 @@
+use myust::{
+    encryption::{x25519, Encryption, HEADER},
+    test_server::TestServer,
+    Client, Error,
+};
+
+#[tokio::test]
+async fn encryption() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .encryption(Encryption::passphrase("correct horse").work_factor(4));
+    let paste = client
+        .create_paste(|p| p.filename("secret.txt").content("hunter2"))
+        .await
+        .unwrap();
+    assert_eq!(paste.files[0].content, "hunter2");
+
+    let plain = Client::new().base_url(server.url());
+    let stored = plain.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert!(stored.files[0].content.starts_with(HEADER));
+    assert!(Encryption::is_encrypted(&stored.files[0].content));
+    assert!(!stored.files[0].content.contains("hunter2"));
+
+    let fetched = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(fetched.files[0].content, "hunter2");
+
+    let wrong = Client::new()
+        .base_url(server.url())
+        .encryption(Encryption::passphrase("battery staple").work_factor(4));
+    let err = wrong.get_paste(|p| p.id(&paste.id)).await.unwrap_err();
+    assert!(matches!(err, Error::Encryption(_)));
+}
+
+#[tokio::test]
+async fn encryption_x25519() {
+    let server = TestServer::start();
+    let identity = x25519::Identity::generate();
+    let client = Client::new()
+        .base_url(server.url())
+        .encryption(Encryption::x25519(identity));
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("a.txt").content("first"))
+                .file(|f| f.filename("b.txt").content("second"))
+        })
+        .await
+        .unwrap();
+    let fetched = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(fetched.files, paste.files);
+}
@@ -0,0 +1,44 @@
+use myust::{test_server::TestServer, Client, Error, MystbinError, StatusCode, ValidationError};
+
+fn api_error(code: u16) -> Error {
+    MystbinError {
+        code: StatusCode::from_u16(code).unwrap(),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[tokio::test]
+async fn error_classification() {
+    for code in [408, 429, 500, 503] {
+        assert!(api_error(code).is_retryable(), "{code}");
+    }
+    for code in [400, 401, 404, 422] {
+        assert!(!api_error(code).is_retryable(), "{code}");
+        assert!(api_error(code).is_client_error(), "{code}");
+    }
+    assert!(api_error(401).is_auth_error());
+    assert!(api_error(403).is_auth_error());
+    assert!(!api_error(404).is_auth_error());
+    assert!(!api_error(502).is_client_error());
+
+    let provider = Error::Provider {
+        provider: "gist",
+        code: StatusCode::SERVICE_UNAVAILABLE,
+        message: None,
+    };
+    assert!(provider.is_retryable());
+    assert!(Error::from(ValidationError::EmptyContent).is_client_error());
+    assert!(!Error::Unsigned.is_retryable());
+
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let err = client
+        .get_paste(|p| p.id("MissingPaste"))
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found());
+    assert!(!err.is_retryable());
+    let err = client.get_user_pastes(|p| p).await.unwrap_err();
+    assert!(err.is_auth_error());
+}
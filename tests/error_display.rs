@@ -0,0 +1,39 @@
+use std::{error::Error as _, time::Duration};
+
+use myust::{test_server::TestServer, Client, Error, MystbinError, StatusCode, ValidationError};
+use serde_json::json;
+
+#[tokio::test]
+async fn error_display() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let err = client
+        .get_paste(|p| p.id("MissingPaste"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().starts_with("paste not found"));
+    assert!(err.source().is_none());
+
+    let rate_limited = MystbinError {
+        code: StatusCode::TOO_MANY_REQUESTS,
+        retry_after: Some(Duration::from_secs(12)),
+        ..Default::default()
+    };
+    assert_eq!(rate_limited.to_string(), "rate limited, retry in 12s");
+
+    let rejected = MystbinError {
+        code: StatusCode::UNPROCESSABLE_ENTITY,
+        detail: Some(json!([{ "loc": ["body", "files"], "msg": "field required" }])),
+        ..Default::default()
+    };
+    assert_eq!(
+        Error::from(rejected).to_string(),
+        "the API rejected the request; body.files: field required"
+    );
+
+    let err = Error::from(ValidationError::EmptyContent);
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "content can not be empty"
+    );
+}
@@ -0,0 +1,63 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+};
+
+use myust::{test_server::TestServer, Client, Error, ERROR_BODY_LIMIT};
+
+/// Serve one response with an HTML error page, like a proxy in front of the API.
+fn serve_error_page(page: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        write!(
+            stream,
+            "HTTP/1.1 502 Bad Gateway\r\nContent-Type: text/html\r\nServer: nginx\r\nSet-Cookie: session=secret\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            page.len(),
+            page
+        )
+        .unwrap();
+    });
+    url
+}
+
+#[tokio::test]
+async fn error_response() {
+    let url = serve_error_page("<html>502 Bad Gateway</html>".to_string());
+    let client = Client::new().base_url(url);
+    let err = match client.get_paste(|p| p.id("EquipmentMovingExpensive")).await {
+        Err(Error::Api(err)) => err,
+        result => panic!("unexpected result: {result:?}"),
+    };
+    assert_eq!(err.code, 502);
+    let response = err.response.unwrap();
+    assert_eq!(response.body, "<html>502 Bad Gateway</html>");
+    assert!(!response.truncated);
+    assert_eq!(response.headers["server"], "nginx");
+    assert!(response.headers.get("set-cookie").is_none());
+
+    let url = serve_error_page("x".repeat(ERROR_BODY_LIMIT * 2));
+    let client = Client::new().base_url(url);
+    let err = match client.get_paste(|p| p.id("EquipmentMovingExpensive")).await {
+        Err(Error::Api(err)) => err,
+        result => panic!("unexpected result: {result:?}"),
+    };
+    let response = err.response.unwrap();
+    assert_eq!(response.body.len(), ERROR_BODY_LIMIT);
+    assert!(response.truncated);
+
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let err = match client.get_paste(|p| p.id("MissingPaste")).await {
+        Err(Error::Api(err)) => err,
+        result => panic!("unexpected result: {result:?}"),
+    };
+    assert_eq!(err.response.unwrap().body, r#"{"error":"Paste not found"}"#);
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use myust::{test_server::TestServer, Client, Expiry};
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+#[tokio::test]
+async fn expiring_pastes() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let in_an_hour = |hours| Expiry {
+        hours,
+        ..Default::default()
+    };
+    let soon = client
+        .create_paste(|p| {
+            p.filename("soon.txt")
+                .content("soon")
+                .expires(in_an_hour(1))
+        })
+        .await
+        .unwrap();
+    let later = client
+        .create_paste(|p| {
+            p.filename("later.txt")
+                .content("later")
+                .expires(in_an_hour(5))
+        })
+        .await
+        .unwrap();
+    client
+        .create_paste(|p| p.filename("kept.txt").content("kept"))
+        .await
+        .unwrap();
+
+    let expiring = client.expiring_within(2 * HOUR).await.unwrap();
+    assert_eq!(expiring.len(), 1);
+    assert_eq!(expiring[0].id, soon.id);
+    let expiring = client.expiring_within(6 * HOUR).await.unwrap();
+    assert_eq!(expiring[1].id, later.id);
+    // a window past the end of time has no upper bound
+    let expiring = client.expiring_within(Duration::MAX).await.unwrap();
+    assert_eq!(expiring.len(), 2);
+
+    let mut reminders = Box::pin(client.expiry_reminders(2 * HOUR, Duration::from_millis(10)));
+    let first = reminders.next().await.unwrap().unwrap();
+    assert_eq!(first.id, soon.id);
+    let quick = client
+        .create_paste(|p| {
+            p.filename("quick.txt")
+                .content("quick")
+                .expires(in_an_hour(1))
+        })
+        .await
+        .unwrap();
+    let next = tokio::time::timeout(Duration::from_secs(5), reminders.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(next.id, quick.id);
+}
@@ -0,0 +1,146 @@
+use std::time::{Duration, SystemTime};
+
+use myust::{test_server::TestServer, Client, Error, Expiry, ValidationError};
+
+#[test]
+fn expiry_from_duration() {
+    let duration = Duration::from_secs(((2 * 24 + 5) * 60 + 30) * 60 + 15);
+    assert_eq!(
+        Expiry::from(duration + Duration::from_millis(900)),
+        Expiry {
+            days: 2,
+            hours: 5,
+            minutes: 30,
+            seconds: 15,
+            never: false,
+        }
+    );
+    assert_eq!(Expiry::from(Duration::MAX).days, i32::MAX);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn expiry_from_chrono_duration() {
+    let expiry = Expiry::try_from(chrono::Duration::minutes(90)).unwrap();
+    assert_eq!(
+        expiry,
+        Expiry {
+            hours: 1,
+            minutes: 30,
+            ..Default::default()
+        }
+    );
+    assert!(matches!(
+        Expiry::try_from(chrono::Duration::seconds(-5)),
+        Err(ValidationError::NegativeExpiry { value: -5, .. })
+    ));
+}
+
+#[test]
+fn expiry_from_rfc3339() {
+    let in_a_day = SystemTime::now() + Duration::from_secs(24 * 60 * 60 + 30);
+    let timestamp = humantime::format_rfc3339_seconds(in_a_day).to_string();
+    let expiry = Expiry::try_from_rfc3339(&timestamp).unwrap();
+    assert_eq!((expiry.days, expiry.hours, expiry.minutes), (1, 0, 0));
+    let offset = timestamp.replace('Z', "+00:00");
+    assert_eq!(Expiry::try_from_rfc3339(&offset).unwrap().days, 1);
+
+    for timestamp in [
+        "2001-01-01T00:00:00Z",
+        "tomorrow",
+        "2999-01-01T00:00:00+02:00",
+    ] {
+        assert_eq!(
+            Expiry::try_from_rfc3339(timestamp),
+            Err(ValidationError::InvalidExpiry(timestamp.to_string()))
+        );
+    }
+}
+
+#[test]
+fn expiry_presets() {
+    assert_eq!(Expiry::ONE_HOUR, Expiry::from(Duration::from_secs(60 * 60)));
+    assert_eq!(Expiry::ONE_DAY.days, 1);
+    assert_eq!(Expiry::ONE_WEEK.days, 7);
+    assert!(Expiry::never().is_never() && !Expiry::ONE_DAY.is_never());
+}
+
+#[tokio::test]
+async fn never_overrides_default_expiry() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .default_expiry(Expiry::ONE_DAY);
+    let expiring = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    assert!(expiring.expires.is_some());
+    let kept = client
+        .create_paste(|p| {
+            p.filename("myust.txt")
+                .content("hi")
+                .expires(Expiry::never())
+        })
+        .await
+        .unwrap();
+    assert_eq!(kept.expires, None);
+
+    let err = client
+        .create_paste(|p| {
+            p.filename("myust.txt")
+                .content("hi")
+                .expires(Expiry::default())
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Invalid(ValidationError::ZeroExpiry)));
+}
+
+#[test]
+fn expiry_display_and_parse() {
+    let expiry = Expiry {
+        hours: 23,
+        minutes: 90,
+        seconds: 61,
+        ..Default::default()
+    };
+    assert_eq!(expiry.to_string(), "23h 90m 61s");
+    let normalized = expiry.normalized();
+    assert_eq!(normalized.to_string(), "1d 31m 1s");
+    assert_eq!(normalized.days, 1);
+    assert_eq!(Expiry::parse("1d 31m 1s").unwrap(), normalized);
+    assert_eq!(Expiry::parse("23h 90m 61s").unwrap(), normalized);
+
+    assert_eq!(Expiry::parse("2d 4h").unwrap().to_string(), "2d 4h");
+    assert_eq!("1week".parse::<Expiry>().unwrap(), Expiry::ONE_WEEK);
+    assert_eq!(Expiry::parse("never").unwrap(), Expiry::never());
+    assert_eq!(Expiry::never().to_string(), "never");
+    assert_eq!(Expiry::default().to_string(), "0s");
+    assert_eq!(
+        Expiry::parse("soon"),
+        Err(ValidationError::InvalidExpiry("soon".to_string()))
+    );
+}
+
+#[test]
+fn longest_expiry() {
+    let longest = Expiry::from(Expiry::MAX_PERIOD);
+    assert_eq!(longest.validate(), Ok(()));
+    let too_long = Expiry::from(Expiry::MAX_PERIOD + Duration::from_secs(1));
+    assert_eq!(
+        too_long.validate(),
+        Err(ValidationError::InvalidExpiry(too_long.to_string()))
+    );
+    let overflowing = Expiry {
+        days: i32::MAX,
+        hours: i32::MAX,
+        minutes: i32::MAX,
+        seconds: i32::MAX,
+        ..Default::default()
+    };
+    assert!(matches!(
+        overflowing.validate(),
+        Err(ValidationError::InvalidExpiry(_))
+    ));
+}
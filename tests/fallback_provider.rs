@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use myust::{
+    test_server::TestServer, Client, CreatePasteRequest, Error, FallbackProvider, File,
+    PasteProvider, PasteResult, StatusCode,
+};
+
+/// A provider always failing with `code`.
+struct Failing(StatusCode);
+
+#[async_trait]
+impl PasteProvider for Failing {
+    fn name(&self) -> &str {
+        "failing"
+    }
+
+    async fn create(&self, _request: CreatePasteRequest) -> Result<PasteResult, Error> {
+        Err(self.error())
+    }
+
+    async fn get(&self, _id: &str, _password: Option<&str>) -> Result<PasteResult, Error> {
+        Err(self.error())
+    }
+
+    async fn delete(&self, _id: &str) -> Result<(), Error> {
+        Err(self.error())
+    }
+
+    fn url_for(&self, id: &str) -> String {
+        format!("https://failing.example/{}", id)
+    }
+}
+
+impl Failing {
+    fn error(&self) -> Error {
+        Error::Provider {
+            provider: "failing",
+            code: self.0,
+            message: None,
+        }
+    }
+}
+
+fn request() -> CreatePasteRequest {
+    CreatePasteRequest {
+        files: vec![File {
+            filename: "panic.log".to_string(),
+            content: "thread 'main' panicked".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn fallback_provider() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let fallback = FallbackProvider::new()
+        .provider(Failing(StatusCode::SERVICE_UNAVAILABLE))
+        .provider(client.web_url("https://backup.example"));
+
+    let served = fallback.create_paste(request()).await.unwrap();
+    assert_eq!(served.provider, "mystbin");
+    assert_eq!(
+        served.url,
+        format!("https://backup.example/{}", served.paste.id)
+    );
+    assert_eq!(fallback.served_by(&served.paste.id), Some("mystbin"));
+    assert_eq!(fallback.url_for(&served.paste.id), served.url);
+
+    let fetched = fallback.get(&served.paste.id, None).await.unwrap();
+    assert_eq!(fetched.files[0].content, "thread 'main' panicked");
+    fallback.delete(&served.paste.id).await.unwrap();
+    assert_eq!(server.paste_count(), 0);
+
+    let rejecting = FallbackProvider::new()
+        .provider(Failing(StatusCode::BAD_REQUEST))
+        .provider(Client::new().base_url(server.url()));
+    let err = rejecting.create(request()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Provider {
+            code: StatusCode::BAD_REQUEST,
+            ..
+        }
+    ));
+    assert_eq!(server.paste_count(), 0);
+
+    let err = FallbackProvider::new().create(request()).await.unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+}
+
+#[tokio::test]
+async fn fall_back_on_unreachable_client() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let closed = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let server = TestServer::start();
+    let fallback = FallbackProvider::new()
+        .provider(Client::new().base_url(closed))
+        .provider(Client::new().base_url(server.url()));
+    let served = fallback.create_paste(request()).await.unwrap();
+    assert_eq!(served.provider, "mystbin");
+    assert_eq!(server.paste_count(), 1);
+}
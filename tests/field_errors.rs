@@ -0,0 +1,54 @@
+use myust::{
+    test_server::TestServer, Client, CreatePasteRequest, Error, FieldError, File, PasteProvider,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn field_errors() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let request = CreatePasteRequest {
+        files: vec![
+            File {
+                filename: "myust.txt".to_string(),
+                content: "hi".to_string(),
+                ..Default::default()
+            },
+            File {
+                filename: "empty.txt".to_string(),
+                content: String::new(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let err = match client.create(request).await {
+        Err(Error::Api(err)) => err,
+        other => panic!("expected an API error, got {:?}", other),
+    };
+    assert_eq!(err.code, 422);
+    let errors = err.field_errors();
+    assert_eq!(
+        errors,
+        vec![FieldError {
+            location: vec![
+                "body".to_string(),
+                "files".to_string(),
+                "1".to_string(),
+                "content".to_string()
+            ],
+            field: Some("content".to_string()),
+            message: "content can not be empty".to_string(),
+        }]
+    );
+    assert_eq!(errors[0].file_index(), Some(1));
+    assert_eq!(
+        errors[0].to_string(),
+        "body.files.1.content: content can not be empty"
+    );
+
+    let errors = FieldError::parse(&json!([{ "loc": ["body", "files"], "msg": "field required" }]));
+    assert_eq!(errors[0].field.as_deref(), Some("files"));
+    assert_eq!(errors[0].file_index(), None);
+    assert!(FieldError::parse(&json!({ "msg": "not a list" })).is_empty());
+}
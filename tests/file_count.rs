@@ -0,0 +1,33 @@
+use futures_util::StreamExt;
+use myust::{
+    test_server::TestServer, Client, CreatePasteRequest, Error, PasteProvider, PastesBuilder,
+    MAX_FILES,
+};
+
+#[tokio::test]
+async fn file_count() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+
+    let mut pastes = PastesBuilder::default();
+    for n in 0..MAX_FILES + 2 {
+        pastes.file(|f| f.filename(format!("{}.txt", n)).content("content"));
+    }
+    pastes.files[0].idempotency_key("logs");
+    let request = CreatePasteRequest::from(&mut pastes);
+    let err = client.create(request.clone()).await.unwrap_err();
+    assert!(
+        matches!(err, Error::TooManyFiles { count, max } if count == MAX_FILES + 2 && max == MAX_FILES)
+    );
+    assert!(err.to_string().contains("split them across several pastes"));
+    assert_eq!(server.paste_count(), 0);
+
+    let parts = request.split_files();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].files.len(), MAX_FILES);
+    assert_eq!(parts[1].files[1].filename, format!("{}.txt", MAX_FILES + 1));
+    assert_eq!(parts[1].idempotency_key.as_deref(), Some("logs-2"));
+    let created: Vec<_> = client.bulk_create(parts, |bulk| bulk).collect().await;
+    assert!(created.iter().all(|(_, result)| result.is_ok()));
+    assert_eq!(server.paste_count(), 2);
+}
@@ -0,0 +1,42 @@
+use myust::{test_server::TestServer, Client, Error};
+
+#[tokio::test]
+async fn get_paste_selection() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let source: String = (1..=200).map(|n| format!("line {}\n", n)).collect();
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("README.md").content("# myust"))
+                .file(|f| f.filename("main.rs").content(&source))
+        })
+        .await
+        .unwrap();
+
+    let selected = client
+        .get_paste(|p| p.id(&paste.id).file("main.rs").lines(100..103))
+        .await
+        .unwrap();
+    assert_eq!(selected.files.len(), 1);
+    assert_eq!(selected.files[0].filename, "main.rs");
+    assert_eq!(selected.files[0].content, "line 101\nline 102\nline 103\n");
+
+    let tail = client
+        .get_paste(|p| p.id(&paste.id).file("main.rs").lines(199..))
+        .await
+        .unwrap();
+    assert_eq!(tail.files[0].content, "line 200\n");
+
+    let past_end = client
+        .get_paste(|p| p.id(&paste.id).lines(1000..=2000))
+        .await
+        .unwrap();
+    assert_eq!(past_end.files.len(), 2);
+    assert!(past_end.files.iter().all(|file| file.content.is_empty()));
+
+    let err = client
+        .get_paste(|p| p.id(&paste.id).file("lib.rs"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::FileNotFound(name) if name == "lib.rs"));
+}
@@ -0,0 +1,222 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+use myust::{
+    gist::{GistProvider, DESCRIPTION_FILENAME},
+    test_server::TestServer,
+    Client, CreatePasteRequest, Error, File, PasteProvider, StatusCode,
+};
+use serde_json::{json, Value};
+
+/// Answer one request per response, in order, returning each request line,
+/// headers and body.
+fn serve(responses: Vec<(&'static str, Value)>) -> (String, thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut requests = vec![];
+        for (status, response) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let response = response.to_string();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+            requests.push(head + &String::from_utf8(body).unwrap());
+        }
+        requests
+    });
+    (url, handle)
+}
+
+/// Answer one request with `response`, returning the request line, headers and body.
+fn serve_once(response: Value, status: &'static str) -> (String, thread::JoinHandle<String>) {
+    let (url, handle) = serve(vec![(status, response)]);
+    (url, thread::spawn(move || handle.join().unwrap().remove(0)))
+}
+
+fn gist_json() -> Value {
+    json!({
+        "id": "aa5a315d61ae9438b18d",
+        "created_at": "2023-05-01T12:00:00Z",
+        "files": {
+            "main.rs": { "filename": "main.rs", "content": "fn main() {}" },
+            "README.md": { "filename": "README.md", "content": "# myust" },
+        },
+    })
+}
+
+#[tokio::test]
+async fn gist_create() {
+    let (url, server) = serve_once(gist_json(), "201 Created");
+    let gist = GistProvider::new().token("ghp_secret").api_url(url);
+    let paste = gist
+        .create(CreatePasteRequest {
+            files: vec![
+                File {
+                    filename: "main.rs".to_string(),
+                    content: "fn main() {}".to_string(),
+                    ..Default::default()
+                },
+                File {
+                    filename: "README.md".to_string(),
+                    content: "# myust".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(paste.id, "aa5a315d61ae9438b18d");
+    assert_eq!(paste.files.len(), 2);
+    assert_eq!(
+        gist.url_for(&paste.id),
+        "https://gist.github.com/aa5a315d61ae9438b18d"
+    );
+
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /gists HTTP/1.1"));
+    assert!(request.contains("authorization: Bearer ghp_secret"));
+    let body: Value = serde_json::from_str(&request[request.find('{').unwrap()..]).unwrap();
+    assert_eq!(body["description"], "main.rs, README.md");
+    assert_eq!(body["public"], false);
+    assert_eq!(body["files"]["README.md"]["content"], "# myust");
+}
+
+#[tokio::test]
+async fn gist_errors() {
+    let (url, server) = serve_once(json!({ "message": "Not Found" }), "404 Not Found");
+    let gist = GistProvider::new().api_url(url);
+    let err = gist.get("missing", None).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Provider { provider: "gist", code: StatusCode::NOT_FOUND, message: Some(ref m) } if m == "Not Found"
+    ));
+    assert!(server.join().unwrap().starts_with("GET /gists/missing"));
+
+    let err = gist
+        .create(CreatePasteRequest {
+            files: vec![File::default()],
+            password: Some("hunter2".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    assert!(!format!("{:?}", GistProvider::new().token("ghp_secret")).contains("ghp_secret"));
+}
+
+#[tokio::test]
+async fn migrate_from_gist() {
+    let mut described = gist_json();
+    described["description"] = json!("A tiny Rust program");
+    let mut other = gist_json();
+    other["id"] = json!("bb5a315d61ae9438b18d");
+    let (url, github) = serve(vec![
+        (
+            "200 OK",
+            json!([{ "id": "aa5a315d61ae9438b18d" }, { "id": "bb5a315d61ae9438b18d" }]),
+        ),
+        ("200 OK", described),
+        ("200 OK", other),
+        ("200 OK", gist_json()),
+    ]);
+    let gists = GistProvider::new().api_url(url);
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+
+    let migrated = client.migrate_from_gist(&gists, "octocat").await.unwrap();
+    assert!(migrated.failed.is_empty());
+    assert_eq!(migrated.succeeded.len(), 2);
+    assert_eq!(
+        migrated.succeeded[0].gist_url,
+        "https://gist.github.com/aa5a315d61ae9438b18d"
+    );
+    let files = &migrated.succeeded[0].paste.files;
+    assert_eq!(files.len(), 3);
+    assert_eq!(files[2].filename, DESCRIPTION_FILENAME);
+    assert_eq!(files[2].content, "A tiny Rust program");
+    assert_eq!(migrated.succeeded[1].paste.files.len(), 2);
+    assert_eq!(server.paste_count(), 2);
+
+    let migrated = client
+        .migrate_from_gist(
+            &gists,
+            "https://gist.github.com/octocat/aa5a315d61ae9438b18d",
+        )
+        .await
+        .unwrap();
+    assert_eq!(migrated.succeeded.len(), 1);
+    assert!(migrated.succeeded[0]
+        .paste_url
+        .starts_with("https://mystb.in/"));
+
+    let requests = github.join().unwrap();
+    assert!(requests[0].starts_with("GET /users/octocat/gists?per_page=100&page=1 "));
+    assert!(requests[1].starts_with("GET /gists/aa5a315d61ae9438b18d "));
+    assert!(requests[3].starts_with("GET /gists/aa5a315d61ae9438b18d "));
+}
+
+#[tokio::test]
+async fn migrate_truncated_gist() {
+    let (raw_url, raw) = serve_once(json!({ "log": "a".repeat(100) }), "200 OK");
+    let mut truncated = gist_json();
+    truncated["files"]["main.rs"] = json!({
+        "filename": "main.rs",
+        "content": "{\"log\": \"aaa",
+        "truncated": true,
+        "raw_url": format!("{}/raw/main.rs", raw_url),
+    });
+    let mut broken = truncated.clone();
+    broken["files"]["main.rs"]["raw_url"] = Value::Null;
+    let (url, github) = serve(vec![("200 OK", truncated), ("200 OK", broken)]);
+    let gists = GistProvider::new().api_url(url);
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+
+    let migrated = client
+        .migrate_from_gist(&gists, "aa5a315d61ae9438b18d")
+        .await
+        .unwrap();
+    let paste = &migrated.succeeded[0].paste;
+    let file = paste
+        .files
+        .iter()
+        .find(|f| f.filename == "main.rs")
+        .unwrap();
+    assert_eq!(file.content, json!({ "log": "a".repeat(100) }).to_string());
+    assert!(raw.join().unwrap().starts_with("GET /raw/main.rs "));
+
+    let migrated = client
+        .migrate_from_gist(&gists, "aa5a315d61ae9438b18d")
+        .await
+        .unwrap();
+    assert!(migrated.succeeded.is_empty());
+    assert!(matches!(migrated.failed[0].1, Error::InvalidResponse(_)));
+    assert_eq!(server.paste_count(), 1);
+    github.join().unwrap();
+}
@@ -0,0 +1,120 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+use myust::{
+    haste::{DpasteProvider, HastebinProvider},
+    CreatePasteRequest, Error, Expiry, File, PasteProvider, StatusCode,
+};
+
+/// Answer one request with `response`, returning the request line, headers and body.
+fn serve_once(
+    response: &'static str,
+    status: &'static str,
+) -> (String, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+            if line == "\r\n" {
+                break;
+            }
+            head.push_str(&line);
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            response.len(),
+            response
+        )
+        .unwrap();
+        head + "\r\n" + &String::from_utf8(body).unwrap()
+    });
+    (url, handle)
+}
+
+fn request(files: usize) -> CreatePasteRequest {
+    CreatePasteRequest {
+        files: (0..files)
+            .map(|n| File {
+                filename: format!("file{}.txt", n),
+                content: "Hello from myust!".to_string(),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn hastebin() {
+    let (url, server) = serve_once(r#"{"key":"abcdef"}"#, "200 OK");
+    let haste = HastebinProvider::new(format!("{}/", url));
+    let paste = haste.create(request(1)).await.unwrap();
+    assert_eq!(paste.id, "abcdef");
+    assert_eq!(haste.url_for(&paste.id), format!("{}/abcdef", url));
+    let sent = server.join().unwrap();
+    assert!(sent.starts_with("POST /documents HTTP/1.1"));
+    assert!(sent.ends_with("\r\n\r\nHello from myust!"));
+
+    let (url, server) = serve_once("Hello from myust!", "200 OK");
+    let haste = HastebinProvider::new(url);
+    let paste = haste.get("abcdef", None).await.unwrap();
+    assert_eq!(paste.files[0].content, "Hello from myust!");
+    assert!(server
+        .join()
+        .unwrap()
+        .starts_with("GET /raw/abcdef HTTP/1.1"));
+
+    let err = haste.create(request(2)).await.unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    let err = haste.delete("abcdef").await.unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+}
+
+#[tokio::test]
+async fn dpaste() {
+    let (url, server) = serve_once("https://dpaste.com/ABCD1234\n", "201 Created");
+    let dpaste = DpasteProvider::new().base_url(url).token("secret");
+    let mut request = request(1);
+    request.expires = Some(Expiry {
+        hours: 30,
+        ..Default::default()
+    });
+    let paste = dpaste.create(request).await.unwrap();
+    assert_eq!(paste.id, "ABCD1234");
+    let sent = server.join().unwrap();
+    assert!(sent.starts_with("POST /api/v2/ HTTP/1.1"));
+    assert!(sent.contains("authorization: Bearer secret"));
+    assert!(sent.ends_with("content=Hello+from+myust%21&title=file0.txt&expiry_days=2"));
+
+    let (url, server) = serve_once("Not found", "404 Not Found");
+    let dpaste = DpasteProvider::new().base_url(url);
+    let err = dpaste.get("missing", None).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Provider { provider: "dpaste", code: StatusCode::NOT_FOUND, message: Some(ref m) } if m == "Not found"
+    ));
+    assert!(server
+        .join()
+        .unwrap()
+        .starts_with("GET /missing.txt HTTP/1.1"));
+    assert_eq!(
+        DpasteProvider::new().url_for("ABCD1234"),
+        "https://dpaste.com/ABCD1234"
+    );
+}
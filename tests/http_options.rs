@@ -0,0 +1,110 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use myust::{Client, HttpOptions, SyncClient};
+
+/// Answer every request with an empty object, keeping connections open, and count
+/// the connections accepted.
+fn serve_keep_alive() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let accepted = connections.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            accepted.fetch_add(1, Ordering::SeqCst);
+            thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut content_length = 0;
+                    let mut line = String::new();
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                            return;
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap();
+                            }
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+                    let written = write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\n\r\n{{}}"
+                    );
+                    if written.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    (url, connections)
+}
+
+#[tokio::test]
+async fn connection_pool() {
+    let (url, connections) = serve_keep_alive();
+    let client = Client::new().base_url(&url).http_options(HttpOptions {
+        tcp_keepalive: Some(Duration::from_secs(60)),
+        pool_max_idle_per_host: Some(4),
+        ..Default::default()
+    });
+    for _ in 0..5 {
+        client.ping().await.unwrap();
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+    let (url, connections) = serve_keep_alive();
+    let client = Client::new().base_url(&url).http_options(HttpOptions {
+        pool_max_idle_per_host: Some(0),
+        ..Default::default()
+    });
+    for _ in 0..5 {
+        client.ping().await.unwrap();
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test]
+async fn pool_idle_timeout() {
+    let (url, connections) = serve_keep_alive();
+    let client = Client::new().base_url(&url).http_options(HttpOptions {
+        pool_idle_timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+    client.ping().await.unwrap();
+    client.ping().await.unwrap();
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    client.ping().await.unwrap();
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn sync_connection_pool() {
+    let (url, connections) = serve_keep_alive();
+    let client = SyncClient::new().base_url(&url).http_options(HttpOptions {
+        pool_max_idle_per_host: Some(0),
+        ..Default::default()
+    });
+    for _ in 0..3 {
+        client.ping().unwrap();
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,70 @@
+use myust::{test_server::TestServer, Client, CreatePasteRequest, File, PasteProvider};
+
+#[tokio::test]
+async fn generated_idempotency_keys() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let first = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    let second = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    assert!(first.idempotency_key.is_some());
+    assert_ne!(first.idempotency_key, second.idempotency_key);
+    assert_ne!(first.id, second.id);
+    assert_eq!(server.paste_count(), 2);
+
+    // fetched pastes weren't created by this request
+    let fetched = client.get_paste(|p| p.id(&first.id)).await.unwrap();
+    assert_eq!(fetched.idempotency_key, None);
+}
+
+#[tokio::test]
+async fn replayed_idempotency_key() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let first = client
+        .create_paste(|p| {
+            p.filename("myust.txt")
+                .content("hi")
+                .idempotency_key("deploy-42")
+        })
+        .await
+        .unwrap();
+    let request = CreatePasteRequest {
+        files: vec![File {
+            filename: "myust.txt".to_string(),
+            content: "hi".to_string(),
+            ..Default::default()
+        }],
+        idempotency_key: Some("deploy-42".to_string()),
+        ..Default::default()
+    };
+    let second = client.create(request).await.unwrap();
+    assert_eq!(first.id, second.id);
+    assert_eq!(second.idempotency_key.as_deref(), Some("deploy-42"));
+    assert_eq!(server.paste_count(), 1);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn sync_idempotency_key() {
+    let server = TestServer::start();
+    let client = myust::SyncClient::new().base_url(server.url());
+    let create = || {
+        client
+            .create_multifile_paste(|p| {
+                p.file(|f| f.filename("a.txt").content("a").idempotency_key("once"))
+                    .file(|f| f.filename("b.txt").content("b"))
+            })
+            .unwrap()
+    };
+    let first = create();
+    let second = create();
+    assert_eq!(first.id, second.id);
+    assert_eq!(first.idempotency_key.as_deref(), Some("once"));
+    assert_eq!(server.paste_count(), 1);
+}
@@ -0,0 +1,122 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use myust::{test_server::TestServer, Client, Error, ValidationError};
+
+fn tree(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("myust-import-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("crash-report/nested")).unwrap();
+    fs::write(
+        root.join("crash-report/backtrace.txt"),
+        "thread 'main' panicked",
+    )
+    .unwrap();
+    fs::write(root.join("crash-report/config.toml"), "debug = true").unwrap();
+    fs::write(root.join("crash-report/nested/skipped.txt"), "skipped").unwrap();
+    fs::write(root.join("notes.md"), "# Notes").unwrap();
+    fs::write(root.join("logo.png"), [0x89, b'P', b'N', b'G', 0xff]).unwrap();
+    fs::write(
+        root.join("manifest.json"),
+        r#"{"pastes": [{"path": "crash-report", "password": "hunter2", "expires": {"days": 7}}]}"#,
+    )
+    .unwrap();
+    root
+}
+
+#[tokio::test]
+async fn import_dir() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let root = tree("dir");
+
+    let progress = AtomicUsize::new(0);
+    let result = client
+        .import_dir(&root, |import| {
+            import.progress(|done, total| {
+                assert_eq!(total, 3);
+                progress.store(done, Ordering::SeqCst);
+            })
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(progress.load(Ordering::SeqCst), 3);
+    assert!(result.failed.is_empty());
+    let sources: Vec<_> = result.succeeded.iter().map(|p| p.source.as_str()).collect();
+    assert_eq!(sources, ["crash-report", "logo.png", "notes.md"]);
+    assert_eq!(server.paste_count(), 3);
+
+    let report = &result.succeeded[0];
+    let filenames: Vec<_> = report
+        .request
+        .files
+        .iter()
+        .map(|f| f.filename.as_str())
+        .collect();
+    assert_eq!(filenames, ["backtrace.txt", "config.toml"]);
+    assert_eq!(report.request.password.as_deref(), Some("hunter2"));
+    assert_eq!(report.request.expires.as_ref().unwrap().days, 7);
+
+    let id = &report.paste.as_ref().unwrap().id;
+    let paste = client
+        .get_paste(|p| p.id(id).password("hunter2"))
+        .await
+        .unwrap();
+    assert_eq!(paste.files.len(), 2);
+
+    let logo = &result.succeeded[1].request.files[0];
+    assert_eq!(logo.decode_bytes().unwrap(), [0x89, b'P', b'N', b'G', 0xff]);
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn import_archive_dry_run() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let root = tree("archive");
+    fs::write(root.join("empty.txt"), "").unwrap();
+
+    let archive = root.with_extension("tar");
+    let mut builder = tar::Builder::new(fs::File::create(&archive).unwrap());
+    builder.append_dir_all("", &root).unwrap();
+    builder.finish().unwrap();
+
+    let result = client
+        .import_archive(&archive, |import| import.dry_run(true))
+        .await
+        .unwrap();
+
+    assert_eq!(server.paste_count(), 0);
+    assert_eq!(result.succeeded.len(), 3);
+    assert!(result.succeeded.iter().all(|p| p.paste.is_none()));
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].0, "empty.txt");
+    assert!(matches!(
+        result.failed[0].1,
+        Error::Invalid(ValidationError::EmptyContent)
+    ));
+
+    fs::remove_dir_all(root).unwrap();
+    fs::remove_file(archive).unwrap();
+}
+
+#[tokio::test]
+async fn import_unknown_manifest_path() {
+    let client = Client::new();
+    let root = tree("manifest");
+    fs::write(
+        root.join("manifest.json"),
+        r#"{"pastes": [{"path": "missing"}]}"#,
+    )
+    .unwrap();
+
+    let result = client.import_dir(&root, |import| import).await;
+    assert!(matches!(result, Err(Error::FileNotFound(path)) if path == "missing"));
+
+    fs::remove_dir_all(root).unwrap();
+}
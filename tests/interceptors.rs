@@ -0,0 +1,44 @@
+use std::sync::{Arc, Mutex};
+
+use myust::{
+    test_server::TestServer, Client, Interceptor, OutgoingRequest, RawResponse, StatusCode,
+};
+
+struct CustomAuth;
+
+impl Interceptor for CustomAuth {
+    fn on_request(&self, request: &mut OutgoingRequest<'_>) {
+        let token = format!("Bearer {}", TestServer::TOKEN);
+        request
+            .headers
+            .insert("Authorization", token.parse().unwrap());
+    }
+}
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<StatusCode>>);
+
+impl Interceptor for Recorder {
+    fn on_response(&self, response: &RawResponse<'_>) {
+        assert!(!response.body.is_empty());
+        self.0.lock().unwrap().push(response.status);
+    }
+}
+
+#[tokio::test]
+async fn interceptors() {
+    let server = TestServer::start();
+    let recorder = Arc::new(Recorder::default());
+    let client = Client::new()
+        .base_url(server.url())
+        .interceptor(CustomAuth)
+        .interceptor(recorder.clone());
+    client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    // authenticated through the interceptor only
+    let pastes = client.get_user_pastes(|p| p).await.unwrap();
+    assert_eq!(pastes.len(), 1);
+    assert_eq!(*recorder.0.lock().unwrap(), vec![201, 200]);
+}
@@ -0,0 +1,62 @@
+use myust::{test_server::TestServer, Client, Error, File, InvalidCharacters, ValidationError};
+
+#[tokio::test]
+async fn invalid_characters() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let log = b"started\r\n\x00\x00\tready\xff\n";
+
+    let err = client
+        .create_paste(|p| {
+            p.filename("app.log")
+                .content_bytes(log)
+                .invalid_characters(InvalidCharacters::Reject)
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Invalid(ValidationError::InvalidCharacter {
+            character: '\0',
+            position: 9,
+            ..
+        })
+    ));
+    assert_eq!(server.paste_count(), 0);
+
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("app.log")
+                    .content_bytes(log)
+                    .invalid_characters(InvalidCharacters::Strip)
+            });
+            p.file(|f| f.filename("other.log").content("bell\x07"))
+        })
+        .await
+        .unwrap();
+    let paste = client.get_paste(|p| p.id(paste.id.as_str())).await.unwrap();
+    assert_eq!(paste.files[0].content, "started\r\n\tready\n");
+    assert_eq!(paste.files[1].content, "bell");
+
+    let paste = client
+        .create_paste(|p| p.filename("app.log").content_bytes(log))
+        .await
+        .unwrap();
+    let paste = client.get_paste(|p| p.id(paste.id.as_str())).await.unwrap();
+    assert_eq!(paste.files[0].content, "started\r\n\0\0\tready\u{FFFD}\n");
+}
+
+#[test]
+fn character_helpers() {
+    assert!(File::validate_characters("a.txt", "tabs\tand\r\nbreaks").is_ok());
+    assert_eq!(
+        File::validate_characters("a.txt", "é\u{FFFD}"),
+        Err(ValidationError::InvalidCharacter {
+            file: "a.txt".to_string(),
+            character: '\u{FFFD}',
+            position: 2,
+        })
+    );
+    assert_eq!(File::strip_invalid_characters("a\0b\x1bc"), "abc");
+}
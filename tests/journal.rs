@@ -0,0 +1,146 @@
+use myust::{journal::Journal, test_server::TestServer, Client, Error, SyncClient};
+
+#[tokio::test]
+async fn journal_created_pastes() {
+    let server = TestServer::start();
+    let path = std::env::temp_dir()
+        .join(format!("myust-journal-{}", std::process::id()))
+        .join("journal.jsonl");
+    let client = Client::new()
+        .base_url(server.url())
+        .web_url("https://paste.internal.example")
+        .journal(&path);
+
+    let journal = Journal::new(&path);
+    assert!(journal.recent(5).unwrap().is_empty());
+
+    let first = client
+        .create_paste(|p| p.filename("first.txt").content("first"))
+        .await
+        .unwrap();
+    let second = client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("a.rs").content("a"))
+                .file(|f| f.filename("b.rs").content("b"))
+        })
+        .await
+        .unwrap();
+
+    let recent = journal.recent(5).unwrap();
+    assert_eq!(recent.len(), 2);
+    assert_eq!(recent[0].id, second.id);
+    assert_eq!(recent[0].files, ["a.rs", "b.rs"]);
+    assert_eq!(recent[1].id, first.id);
+    assert_eq!(
+        recent[1].url,
+        format!("https://paste.internal.example/{}", first.id)
+    );
+    assert_eq!(journal.recent(1).unwrap().len(), 1);
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn deduplicate_uploads() {
+    let server = TestServer::start();
+    let path = std::env::temp_dir()
+        .join(format!("myust-dedup-{}", std::process::id()))
+        .join("journal.jsonl");
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await
+        .journal(&path)
+        .deduplicate(true);
+
+    let first = client
+        .create_paste(|p| p.filename("notes.txt").content("same"))
+        .await
+        .unwrap();
+    let again = client
+        .create_paste(|p| p.filename("notes.txt").content("same"))
+        .await
+        .unwrap();
+    assert_eq!(again.id, first.id);
+    assert_eq!(server.paste_count(), 1);
+
+    let protected = client
+        .create_paste(|p| p.filename("notes.txt").content("same").password("pw"))
+        .await
+        .unwrap();
+    assert_ne!(protected.id, first.id);
+
+    // a deleted paste isn't reused
+    client.delete_paste(&first.id).await.unwrap();
+    let recreated = client
+        .create_paste(|p| p.filename("notes.txt").content("same"))
+        .await
+        .unwrap();
+    assert_ne!(recreated.id, first.id);
+    assert_eq!(server.paste_count(), 2);
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn deduplicate_per_paste() {
+    let server = TestServer::start();
+    let path = std::env::temp_dir()
+        .join(format!("myust-dedup-per-paste-{}", std::process::id()))
+        .join("journal.jsonl");
+    let client = Client::new().base_url(server.url()).journal(&path);
+
+    let first = client
+        .create_paste(|p| p.filename("notes.txt").content("same"))
+        .await
+        .unwrap();
+    let again = client
+        .create_paste(|p| p.filename("notes.txt").content("same").deduplicate(true))
+        .await
+        .unwrap();
+    assert_eq!(again.id, first.id);
+
+    let client = client.deduplicate(true);
+    let forced = client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("notes.txt").content("same").deduplicate(false))
+        })
+        .await
+        .unwrap();
+    assert_ne!(forced.id, first.id);
+    assert_eq!(server.paste_count(), 2);
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn deduplicate_without_journal() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url()).deduplicate(true);
+    let err = client
+        .create_paste(|p| p.filename("notes.txt").content("same"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    let client = Client::new().base_url(server.url());
+    let err = client
+        .create_paste(|p| p.filename("notes.txt").content("same").deduplicate(true))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    assert_eq!(server.paste_count(), 0);
+}
+
+#[test]
+fn sync_deduplicate_without_journal() {
+    let server = TestServer::start();
+    let client = SyncClient::new().base_url(server.url()).deduplicate(true);
+    let err = client
+        .create_paste(|p| p.filename("notes.txt").content("same"))
+        .unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    client
+        .create_paste(|p| p.filename("notes.txt").content("same").deduplicate(false))
+        .unwrap();
+    assert_eq!(server.paste_count(), 1);
+}
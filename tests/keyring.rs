@@ -0,0 +1,82 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use keyring::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
+use myust::{keyring as tokens, test_server::TestServer, Client};
+
+type Store = Arc<Mutex<HashMap<(String, String), Vec<u8>>>>;
+
+/// An in-memory keychain, as the system one isn't available in CI.
+struct MemoryBuilder(Store);
+
+struct MemoryCredential(Store, (String, String));
+
+impl CredentialApi for MemoryCredential {
+    fn set_secret(&self, secret: &[u8]) -> keyring::Result<()> {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(self.1.clone(), secret.to_vec());
+        Ok(())
+    }
+
+    fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+        let store = self.0.lock().unwrap();
+        store.get(&self.1).cloned().ok_or(keyring::Error::NoEntry)
+    }
+
+    fn delete_credential(&self) -> keyring::Result<()> {
+        let mut store = self.0.lock().unwrap();
+        store
+            .remove(&self.1)
+            .map(|_| ())
+            .ok_or(keyring::Error::NoEntry)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl CredentialBuilderApi for MemoryBuilder {
+    fn build(
+        &self,
+        _target: Option<&str>,
+        service: &str,
+        user: &str,
+    ) -> keyring::Result<Box<Credential>> {
+        let key = (service.to_string(), user.to_string());
+        Ok(Box::new(MemoryCredential(self.0.clone(), key)))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn keyring_tokens() {
+    let builder: Box<CredentialBuilder> = Box::new(MemoryBuilder(Store::default()));
+    keyring::set_default_credential_builder(builder);
+    let server = TestServer::start();
+
+    assert_eq!(tokens::load_token("myust", "me").unwrap(), None);
+    assert!(Client::new().auth_from_keyring("myust", "me").is_err());
+
+    tokens::store_token("myust", "me", "old-token").unwrap();
+    let previous = tokens::rotate_token("myust", "me", TestServer::TOKEN).unwrap();
+    assert_eq!(previous.as_deref(), Some("old-token"));
+
+    let client = Client::new()
+        .base_url(server.url())
+        .auth_from_keyring("myust", "me")
+        .unwrap();
+    assert!(client.get_user_pastes(|o| o).await.is_ok());
+
+    tokens::delete_token("myust", "me").unwrap();
+    tokens::delete_token("myust", "me").unwrap();
+    assert_eq!(tokens::load_token("myust", "me").unwrap(), None);
+}
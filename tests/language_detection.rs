@@ -0,0 +1,67 @@
+use myust::{language::Language, test_server::TestServer, Client, File};
+
+#[test]
+fn detect() {
+    let detect = Language::detect;
+    assert_eq!(
+        detect("use std::io;\n\nfn main() {\n    let mut x = 1;\n}\n"),
+        Some(Language::Rust)
+    );
+    assert_eq!(
+        detect("import os\n\ndef main():\n    print(os.getcwd())\n"),
+        Some(Language::Python)
+    );
+    assert_eq!(
+        detect("#!/usr/bin/env python3\nx = 1\n"),
+        Some(Language::Python)
+    );
+    assert_eq!(detect("#!/bin/bash\necho hi\n"), Some(Language::Bash));
+    assert_eq!(
+        detect("package main\n\nfunc main() {}\n"),
+        Some(Language::Go)
+    );
+    assert_eq!(
+        detect("#include <stdio.h>\nint main() {}\n"),
+        Some(Language::C)
+    );
+    assert_eq!(detect(r#"{"key": [1, 2]}"#), Some(Language::Json));
+    assert_eq!(
+        detect("<!DOCTYPE html>\n<html></html>\n"),
+        Some(Language::Html)
+    );
+    assert_eq!(detect("just some notes\nnothing to see\n"), None);
+    assert_eq!(detect(""), None);
+
+    assert_eq!(Language::from_filename("lib.RS"), Some(Language::Rust));
+    assert_eq!(Language::from_filename("Makefile"), None);
+    let file = File {
+        filename: "script.rb".to_string(),
+        content: "def main():\n    pass\n".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(file.language(), Some(Language::Ruby));
+}
+
+#[tokio::test]
+async fn detect_language() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("main")
+                    .content("fn main() {\n    println!(\"hi\");\n}\n")
+                    .detect_language(true)
+            })
+            .file(|f| {
+                f.filename("notes.txt")
+                    .content("fn main() {}\n")
+                    .detect_language(true)
+            })
+            .file(|f| f.filename("setup").content("import os\n"))
+        })
+        .await
+        .unwrap();
+    let filenames: Vec<&str> = paste.files.iter().map(|f| f.filename.as_str()).collect();
+    assert_eq!(filenames, ["main.rs", "notes.txt", "setup"]);
+}
@@ -0,0 +1,35 @@
+use myust::{test_server::TestServer, Client, LineEndings};
+
+#[tokio::test]
+async fn line_endings() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("windows.log")
+                    .content("one\r\ntwo\r\n")
+                    .line_endings(LineEndings::Lf)
+            });
+            p.file(|f| f.filename("mac.log").content("one\rtwo\r"))
+        })
+        .await
+        .unwrap();
+    assert_eq!(paste.line_endings, Some(LineEndings::Lf));
+    assert_eq!(paste.files[0].content, "one\ntwo\n");
+    let json = serde_json::to_value(&paste).unwrap();
+    assert_eq!(json["line_endings"], "lf");
+    let fetched = client.get_paste(|p| p.id(paste.id.as_str())).await.unwrap();
+    assert_eq!(fetched.line_endings, None);
+    assert_eq!(fetched.files[0].content, "one\ntwo\n");
+    assert_eq!(fetched.files[1].content, "one\ntwo\n");
+
+    let paste = client
+        .create_paste(|p| p.filename("windows.log").content("one\r\ntwo\r\n"))
+        .await
+        .unwrap();
+    assert_eq!(paste.line_endings, Some(LineEndings::Preserve));
+    let fetched = client.get_paste(|p| p.id(paste.id.as_str())).await.unwrap();
+    assert_eq!(fetched.files[0].content, "one\r\ntwo\r\n");
+}
@@ -0,0 +1,32 @@
+use std::sync::{Arc, Mutex};
+
+use myust::{test_server::TestServer, Client};
+
+#[tokio::test]
+async fn metrics() {
+    let server = TestServer::start();
+    let seen = Arc::new(Mutex::new(vec![]));
+    let recorder = seen.clone();
+    let client =
+        Client::new()
+            .base_url(server.url())
+            .metrics(move |endpoint: &str, status: u16, _| {
+                recorder
+                    .lock()
+                    .unwrap()
+                    .push((endpoint.to_string(), status));
+            });
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    let seen = seen.lock().unwrap();
+    assert_eq!(
+        *seen,
+        vec![
+            ("PUT /paste".to_string(), 201),
+            ("GET /paste/{id}".to_string(), 200)
+        ]
+    );
+}
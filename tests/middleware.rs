@@ -0,0 +1,20 @@
+#![cfg(feature = "middleware")]
+
+use myust::{test_server::TestServer, Client};
+use reqwest_middleware::ClientBuilder;
+
+#[tokio::test]
+async fn middleware() {
+    let server = TestServer::start();
+    let inner = ClientBuilder::new(reqwest::Client::new()).build();
+    let client = Client::with_middleware(inner)
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    assert!(format!("{client:?}").contains("middleware: true"));
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    client.delete_paste(&paste.id).await.unwrap();
+}
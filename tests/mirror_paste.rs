@@ -0,0 +1,39 @@
+use myust::{
+    haste::HastebinProvider, mirror_paste, test_server::TestServer, Client, CreatePasteRequest,
+    Error, File,
+};
+
+#[tokio::test]
+async fn mirror_paste_to_providers() {
+    let primary = TestServer::start();
+    let backup = TestServer::start();
+    let mystbin = Client::new().base_url(primary.url());
+    let mirror = Client::new()
+        .base_url(backup.url())
+        .web_url("https://backup.example");
+    // nothing listens on port 9 (discard), so this provider can't be reached
+    let unreachable = HastebinProvider::new("http://127.0.0.1:9");
+
+    let mirrored = mirror_paste(
+        &[&mystbin, &unreachable, &mirror],
+        CreatePasteRequest {
+            files: vec![File {
+                filename: "report.txt".to_string(),
+                content: "all good".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(primary.paste_count(), 1);
+    assert_eq!(backup.paste_count(), 1);
+
+    let urls = mirrored.urls();
+    assert_eq!(urls.len(), 2);
+    assert!(urls[0].starts_with("https://mystb.in/"));
+    assert!(urls[1].starts_with("https://backup.example/"));
+    assert_eq!(mirrored.failed.len(), 1);
+    assert_eq!(mirrored.failed[0].provider, "hastebin");
+    assert!(matches!(mirrored.failed[0].error, Error::Io(_)));
+}
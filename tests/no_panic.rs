@@ -0,0 +1,151 @@
+use std::{
+    io::{BufRead, BufReader, Cursor, Write},
+    net::TcpListener,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use myust::{
+    template::PasteTemplate, test_server::TestServer, Client, Error, Expiry, SyncClient,
+    ValidationError,
+};
+
+/// Answer every request with a successful response carrying `body`.
+fn serve_ok(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn network_failure() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let client = Client::new().base_url(url.as_str());
+    let err = client
+        .get_paste(|p| p.id("MissingPaste"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Connect(_)));
+    assert!(err.is_retryable());
+    let err = client.delete_paste("MissingPaste").await.unwrap_err();
+    assert!(matches!(err, Error::Connect(_)));
+    let err = Client::new()
+        .base_url(url.as_str())
+        .try_auth("token")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Connect(_)));
+    // a token that can't be checked is kept, the error surfacing on use
+    let client = Client::new().base_url(url.as_str()).auth("token").await;
+    let err = client.get_user_pastes(|p| p).await.unwrap_err();
+    assert!(matches!(err, Error::Connect(_)));
+}
+
+#[tokio::test]
+#[should_panic(expected = "the provided token is invalid")]
+async fn auth_rejected_token() {
+    let server = TestServer::start();
+    Client::new()
+        .base_url(server.url())
+        .auth("not a token")
+        .await;
+}
+
+#[tokio::test]
+async fn malformed_responses() {
+    let client = Client::new().base_url(serve_ok("<html>maintenance</html>"));
+    let err = client.get_paste(|p| p.id("SomePaste")).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidResponse(_)));
+    let err = client.get_user_bookmarks().await.unwrap_err();
+    assert!(matches!(err, Error::InvalidResponse(_)));
+
+    let client = Client::new().base_url(serve_ok("{}"));
+    let err = client.get_paste(|p| p.id("SomePaste")).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidResponse(_)));
+    let err = client
+        .create_paste(|p| p.filename("a.txt").content("a"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidResponse(_)));
+    let err = client.get_user_pastes(|p| p).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidResponse(_)));
+    let err = client.delete_pastes(vec!["SomePaste"]).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidResponse(_)));
+}
+
+#[tokio::test]
+async fn invalid_input() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let forever = Expiry {
+        days: i32::MAX,
+        ..Default::default()
+    };
+    assert!(matches!(
+        forever.validate(),
+        Err(ValidationError::InvalidExpiry(_))
+    ));
+    let err = client
+        .create_paste(|p| p.filename("a.txt").content("a").expires(forever))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Invalid(ValidationError::InvalidExpiry(_))
+    ));
+
+    let err = Client::new()
+        .base_url(server.url())
+        .try_auth("not a token")
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(myust::StatusCode::UNAUTHORIZED));
+}
+
+#[test]
+fn sync_client_async_reader() {
+    let server = TestServer::start();
+    let client = SyncClient::new().base_url(server.url());
+    let err = client
+        .create_paste(|p| {
+            p.filename("a.txt")
+                .content_reader(Cursor::new(b"content".to_vec()))
+        })
+        .unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    assert_eq!(server.paste_count(), 0);
+}
+
+#[test]
+fn unrepresentable_times() {
+    let template = PasteTemplate::new().filename("log-{date}-{time}.txt");
+    let before_1970 = UNIX_EPOCH - Duration::from_secs(1);
+    assert_eq!(template.filename_at(before_1970), None);
+    let after_9999 = UNIX_EPOCH + Duration::from_secs(253_402_300_800);
+    assert_eq!(template.filename_at(after_9999), None);
+    assert_eq!(
+        template.filename_at(after_9999 - Duration::from_secs(1)),
+        Some("log-9999-12-31-23-59-59.txt".to_string())
+    );
+    assert!(template.filename_at(SystemTime::now()).is_some());
+}
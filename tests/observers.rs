@@ -0,0 +1,59 @@
+use std::sync::{Arc, Mutex};
+
+use myust::{test_server::TestServer, Client, Observer, PasteResult};
+
+#[derive(Default)]
+struct AuditLog(Mutex<Vec<String>>);
+
+impl Observer for AuditLog {
+    fn on_paste_created(&self, paste: &PasteResult) {
+        self.0.lock().unwrap().push(format!("created {}", paste.id));
+    }
+
+    fn on_paste_deleted(&self, paste_id: &str) {
+        self.0.lock().unwrap().push(format!("deleted {paste_id}"));
+    }
+
+    fn on_bookmark_changed(&self, paste_id: &str, bookmarked: bool) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("bookmarked {paste_id} {bookmarked}"));
+    }
+}
+
+#[tokio::test]
+async fn observers() {
+    let server = TestServer::start();
+    let log = Arc::new(AuditLog::default());
+    let client = Client::new()
+        .base_url(server.url())
+        .observer(log.clone())
+        .auth(TestServer::TOKEN)
+        .await;
+    let first = client
+        .create_paste(|p| p.filename("myust.txt").content("first"))
+        .await
+        .unwrap();
+    let second = client
+        .create_paste(|p| p.filename("myust.txt").content("second"))
+        .await
+        .unwrap();
+    client.create_bookmark(&first.id).await.unwrap();
+    client.delete_bookmark(&first.id).await.unwrap();
+    client
+        .delete_pastes(vec![&first.id, &second.id, "MissingPaste"])
+        .await
+        .unwrap();
+    assert_eq!(
+        *log.0.lock().unwrap(),
+        vec![
+            format!("created {}", first.id),
+            format!("created {}", second.id),
+            format!("bookmarked {} true", first.id),
+            format!("bookmarked {} false", first.id),
+            format!("deleted {}", first.id),
+            format!("deleted {}", second.id),
+        ]
+    );
+}
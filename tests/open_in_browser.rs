@@ -0,0 +1,35 @@
+#![cfg(unix)]
+
+use std::{fs, os::unix::fs::PermissionsExt, thread, time::Duration};
+
+use myust::Client;
+
+#[test]
+fn open_in_browser() {
+    let dir = std::env::temp_dir().join(format!("myust-browser-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let opened = dir.join("opened");
+    let browser = dir.join("browser");
+    fs::write(
+        &browser,
+        format!("#!/bin/sh\necho \"$1\" > {}\n", opened.display()),
+    )
+    .unwrap();
+    fs::set_permissions(&browser, fs::Permissions::from_mode(0o755)).unwrap();
+    std::env::set_var("BROWSER", &browser);
+
+    let client = Client::new().web_url("https://paste.internal.example");
+    client.open_in_browser("EquipmentMovingExpensive").unwrap();
+    for _ in 0..50 {
+        if opened.exists() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert_eq!(
+        fs::read_to_string(&opened).unwrap().trim(),
+        "https://paste.internal.example/EquipmentMovingExpensive"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
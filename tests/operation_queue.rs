@@ -0,0 +1,98 @@
+use std::{net::TcpListener, time::Duration};
+
+use myust::{
+    queue::{Operation, OperationQueue},
+    test_server::TestServer,
+    Client, File,
+};
+
+fn create(content: &str) -> Operation {
+    Operation::Create {
+        files: vec![File {
+            filename: "queued.txt".to_string(),
+            content: content.to_string(),
+            ..Default::default()
+        }],
+        password: None,
+        expires: None,
+    }
+}
+
+#[tokio::test]
+async fn queue_while_offline() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let existing = client
+        .create_paste(|p| p.filename("existing.txt").content("existing"))
+        .await
+        .unwrap();
+
+    // nothing listens on a port freed right after binding it
+    let closed = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let offline = client.clone().base_url(format!("http://{}", closed));
+
+    let path = std::env::temp_dir()
+        .join(format!("myust-queue-{}", std::process::id()))
+        .join("queue.json");
+    let queue = OperationQueue::open(&path).unwrap();
+    for operation in [
+        create("first"),
+        Operation::Delete {
+            id: existing.id.clone(),
+        },
+        Operation::Delete {
+            id: "MissingPasteId".to_string(),
+        },
+        create("second"),
+    ] {
+        assert!(offline
+            .run_or_queue(operation, &queue)
+            .await
+            .unwrap()
+            .is_none());
+    }
+    let flushed = offline.flush_queue(&queue, |o| o).await.unwrap();
+    assert!(flushed.completed.is_empty());
+    assert_eq!(flushed.remaining, 4);
+
+    // the queue survives a restart
+    let queue = OperationQueue::open(&path).unwrap();
+    assert_eq!(queue.len(), 4);
+    assert_eq!(server.paste_count(), 1);
+
+    let mut conflicts = vec![];
+    let flushed = client
+        .flush_queue(&queue, |o| {
+            o.retry_delay(Duration::from_millis(10))
+                .on_conflict(|operation, _| conflicts.push(operation.clone()))
+        })
+        .await
+        .unwrap();
+    assert_eq!(flushed.completed.len(), 3);
+    assert_eq!(flushed.created.len(), 2);
+    assert_eq!(flushed.created[0].files[0].content, "first");
+    assert_eq!(
+        conflicts,
+        [Operation::Delete {
+            id: "MissingPasteId".to_string()
+        }]
+    );
+    assert_eq!(flushed.remaining, 0);
+    assert!(OperationQueue::open(&path).unwrap().is_empty());
+    assert_eq!(server.paste_count(), 2);
+
+    let paste = client
+        .run_or_queue(create("online"), &queue)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(paste.files[0].content, "online");
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,80 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use myust::{Client, ParseWarning};
+
+/// Answer every request with a successful response carrying `body`.
+fn serve_ok(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn parse_warnings() {
+    let warnings = Arc::new(Mutex::new(vec![]));
+    let seen = warnings.clone();
+    let client = Client::new()
+        .base_url(serve_ok(
+            r#"{"pastes": [
+                {"id": "FirstPaste", "created_at": "2023-01-01T00:00:00Z"},
+                {"id": "OddPaste", "created_at": null},
+                {"created_at": "2023-01-01T00:00:00Z"}
+            ]}"#,
+        ))
+        .parse_warnings(move |warning: &ParseWarning| seen.lock().unwrap().push(warning.clone()));
+    let pastes = client.get_user_pastes(|p| p).await.unwrap();
+    let ids: Vec<_> = pastes.iter().map(|p| p.id.as_str()).collect();
+    assert_eq!(ids, ["FirstPaste", "OddPaste"]);
+    assert_eq!(pastes[1].created_at, "");
+    let warnings = warnings.lock().unwrap().clone();
+    assert_eq!(
+        *warnings,
+        [
+            ParseWarning {
+                field: "created_at",
+                paste_id: Some("OddPaste".to_string()),
+                skipped: false,
+            },
+            ParseWarning {
+                field: "id",
+                paste_id: None,
+                skipped: true,
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "missing or invalid field `created_at` in paste OddPaste, the default was used"
+    );
+
+    let client = Client::new().base_url(serve_ok(
+        r#"{"id": "OddPaste", "files": [{"filename": "a.txt", "content": null}, 42]}"#,
+    ));
+    let paste = client.get_paste(|p| p.id("OddPaste")).await.unwrap();
+    assert_eq!(paste.created_at, "");
+    assert_eq!(paste.files.len(), 1);
+    assert_eq!(paste.files[0].filename, "a.txt");
+    assert_eq!(paste.files[0].content, "");
+}
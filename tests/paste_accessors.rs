@@ -0,0 +1,31 @@
+use myust::{File, PasteResult};
+
+fn file(filename: &str, content: &str) -> File {
+    File {
+        filename: filename.to_string(),
+        content: content.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn paste_accessors() {
+    let mut paste = PasteResult {
+        files: vec![
+            file("main.rs", "fn main() {}\n"),
+            file("README.md", "# myust"),
+        ],
+        ..Default::default()
+    };
+    assert_eq!(paste.file("README.md").unwrap().content, "# myust");
+    assert!(paste.file("readme.md").is_none());
+    assert_eq!(
+        paste.combined_content(),
+        "==> main.rs <==\nfn main() {}\n\n==> README.md <==\n# myust"
+    );
+
+    paste.files.truncate(1);
+    assert_eq!(paste.combined_content(), "fn main() {}\n");
+    paste.files.clear();
+    assert_eq!(paste.combined_content(), "");
+}
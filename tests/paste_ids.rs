@@ -0,0 +1,109 @@
+use myust::{test_server::TestServer, Client, Error, PasteId, Url, ValidationError};
+
+#[test]
+fn parse_paste_ids() {
+    for input in [
+        "EquipmentMovingExpensive",
+        "https://mystb.in/EquipmentMovingExpensive",
+        "https://mystb.in/EquipmentMovingExpensive/?raw#L4",
+        "mystb.in/EquipmentMovingExpensive",
+        "http://localhost:8000/api/paste/EquipmentMovingExpensive",
+    ] {
+        let id = PasteId::try_from(input).unwrap();
+        assert_eq!(id.as_str(), "EquipmentMovingExpensive", "{}", input);
+    }
+    for input in [
+        "",
+        "../users/@me",
+        "Equipment Moving",
+        "https://mystb.in/",
+        "Id?x=1",
+    ] {
+        assert!(
+            matches!(
+                PasteId::try_from(input),
+                Err(ValidationError::InvalidPasteId(_))
+            ),
+            "{}",
+            input
+        );
+    }
+}
+
+#[tokio::test]
+async fn get_paste_by_id_or_url() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    let url = Url::parse(&format!("https://mystb.in/{}", paste.id)).unwrap();
+    let id = PasteId::try_from(paste.id.as_str()).unwrap();
+    for fetched in [
+        client.get_paste(|p| p.id(&paste.id)).await,
+        client.get_paste(|p| p.id(url.as_str())).await,
+        client.get_paste(|p| p.id(url.clone())).await,
+        client.get_paste(|p| p.id(id.clone())).await,
+    ] {
+        assert_eq!(fetched.unwrap().id, paste.id);
+    }
+
+    let err = client
+        .get_paste(|p| p.id("../users/@me"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Invalid(ValidationError::InvalidPasteId(_))
+    ));
+    // an ID set on the field directly is checked too
+    let err = client
+        .get_paste(|p| {
+            p.id = "users/@me?".to_string();
+            p
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Invalid(_)));
+}
+
+#[tokio::test]
+async fn reject_traversal_ids() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    let invalid = |result: Result<(), Error>| {
+        matches!(
+            result,
+            Err(Error::Invalid(ValidationError::InvalidPasteId(_)))
+        )
+    };
+    assert!(invalid(client.delete_paste("../users/@me").await.map(drop)));
+    assert!(invalid(
+        client
+            .delete_pastes(vec![paste.id.as_str(), "../users/@me"])
+            .await
+            .map(drop)
+    ));
+    assert!(invalid(client.create_bookmark("../users/@me").await));
+    assert!(invalid(client.delete_bookmark("../users/@me").await));
+    assert!(invalid(
+        client.toggle_bookmark("../users/@me").await.map(drop)
+    ));
+    assert!(invalid(client.has_bookmark("../users/@me").await.map(drop)));
+    // nothing was deleted by the rejected batch
+    assert_eq!(server.paste_count(), 1);
+
+    let url = format!("https://mystb.in/{}", paste.id);
+    client.create_bookmark(url.as_str()).await.unwrap();
+    assert!(client.has_bookmark(&paste.id).await.unwrap());
+    client.delete_paste(url).await.unwrap();
+    assert_eq!(server.paste_count(), 0);
+}
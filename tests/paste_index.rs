@@ -0,0 +1,100 @@
+use myust::{store::PasteIndex, test_server::TestServer, Client};
+
+#[tokio::test]
+async fn sync_paste_index() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let notes = client
+        .create_paste(|p| p.filename("notes.md").content("TODO: write docs"))
+        .await
+        .unwrap();
+    let code = client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("main.rs").content("fn main() {}"));
+            p.file(|f| f.filename("lib.rs").content("pub mod api;"))
+        })
+        .await
+        .unwrap();
+    let locked = client
+        .create_paste(|p| p.filename("locked.txt").content("secret").password("pw"))
+        .await
+        .unwrap();
+    client.create_bookmark(&notes.id).await.unwrap();
+
+    let index = PasteIndex::open_in_memory().unwrap();
+    let sync = client.sync_index(&index).await.unwrap();
+    assert_eq!(sync.added.len(), 3);
+    assert_eq!(sync.bookmarks, 1);
+    assert_eq!(sync.failed.len(), 1);
+    assert_eq!(sync.failed[0].0, locked.id);
+
+    assert_eq!(index.pastes().unwrap().len(), 3);
+    let found = index.search("todo").unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, notes.id);
+    assert_eq!(
+        index.search("lib.rs").unwrap()[0].files,
+        ["main.rs", "lib.rs"]
+    );
+    assert!(index.search("100%").unwrap().is_empty());
+    assert_eq!(
+        index.get(&code.id).unwrap().unwrap().files[1].content,
+        "pub mod api;"
+    );
+    assert!(index.get(&locked.id).unwrap().unwrap().files.is_empty());
+    assert!(index.is_bookmarked(&notes.id).unwrap());
+    assert_eq!(index.bookmarks().unwrap()[0].origin.as_deref(), Some("api"));
+
+    client.delete_paste(&code.id).await.unwrap();
+    client.delete_bookmark(&notes.id).await.unwrap();
+    let sync = client.sync_index(&index).await.unwrap();
+    assert!(sync.added.is_empty());
+    assert_eq!(sync.removed, [code.id.as_str()]);
+    assert_eq!(sync.failed.len(), 1);
+    assert!(index.get(&code.id).unwrap().is_none());
+    assert!(index.bookmarks().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn tag_indexed_pastes() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let deployment = client
+        .create_paste(|p| p.filename("deployment.yaml").content("kind: Deployment"))
+        .await
+        .unwrap();
+    let service = client
+        .create_paste(|p| p.filename("service.yaml").content("kind: Service"))
+        .await
+        .unwrap();
+
+    let index = PasteIndex::open_in_memory().unwrap();
+    client.sync_index(&index).await.unwrap();
+    index.tag(&deployment.id, "k8s").unwrap();
+    index.tag(&deployment.id, "k8s").unwrap();
+    index.tag(&deployment.id, "prod").unwrap();
+    index.tag(&service.id, "k8s").unwrap();
+
+    assert_eq!(index.tagged("k8s").unwrap().len(), 2);
+    let prod = index.tagged("prod").unwrap();
+    assert_eq!(prod.len(), 1);
+    assert_eq!(prod[0].tags, ["k8s", "prod"]);
+    assert_eq!(
+        index.tags().unwrap(),
+        [("k8s".to_string(), 2), ("prod".to_string(), 1)]
+    );
+
+    assert!(index.untag(&deployment.id, "prod").unwrap());
+    assert!(!index.untag(&deployment.id, "prod").unwrap());
+    assert!(index.tagged("prod").unwrap().is_empty());
+
+    client.delete_paste(&service.id).await.unwrap();
+    client.sync_index(&index).await.unwrap();
+    assert_eq!(index.tags().unwrap(), [("k8s".to_string(), 1)]);
+}
@@ -0,0 +1,99 @@
+use myust::{test_server::TestServer, Client, Error, LockedFile};
+
+#[tokio::test]
+async fn wrong_password() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi").password("myust"))
+        .await
+        .unwrap();
+
+    let err = client
+        .get_paste(|p| p.id(&paste.id).password("wrong"))
+        .await
+        .unwrap_err();
+    assert!(matches!(&err, Error::WrongPassword(id) if id == &paste.id));
+    assert!(!err.is_not_found());
+
+    let err = client
+        .get_paste(|p| p.id("MissingPaste").password("myust"))
+        .await
+        .unwrap_err();
+    assert!(matches!(&err, Error::Api(err) if err.code == 404));
+
+    let found = client
+        .get_paste(|p| p.id(&paste.id).password("myust"))
+        .await
+        .unwrap();
+    assert_eq!(found.files[0].content, "hi");
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn wrong_password_sync() {
+    let server = TestServer::start();
+    let client = myust::SyncClient::new().base_url(server.url());
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi").password("myust"))
+        .unwrap();
+
+    let err = client
+        .get_paste(|p| p.id(&paste.id).password("wrong"))
+        .unwrap_err();
+    assert!(matches!(err, Error::WrongPassword(_)));
+}
+
+#[tokio::test]
+async fn password_prompt() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("hi").password("myust"))
+        .await
+        .unwrap();
+
+    let err = client.get_paste(|p| p.id(&paste.id)).await.unwrap_err();
+    let locked = match err {
+        Error::PasteRequiresPassword(locked) => locked,
+        err => panic!("unexpected error: {err:?}"),
+    };
+    assert_eq!(locked.id, paste.id);
+    assert_eq!(locked.created_at.as_ref(), Some(&paste.created_at));
+    assert_eq!(
+        locked.files,
+        vec![LockedFile {
+            filename: "myust.txt".to_string(),
+            size: Some(2),
+        }]
+    );
+
+    let found = client
+        .get_paste_with_password_prompt(&paste.id, |locked| {
+            assert_eq!(locked.files[0].filename, "myust.txt");
+            Some("myust".to_string())
+        })
+        .await
+        .unwrap();
+    assert_eq!(found.files[0].content, "hi");
+
+    let err = client
+        .get_paste_with_password_prompt(&paste.id, |_| Some("wrong".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::WrongPassword(_)));
+    let err = client
+        .get_paste_with_password_prompt(&paste.id, |_| None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::PasteRequiresPassword(_)));
+
+    let open = client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    client
+        .get_paste_with_password_prompt(&open.id, |_| panic!("the paste has no password"))
+        .await
+        .unwrap();
+}
@@ -0,0 +1,79 @@
+use myust::{
+    template::{PasswordPolicy, PasteTemplate},
+    test_server::TestServer,
+    Error, Expiry, ValidationError,
+};
+
+#[tokio::test]
+async fn paste_templates() {
+    let server = TestServer::start();
+    let client = myust::Client::new().base_url(server.url());
+    let template = PasteTemplate::new()
+        .filename("incident-{date}.log")
+        .expires(Expiry::ONE_DAY)
+        .header("== incident ==")
+        .footer("== end ==");
+
+    let paste = client
+        .create_paste_with_template(&template, |p| p.content("disk full\n"))
+        .await
+        .unwrap();
+    let filename = &paste.files[0].filename;
+    assert!(filename.starts_with("incident-20"), "{}", filename);
+    assert_eq!(filename.len(), "incident-YYYY-MM-DD.log".len());
+    assert_eq!(
+        paste.files[0].content,
+        "== incident ==\ndisk full\n== end =="
+    );
+    assert!(paste.expires.is_some());
+
+    let paste = client
+        .create_paste_with_template(&template, |p| {
+            p.filename("oom.log")
+                .content("out of memory")
+                .expires(Expiry::never())
+        })
+        .await
+        .unwrap();
+    assert_eq!(paste.files[0].filename, "oom.log");
+    assert!(paste.expires.is_none());
+    assert_eq!(server.paste_count(), 2);
+}
+
+#[tokio::test]
+async fn password_policy() {
+    let server = TestServer::start();
+    let client = myust::Client::new().base_url(server.url());
+
+    let required = PasteTemplate::new().password_policy(PasswordPolicy::Required);
+    let result = client
+        .create_paste_with_template(&required, |p| p.filename("a.txt").content("a"))
+        .await;
+    assert!(matches!(
+        result,
+        Err(Error::Invalid(ValidationError::PasswordRequired))
+    ));
+
+    let forbidden = PasteTemplate::new().password_policy(PasswordPolicy::Forbidden);
+    let result = client
+        .create_paste_with_template(&forbidden, |p| {
+            p.filename("a.txt").content("a").password("secret")
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(Error::Invalid(ValidationError::PasswordForbidden))
+    ));
+    assert_eq!(server.paste_count(), 0);
+}
+
+#[test]
+fn filename_at() {
+    let template = PasteTemplate::new().filename("log-{date}-{time}.txt");
+    let time = humantime::parse_rfc3339("2023-04-01T13:37:00Z").unwrap();
+    assert_eq!(
+        template.filename_at(time).unwrap(),
+        "log-2023-04-01-13-37-00.txt"
+    );
+    assert_eq!(PasteTemplate::new().filename_at(time), None);
+}
@@ -0,0 +1,88 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+use myust::{
+    pastebin::{PastebinProvider, Privacy},
+    CreatePasteRequest, Error, Expiry, PasteBuilder, PasteProvider,
+};
+
+/// Answer one request with `response`, returning the request line, headers and body.
+fn serve_once(response: &'static str) -> (String, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+            if line == "\r\n" {
+                break;
+            }
+            head.push_str(&line);
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+        head + "\r\n" + &String::from_utf8(body).unwrap()
+    });
+    (url, handle)
+}
+
+#[tokio::test]
+async fn pastebin() {
+    let (url, server) = serve_once("https://pastebin.com/UIFdu235s");
+    let pastebin = PastebinProvider::new("devkey")
+        .base_url(url)
+        .privacy(Privacy::Public);
+    let mut paste = PasteBuilder::default();
+    paste.filename("notes.txt").content("hi").expires(Expiry {
+        hours: 2,
+        ..Default::default()
+    });
+    let paste = pastebin
+        .create(CreatePasteRequest::from(paste))
+        .await
+        .unwrap();
+    assert_eq!(paste.id, "UIFdu235s");
+    let sent = server.join().unwrap();
+    assert!(sent.starts_with("POST /api/api_post.php HTTP/1.1"));
+    assert!(sent.contains("api_option=paste&api_paste_code=hi&api_paste_name=notes.txt"));
+    assert!(sent.contains("api_paste_private=0&api_paste_expire_date=1D&api_dev_key=devkey"));
+
+    let (url, server) = serve_once("Bad API request, invalid api_dev_key");
+    let pastebin = PastebinProvider::new("wrong")
+        .base_url(url)
+        .user_key("user");
+    let err = pastebin.delete("UIFdu235s").await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Provider {
+            provider: "pastebin",
+            ..
+        }
+    ));
+    assert!(server.join().unwrap().contains("api_user_key=user"));
+
+    let err = PastebinProvider::new("devkey")
+        .delete("UIFdu235s")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    let debug = format!("{:?}", PastebinProvider::new("devkey").user_key("user"));
+    assert!(!debug.contains("devkey") && !debug.contains("user\""));
+}
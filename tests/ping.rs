@@ -0,0 +1,92 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use myust::{
+    test_server::TestServer, Client, Error, Interceptor, OutgoingRequest, StatusCode, SyncClient,
+};
+
+/// Answer every request with a 503.
+fn serve_unavailable() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let body = r#"{"error": "down for maintenance"}"#;
+            write!(
+                stream,
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<String>>);
+
+impl Interceptor for Recorder {
+    fn on_request(&self, request: &mut OutgoingRequest<'_>) {
+        self.0.lock().unwrap().push(request.url.path().to_string());
+    }
+}
+
+#[tokio::test]
+async fn ping() {
+    let server = TestServer::start();
+    let seen = Arc::new(Mutex::new(vec![]));
+    let metrics = seen.clone();
+    let recorder = Arc::new(Recorder::default());
+    let client = Client::new()
+        .base_url(server.url())
+        .interceptor(recorder.clone())
+        .metrics(move |endpoint: &str, _, _| {
+            metrics.lock().unwrap().push(endpoint.to_string());
+        });
+    assert!(client.ping().await.is_ok());
+    assert_eq!(*seen.lock().unwrap(), vec!["GET /".to_string()]);
+    assert_eq!(*recorder.0.lock().unwrap(), vec!["/".to_string()]);
+
+    let err = Client::new()
+        .base_url(serve_unavailable())
+        .ping()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Api(_)));
+    assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+
+    let url = server.url();
+    drop(server);
+    let err = Client::new().base_url(url).ping().await.unwrap_err();
+    assert!(matches!(err, Error::Connect(_)));
+}
+
+#[test]
+fn sync_ping() {
+    let server = TestServer::start();
+    let client = SyncClient::new().base_url(server.url());
+    assert!(client.ping().is_ok());
+
+    let err = SyncClient::new()
+        .base_url(serve_unavailable())
+        .ping()
+        .unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+
+    let url = server.url();
+    drop(server);
+    let err = SyncClient::new().base_url(url).ping().unwrap_err();
+    assert!(matches!(err, Error::Connect(_)));
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use myust::{test_server::TestServer, Client, CreatePasteRequest, Error, File, PasteProvider};
+
+async fn round_trip(provider: &dyn PasteProvider) -> Result<String, Error> {
+    let paste = provider
+        .create(CreatePasteRequest {
+            files: vec![File {
+                filename: "myust.txt".to_string(),
+                content: "Hello from myust!".to_string(),
+                ..Default::default()
+            }],
+            password: Some("hunter2".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let fetched = provider.get(&paste.id, Some("hunter2")).await?;
+    assert_eq!(fetched.files[0].content, "Hello from myust!");
+    provider.delete(&paste.id).await?;
+    Ok(paste.id)
+}
+
+#[tokio::test]
+async fn provider() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let provider: Arc<dyn PasteProvider> = Arc::new(client.web_url("https://paste.example/"));
+    assert_eq!(provider.name(), "mystbin");
+
+    let id = round_trip(&provider).await.unwrap();
+    assert_eq!(server.paste_count(), 0);
+    assert_eq!(
+        provider.url_for(&id),
+        format!("https://paste.example/{}", id)
+    );
+
+    let err = provider.get(&id, None).await.unwrap_err();
+    assert!(matches!(err, Error::Api(err) if err.code == 404));
+    assert_eq!(
+        Client::new().paste_url("FakePaste1"),
+        "https://mystb.in/FakePaste1"
+    );
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use myust::{test_server::TestServer, Expiry};
+
+#[tokio::test]
+async fn prune_bookmarks() {
+    let server = TestServer::start();
+    let client = myust::Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let alive = client
+        .create_paste(|p| p.filename("alive.txt").content("alive"))
+        .await
+        .unwrap();
+    let locked = client
+        .create_paste(|p| {
+            p.filename("locked.txt")
+                .content("locked")
+                .password("secret")
+        })
+        .await
+        .unwrap();
+    let expiring = client
+        .create_paste(|p| {
+            p.filename("expiring.txt")
+                .content("expiring")
+                .expires(Expiry {
+                    seconds: 1,
+                    ..Default::default()
+                })
+        })
+        .await
+        .unwrap();
+    for paste in [&alive, &locked, &expiring] {
+        client.create_bookmark(&paste.id).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(2100)).await;
+
+    let report = client.prune_bookmarks().await.unwrap();
+    assert_eq!(report.pruned, vec![expiring.id.clone()]);
+    assert_eq!(report.kept, vec![alive.id.clone(), locked.id.clone()]);
+    assert!(report.failed.is_empty());
+    assert!(!client.has_bookmark(&expiring.id).await.unwrap());
+    assert!(client.has_bookmark(&alive.id).await.unwrap());
+}
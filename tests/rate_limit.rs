@@ -0,0 +1,77 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+};
+
+use myust::{test_server::TestServer, Client};
+
+/// Answer every request with an empty listing and these rate limit headers.
+fn serve_rate_limit(limit: &'static str, remaining: &'static str, reset: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let body = r#"{"pastes": []}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nX-Ratelimit-Limit: {}\r\nX-Ratelimit-Remaining: {}\r\n\
+                 X-Ratelimit-Reset: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                limit,
+                remaining,
+                reset,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn rate_limit_status() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    assert!(client.rate_limit_status().is_none());
+    client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    client
+        .create_paste(|p| p.filename("myust.txt").content("hi"))
+        .await
+        .unwrap();
+    let status = client.clone().rate_limit_status().unwrap();
+    assert_eq!(status.limit, Some(TestServer::RATE_LIMIT));
+    assert_eq!(status.remaining, TestServer::RATE_LIMIT - 2);
+    assert!(status.reset.is_some());
+}
+
+#[tokio::test]
+async fn malformed_reset() {
+    for (reset, kept) in [
+        ("inf", false),
+        ("1e30", false),
+        ("1.8e19", false),
+        // negative resets and NaN are taken as "now"
+        ("-inf", true),
+        ("-1e30", true),
+        ("NaN", true),
+    ] {
+        let client = Client::new()
+            .base_url(serve_rate_limit("100", "42", reset))
+            .auth("token")
+            .await;
+        client.get_user_pastes(|p| p).await.unwrap();
+        let status = client.rate_limit_status().unwrap();
+        assert_eq!(status.remaining, 42, "{reset}");
+        assert_eq!(status.reset.is_some(), kept, "{reset}");
+    }
+}
@@ -1,8 +1,9 @@
-use myust::{Client, Expiry};
+use myust::{test_server::TestServer, Client, Expiry};
 
 #[tokio::test]
 async fn main() {
-    let client = Client::new();
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
     let tomorrow = Expiry {
         days: 1,
         ..Default::default()
@@ -22,7 +23,7 @@ async fn main() {
             println!("Result: {}", url)
         }
         Err(_) => {
-            println!("Error code: {}", result.unwrap_err().code)
+            println!("Error: {}", result.unwrap_err())
         }
     }
 }
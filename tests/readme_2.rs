@@ -1,15 +1,22 @@
-use myust::Client;
+use myust::{test_server::TestServer, Client};
 
 #[tokio::test]
 async fn main() {
+    let server = TestServer::start();
     let client = Client::new()
-        .auth(std::env::var("MYSTBIN_TOKEN").unwrap())
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
         .await;
-    let result = client.delete_paste("EquipmentMovingExpensive").await; // The paste ID to delete
+    let paste = client
+        .create_paste(|p| p.filename("myust.txt").content("delete me"))
+        .await
+        .unwrap();
+    let result = client.delete_paste(&paste.id).await; // The paste ID to delete
     match result {
         Ok(_) => println!("Successfully deleted the paste."),
         Err(_) => {
-            println!("Error code: {}", result.unwrap_err().code)
+            println!("Error code: {:?}", result.unwrap_err().status())
         }
     }
+    assert_eq!(server.paste_count(), 0);
 }
@@ -1,9 +1,11 @@
 #![cfg(feature = "sync")]
 
-use myust::SyncClient;
+use myust::{test_server::TestServer, SyncClient};
 
+#[test]
 fn main() {
-    let client = SyncClient::new();
+    let server = TestServer::start();
+    let client = SyncClient::new().base_url(server.url());
     let paste = client
         .create_multifile_paste(|p| {
             p.file(|f| {
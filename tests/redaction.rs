@@ -0,0 +1,36 @@
+use myust::{GetPasteBuilder, PasteBuilder, PastesBuilder};
+
+#[test]
+fn redaction() {
+    let mut paste = PasteBuilder::default();
+    paste
+        .filename("myust.txt")
+        .content("hi")
+        .password("hunter2");
+    let debug = format!("{paste:?}");
+    assert!(!debug.contains("hunter2"));
+    assert!(debug.contains("***"));
+
+    let mut pastes = PastesBuilder::default();
+    pastes.file(|f| f.filename("myust.txt").content("hi").password("hunter2"));
+    assert!(!format!("{pastes:#?}").contains("hunter2"));
+
+    let mut get = GetPasteBuilder::default();
+    get.id("FakePaste1").password("hunter2");
+    assert!(!format!("{get:?}").contains("hunter2"));
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn client_debug() {
+    use myust::{test_server::TestServer, SyncClient};
+
+    let server = TestServer::start();
+    let client = SyncClient::new().base_url(server.url());
+    assert!(format!("{client:?}").contains("authenticated: false"));
+    let client = client.auth(TestServer::TOKEN);
+    let debug = format!("{client:?}");
+    assert!(debug.contains("authenticated: true"));
+    assert!(debug.contains(&server.url()));
+    assert!(!debug.contains(TestServer::TOKEN));
+}
@@ -0,0 +1,78 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+};
+
+use myust::{test_server::TestServer, Client, Error, HttpOptions};
+
+/// Redirect every request to the same path on `target`, like an alternate domain.
+fn serve_redirects(target: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let path = request_line.split(' ').nth(1).unwrap().to_string();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 308 Permanent Redirect\r\nLocation: {}{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                target, path
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn redirects() {
+    let server = TestServer::start();
+    let redirecting = serve_redirects(server.url());
+    let paste = Client::new()
+        .base_url(server.url())
+        .create_paste(|p| p.filename("myust.txt").content("hi").password("myust"))
+        .await
+        .unwrap();
+
+    let client = Client::new().base_url(&redirecting);
+    let found = client
+        .get_paste(|p| p.id(&paste.id).password("myust"))
+        .await
+        .unwrap();
+    assert_eq!(found.files[0].content, "hi");
+    let last_url = client.last_url().unwrap();
+    assert!(last_url.as_str().starts_with(&server.url()));
+    assert!(!last_url.as_str().contains("password=myust"));
+
+    let err = client
+        .get_paste(|p| p.id("MissingPaste"))
+        .await
+        .unwrap_err();
+    let Error::Api(err) = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    let url = err.response.unwrap().url.unwrap();
+    assert_eq!(url.as_str(), format!("{}/paste/MissingPaste", server.url()));
+
+    let client = Client::new()
+        .base_url(&redirecting)
+        .http_options(HttpOptions {
+            max_redirects: Some(0),
+            ..Default::default()
+        });
+    let err = client.get_paste(|p| p.id(&paste.id)).await.unwrap_err();
+    assert!(matches!(&err, Error::Api(err) if err.code == 308));
+    assert!(client
+        .last_url()
+        .unwrap()
+        .as_str()
+        .starts_with(&redirecting));
+}
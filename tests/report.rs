@@ -0,0 +1,44 @@
+use myust::{
+    report::{CreateReport, DeleteReport},
+    DeleteResult, File, PasteResult,
+};
+use serde_json::json;
+
+#[test]
+fn create_report() {
+    let paste = PasteResult {
+        created_at: "2024-01-01T00:00:00+00:00".to_string(),
+        expires: None,
+        files: vec![File {
+            filename: "myust.txt".to_string(),
+            content: "Hello from myust!".to_string(),
+            ..Default::default()
+        }],
+        id: "EquipmentMovingExpensive".to_string(),
+        idempotency_key: None,
+        line_endings: None,
+    };
+    let report = CreateReport::new(&paste, "https://mystb.in/EquipmentMovingExpensive");
+    assert_eq!(
+        serde_json::to_value(&report).unwrap(),
+        json!({
+            "id": "EquipmentMovingExpensive",
+            "url": "https://mystb.in/EquipmentMovingExpensive",
+            "created_at": "2024-01-01T00:00:00+00:00",
+            "expires": null,
+            "files": ["myust.txt"],
+        })
+    );
+}
+
+#[test]
+fn delete_report() {
+    let report = DeleteReport::from(DeleteResult {
+        succeeded: Some(vec!["First".to_string()]),
+        failed: None,
+    });
+    assert_eq!(
+        serde_json::to_value(&report).unwrap(),
+        json!({ "deleted": ["First"], "failed": [] })
+    );
+}
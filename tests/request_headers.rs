@@ -0,0 +1,136 @@
+use std::sync::{Arc, Mutex};
+
+use myust::{
+    test_server::TestServer, Client, HeaderValue, Interceptor, OutgoingRequest, SyncClient,
+};
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<Option<String>>>);
+
+impl Interceptor for Recorder {
+    fn on_request(&self, request: &mut OutgoingRequest<'_>) {
+        let trace = request
+            .headers
+            .get("x-trace-id")
+            .map(|value| value.to_str().unwrap().to_string());
+        self.0.lock().unwrap().push(trace);
+    }
+}
+
+#[tokio::test]
+async fn per_call_headers() {
+    let server = TestServer::start();
+    let recorder = Arc::new(Recorder::default());
+    let client = Client::new()
+        .base_url(server.url())
+        .interceptor(recorder.clone())
+        .auth(TestServer::TOKEN)
+        .await;
+    let paste = client
+        .create_paste(|p| {
+            p.filename("myust.txt")
+                .content("hi")
+                .header("x-trace-id", HeaderValue::from_static("create"))
+        })
+        .await
+        .unwrap();
+    client
+        .get_paste(|p| {
+            p.id(&paste.id)
+                .header("x-trace-id", HeaderValue::from_static("get"))
+        })
+        .await
+        .unwrap();
+    client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("a.txt")
+                    .content("a")
+                    .header("x-trace-id", HeaderValue::from_static("multi"))
+            })
+            .file(|f| f.filename("b.txt").content("b"))
+        })
+        .await
+        .unwrap();
+    // the authorization of the client is kept alongside the extra header
+    let pastes = client
+        .get_user_pastes(|p| p.header("x-trace-id", HeaderValue::from_static("list")))
+        .await
+        .unwrap();
+    assert_eq!(pastes.len(), 2);
+    client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+
+    let traces = recorder.0.lock().unwrap();
+    assert_eq!(
+        *traces,
+        [
+            Some("create".to_string()),
+            Some("get".to_string()),
+            Some("multi".to_string()),
+            Some("list".to_string()),
+            None,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn client_headers_not_duplicated() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let pastes = client
+        .get_user_pastes(|p| {
+            p.header("authorization", HeaderValue::from_static("stale"))
+                .header("x-request-id", HeaderValue::from_static("mine"))
+                .header("x-trace-id", HeaderValue::from_static("first"))
+                .header("x-trace-id", HeaderValue::from_static("second"))
+        })
+        .await
+        .unwrap();
+    assert!(pastes.is_empty());
+
+    let requests = server.requests();
+    let request = requests.last().unwrap();
+    let values = |name: &str| {
+        request
+            .headers
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        values("authorization"),
+        [format!("Bearer {}", TestServer::TOKEN)]
+    );
+    assert_eq!(values("x-request-id").len(), 1);
+    assert_ne!(values("x-request-id"), ["mine"]);
+    assert_eq!(values("x-trace-id"), ["second"]);
+}
+
+#[test]
+fn sync_client_headers_not_duplicated() {
+    let server = TestServer::start();
+    let client = SyncClient::new()
+        .base_url(server.url())
+        .try_auth(TestServer::TOKEN)
+        .unwrap();
+    client
+        .create_paste(|p| {
+            p.filename("myust.txt")
+                .content("hi")
+                .header("x-request-id", HeaderValue::from_static("mine"))
+        })
+        .unwrap();
+    let requests = server.requests();
+    let request = requests.last().unwrap();
+    let request_ids: Vec<_> = request
+        .headers
+        .iter()
+        .filter(|(name, _)| name == "x-request-id")
+        .collect();
+    assert_eq!(request_ids.len(), 1);
+    assert_ne!(request_ids[0].1, "mine");
+}
@@ -0,0 +1,92 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use futures_util::future::join_all;
+use myust::{Client, RequestLimiter};
+
+/// Answer every request with a 404 after a while, recording the most connections
+/// handled at once.
+fn serve_slowly(max_concurrent: Arc<AtomicUsize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let current = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let current = current.clone();
+            let max_concurrent = max_concurrent.clone();
+            thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                max_concurrent.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(50));
+                current.fetch_sub(1, Ordering::SeqCst);
+                let body = r#"{"error":"Paste not found"}"#;
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            });
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn request_limiter() {
+    let max_concurrent = Arc::new(AtomicUsize::new(0));
+    let url = serve_slowly(max_concurrent.clone());
+    let limiter = RequestLimiter::fifo(2);
+    let client = Client::new()
+        .base_url(&url)
+        .request_limiter(limiter.clone());
+    // another client sharing the limiter counts towards the same limit
+    let other = client
+        .share_pool()
+        .base_url(&url)
+        .request_limiter(limiter.clone());
+
+    let requests = (0..6).map(|i| {
+        let client = if i % 2 == 0 { &client } else { &other };
+        client.get_paste(move |p| p.id(format!("Missing{i}")))
+    });
+    let results = join_all(requests).await;
+    assert!(results.iter().all(|result| result.is_err()));
+    assert_eq!(max_concurrent.load(Ordering::SeqCst), 2);
+    assert_eq!(limiter.in_flight(), 0);
+    assert_eq!(limiter.waiting(), 0);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn sync_request_limiter() {
+    let max_concurrent = Arc::new(AtomicUsize::new(0));
+    let url = serve_slowly(max_concurrent.clone());
+    let limiter = RequestLimiter::new(1);
+    let client = myust::SyncClient::new()
+        .base_url(&url)
+        .request_limiter(limiter.clone());
+
+    thread::scope(|scope| {
+        for i in 0..3 {
+            let client = &client;
+            scope.spawn(move || client.get_paste(|p| p.id(format!("Missing{i}"))));
+        }
+    });
+    assert_eq!(max_concurrent.load(Ordering::SeqCst), 1);
+    assert_eq!(limiter.in_flight(), 0);
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use myust::{retention::RetentionPolicy, test_server::TestServer, Client};
+
+#[tokio::test]
+async fn retention_policy() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let mut ids = vec![];
+    for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+        let paste = client
+            .create_paste(|p| p.filename(name).content(name))
+            .await
+            .unwrap();
+        ids.push(paste.id);
+    }
+    client.create_bookmark(&ids[0]).await.unwrap();
+
+    let report = client
+        .apply_retention(&RetentionPolicy::new())
+        .await
+        .unwrap();
+    assert!(report.deleted.is_empty());
+    let week = Duration::from_secs(7 * 24 * 60 * 60);
+    let report = client
+        .apply_retention(&RetentionPolicy::new().max_age(week))
+        .await
+        .unwrap();
+    assert!(report.deleted.is_empty());
+
+    let policy = RetentionPolicy::new()
+        .max_count(2)
+        .keep_bookmarked(true)
+        .dry_run(true);
+    let report = client.apply_retention(&policy).await.unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.deleted.len(), 1);
+    assert!(report.kept.contains(&ids[0]));
+    assert_eq!(server.paste_count(), 4);
+
+    let report = client
+        .apply_retention(&policy.dry_run(false))
+        .await
+        .unwrap();
+    assert_eq!(report.deleted.len(), 1);
+    assert!(report.failed.is_empty());
+    assert_eq!(server.paste_count(), 3);
+}
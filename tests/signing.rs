@@ -0,0 +1,43 @@
+use myust::{
+    signing::{SigningKey, SIGNATURE_FILENAME},
+    test_server::TestServer,
+    Client, Error,
+};
+
+#[tokio::test]
+async fn signing() {
+    let server = TestServer::start();
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let verifying_key = key.verifying_key();
+    let author = Client::new().base_url(server.url()).signing_key(key);
+    let paste = author
+        .create_paste(|p| p.filename("install.sh").content("echo hi"))
+        .await
+        .unwrap();
+    assert_eq!(paste.files[1].filename, SIGNATURE_FILENAME);
+
+    let reader = Client::new().base_url(server.url());
+    let verified = reader
+        .get_paste_verified(|p| p.id(&paste.id), &verifying_key)
+        .await
+        .unwrap();
+    assert_eq!(verified.files.len(), 1);
+    assert_eq!(verified.files[0].content, "echo hi");
+
+    let someone_else = SigningKey::from_bytes(&[8; 32]).verifying_key();
+    let err = reader
+        .get_paste_verified(|p| p.id(&paste.id), &someone_else)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::BadSignature));
+
+    let unsigned = reader
+        .create_paste(|p| p.filename("install.sh").content("echo hi"))
+        .await
+        .unwrap();
+    let err = reader
+        .get_paste_verified(|p| p.id(&unsigned.id), &verifying_key)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Unsigned));
+}
@@ -0,0 +1,44 @@
+use myust::{File, MAX_FILE_CHARACTERS};
+
+#[test]
+fn split_at_line_breaks() {
+    let line = "x".repeat(999) + "\n";
+    let file = File {
+        filename: "build.log".to_string(),
+        content: line.repeat(MAX_FILE_CHARACTERS / 1000 * 2 + 1),
+        ..Default::default()
+    };
+    let content = file.content.clone();
+
+    let parts = file.split();
+    let filenames: Vec<_> = parts.iter().map(|f| f.filename.as_str()).collect();
+    assert_eq!(filenames, ["build-1.log", "build-2.log", "build-3.log"]);
+    assert!(parts.iter().all(|part| part.validate().is_ok()));
+    assert!(parts.iter().all(|part| part.content.ends_with('\n')));
+    let joined: String = parts.iter().map(|part| part.content.as_str()).collect();
+    assert_eq!(joined, content);
+}
+
+#[test]
+fn split_long_line() {
+    let file = File {
+        filename: "minified".to_string(),
+        content: "é".repeat(MAX_FILE_CHARACTERS + 1),
+        ..Default::default()
+    };
+    let parts = file.split();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].filename, "minified-1");
+    assert_eq!(parts[0].content.chars().count(), MAX_FILE_CHARACTERS);
+    assert_eq!(parts[1].content, "é");
+}
+
+#[test]
+fn split_small_file() {
+    let file = File {
+        filename: "notes.txt".to_string(),
+        content: "short".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(file.clone().split(), [file]);
+}
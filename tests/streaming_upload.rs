@@ -0,0 +1,61 @@
+use std::io::Cursor;
+
+use myust::{test_server::TestServer, Client};
+
+fn content() -> String {
+    "é \"quoted\" \\ tab\t ✓\n".repeat(5_000)
+}
+
+#[tokio::test]
+async fn content_reader() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let paste = client
+        .create_paste(|p| {
+            p.filename("build.log")
+                .content_reader(Cursor::new(content().into_bytes()))
+                .password("myust")
+        })
+        .await
+        .unwrap();
+    assert_eq!(paste.files[0].content, content());
+
+    let fetched = client
+        .get_paste(|p| p.id(&paste.id).password("myust"))
+        .await
+        .unwrap();
+    assert_eq!(fetched.files[0].filename, "build.log");
+    assert_eq!(fetched.files[0].content, content());
+}
+
+#[tokio::test]
+async fn multifile_blocking_reader() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let paste = client
+        .create_multifile_paste(|p| {
+            p.file(|f| f.filename("a.txt").content("in memory"))
+                .file(|f| {
+                    f.filename("b.txt")
+                        .content_blocking_reader(Cursor::new(b"from a \xffreader".to_vec()))
+                })
+        })
+        .await
+        .unwrap();
+    assert_eq!(paste.files[0].content, "in memory");
+    assert_eq!(paste.files[1].content, "from a \u{FFFD}reader");
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn content_reader_sync() {
+    let server = TestServer::start();
+    let client = myust::SyncClient::new().base_url(server.url());
+    let paste = client
+        .create_paste(|p| {
+            p.filename("build.log")
+                .content_blocking_reader(Cursor::new(content().into_bytes()))
+        })
+        .unwrap();
+    assert_eq!(paste.files[0].content, content());
+}
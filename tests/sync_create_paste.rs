@@ -1,9 +1,18 @@
 #[cfg(feature = "sync")]
 #[test]
 fn create_paste_sync() {
-    let client = myust::SyncClient::new().auth(std::env::var("MYSTBIN_TOKEN").unwrap());
+    use myust::test_server::TestServer;
+
+    let server = TestServer::start();
+    let client = myust::SyncClient::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN);
     let paste = client
-        .create_paste(|p| p.filename("myust.txt").content("hi from myust").expires(myust::Expiry::default()))
+        .create_paste(|p| {
+            p.filename("myust.txt")
+                .content("hi from myust")
+                .expires(myust::Expiry::never())
+        })
         .unwrap();
     let result = client.get_paste(|p| p.id(paste.id)).unwrap();
     println!("{result:#?}")
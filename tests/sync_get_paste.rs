@@ -1,9 +1,31 @@
 #[cfg(feature = "sync")]
 #[test]
 fn get_paste_sync() {
-    let client = myust::SyncClient::new();
+    use myust::test_server::TestServer;
+
+    let server = TestServer::start();
+    let client = myust::SyncClient::new().base_url(server.url());
+    let created = client
+        .create_paste(|p| p.filename("myust.txt").content("hi").password("myust"))
+        .unwrap();
     let paste = client
-        .get_paste(|p| p.id("GarminDosageExists").password("myust"))
+        .get_paste(|p| p.id(&created.id).password("myust"))
         .unwrap();
     println!("{paste:#?}");
+    assert!(client.get_paste(|p| p.id(&created.id)).is_err());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn get_missing_paste_sync() {
+    use myust::test_server::TestServer;
+
+    let server = TestServer::start();
+    let client = myust::SyncClient::new().base_url(server.url());
+    let err = match client.get_paste(|p| p.id("MissingPaste")) {
+        Err(myust::Error::Api(err)) => err,
+        result => panic!("unexpected result: {result:?}"),
+    };
+    assert_eq!(err.code, 404);
+    assert!(err.request_id.unwrap().starts_with("myust-"));
 }
@@ -0,0 +1,29 @@
+use myust::{test_server::TestServer, Client};
+
+#[tokio::test]
+async fn syntax_hint() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let paste = client
+        .create_paste(|p| p.filename("main").content("fn main() {}").syntax("Rust"))
+        .await
+        .unwrap();
+    assert_eq!(paste.files[0].filename, "main.rs");
+    assert_eq!(paste.files[0].syntax.as_deref(), Some("Rust"));
+
+    // the API doesn't store the hint
+    let fetched = client.get_paste(|p| p.id(&paste.id)).await.unwrap();
+    assert_eq!(fetched.files[0].filename, "main.rs");
+    assert_eq!(fetched.files[0].syntax, None);
+
+    let paste = client
+        .create_paste(|p| p.filename("build.log").content("ok").syntax("rust"))
+        .await
+        .unwrap();
+    assert_eq!(paste.files[0].filename, "build.log");
+    let paste = client
+        .create_paste(|p| p.filename("deploy").content("ok").syntax("nix"))
+        .await
+        .unwrap();
+    assert_eq!(paste.files[0].filename, "deploy.nix");
+}
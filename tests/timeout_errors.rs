@@ -0,0 +1,47 @@
+use std::{net::TcpListener, thread, time::Duration};
+
+use myust::{Client, Error, HttpOptions, Method, MystbinError, StatusCode};
+
+fn api_error(code: u16) -> Error {
+    MystbinError {
+        code: StatusCode::from_u16(code).unwrap(),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[tokio::test]
+async fn timeout_errors() {
+    // accepts connections but never answers
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        let _streams: Vec<_> = listener.incoming().collect();
+    });
+    let client = Client::new().base_url(url).http_options(HttpOptions {
+        timeout: Some(Duration::from_millis(200)),
+        ..Default::default()
+    });
+    let err = client.get_paste(|p| p.id("SlowPaste")).await.unwrap_err();
+    assert!(matches!(err, Error::Timeout(_)), "{err:?}");
+    assert!(err.to_string().starts_with("request timed out"));
+    assert!(err.is_retryable());
+    assert!(err.is_retryable_for(&Method::PUT));
+    assert!(!err.is_retryable_for(&Method::POST));
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let err = Client::new()
+        .base_url(url)
+        .get_paste(|p| p.id("SomePaste"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Connect(_)), "{err:?}");
+    assert!(err.is_retryable_for(&Method::POST));
+
+    assert!(api_error(429).is_retryable_for(&Method::POST));
+    assert!(!api_error(503).is_retryable_for(&Method::POST));
+    assert!(api_error(503).is_retryable_for(&Method::DELETE));
+    assert!(!api_error(404).is_retryable_for(&Method::GET));
+}
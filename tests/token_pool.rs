@@ -0,0 +1,115 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use myust::{
+    test_server::TestServer, Client, Interceptor, OutgoingRequest, StatusCode, TokenRotation,
+};
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<String>>);
+
+impl Interceptor for Recorder {
+    fn on_request(&self, request: &mut OutgoingRequest<'_>) {
+        let token = request.headers["Authorization"].to_str().unwrap();
+        let token = token.trim_start_matches("Bearer ").to_string();
+        self.0.lock().unwrap().push(token);
+    }
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+#[tokio::test]
+async fn round_robin() {
+    let server = TestServer::start();
+    let recorder = Arc::new(Recorder::default());
+    let client = Client::new()
+        .base_url(server.url())
+        .interceptor(recorder.clone())
+        .token_pool(
+            [TestServer::TOKEN, "second", "third"],
+            TokenRotation::RoundRobin,
+        );
+    let mut pastes = vec![];
+    for _ in 0..4 {
+        let paste = client
+            .create_paste(|p| p.filename("myust.txt").content("hi"))
+            .await
+            .unwrap();
+        pastes.push(paste);
+    }
+    assert_eq!(
+        recorder.take(),
+        [TestServer::TOKEN, "second", "third", TestServer::TOKEN]
+    );
+
+    // user-scoped requests stick to the first token, or to the paste's creator
+    let listed = client.get_user_pastes(|p| p).await.unwrap();
+    assert_eq!(listed.len(), 2);
+    client.delete_paste(&pastes[1].id).await.unwrap();
+    client.create_bookmark(&pastes[2].id).await.unwrap();
+    assert_eq!(
+        recorder.take(),
+        [TestServer::TOKEN, "second", TestServer::TOKEN]
+    );
+    assert_eq!(server.paste_count(), 3);
+}
+
+/// Answer 429 to requests with the `limited` token, and 404 to the others.
+fn serve_limited() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut limited = false;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                limited |= line.eq_ignore_ascii_case("authorization: Bearer limited\r\n");
+                line.clear();
+            }
+            let status = match limited {
+                true => "429 Too Many Requests",
+                false => "404 Not Found",
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn least_recently_limited() {
+    let recorder = Arc::new(Recorder::default());
+    let client = Client::new()
+        .base_url(serve_limited())
+        .interceptor(recorder.clone())
+        .token_pool(["limited", "spare"], TokenRotation::LeastRecentlyLimited);
+    let mut statuses = vec![];
+    for _ in 0..3 {
+        let err = client.get_paste(|p| p.id("Missing")).await.unwrap_err();
+        statuses.push(err.status().unwrap());
+    }
+    assert_eq!(recorder.take(), ["limited", "spare", "spare"]);
+    assert_eq!(
+        statuses,
+        [
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::NOT_FOUND,
+            StatusCode::NOT_FOUND
+        ]
+    );
+}
@@ -1,7 +1,11 @@
+use myust::test_server::TestServer;
+
 #[tokio::test]
 async fn user_bookmarks() {
+    let server = TestServer::start();
     let client = myust::Client::new()
-        .auth(std::env::var("MYSTBIN_TOKEN").unwrap())
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
         .await;
     let bm = client.get_user_bookmarks().await.unwrap();
     println!("{bm:#?}")
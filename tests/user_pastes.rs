@@ -1,8 +1,48 @@
+use myust::test_server::TestServer;
+
 #[tokio::test]
 async fn user_pastes() {
+    let server = TestServer::start();
     let client = myust::Client::new()
-        .auth(std::env::var("MYSTBIN_TOKEN").unwrap())
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
         .await;
+    for i in 0..3 {
+        client
+            .create_paste(|p| p.filename("myust.txt").content(format!("paste {i}")))
+            .await
+            .unwrap();
+    }
     let pastes = client.get_user_pastes(|p| p).await.unwrap();
-    println!("{pastes:#?}")
+    println!("{pastes:#?}");
+    assert_eq!(pastes.len(), 3);
+    let pastes = client
+        .get_user_pastes(|p| p.limit(2).page(2))
+        .await
+        .unwrap();
+    assert_eq!(pastes.len(), 1);
+}
+
+#[tokio::test]
+async fn count_user_pastes() {
+    let server = TestServer::start();
+    let client = myust::Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    assert_eq!(client.count_user_pastes().await.unwrap(), 0);
+    for i in 0..51 {
+        client
+            .create_paste(|p| p.filename("myust.txt").content(format!("paste {i}")))
+            .await
+            .unwrap();
+    }
+    assert_eq!(client.count_user_pastes().await.unwrap(), 51);
+    let page = client
+        .get_user_pastes_page(|p| p.limit(50).page(2))
+        .await
+        .unwrap();
+    assert_eq!(page.page, 2);
+    assert_eq!(page.pastes.len(), 1);
+    assert_eq!(page.total, None);
 }
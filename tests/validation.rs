@@ -0,0 +1,143 @@
+use myust::{
+    Error, Expiry, File, PasteBuilder, PastesBuilder, ValidationError, MAX_FILES,
+    MAX_FILE_CHARACTERS,
+};
+
+#[test]
+fn validation() {
+    let expiry = Expiry {
+        hours: 1,
+        minutes: -5,
+        ..Default::default()
+    };
+    assert!(!expiry.valid());
+    assert_eq!(
+        expiry.validate(),
+        Err(ValidationError::NegativeExpiry {
+            field: "minutes",
+            value: -5
+        })
+    );
+    assert_eq!(
+        Expiry::default().validate(),
+        Err(ValidationError::ZeroExpiry)
+    );
+    assert!(Expiry::never().valid());
+
+    assert!(File::validate_filename("myust.txt").is_ok());
+    assert!(File::validate_filename("../myust.txt").is_err());
+    assert_eq!(
+        File::validate_content(""),
+        Err(ValidationError::EmptyContent)
+    );
+
+    let mut builder = PasteBuilder::default();
+    builder.filename("myust.txt").content("hi").expires(expiry);
+    assert!(builder.validate().is_err());
+
+    let mut builder = PastesBuilder::default();
+    assert_eq!(builder.validate(), Err(ValidationError::NoFiles));
+    builder.file(|f| f.filename("myust.txt").content("hi"));
+    assert!(builder.validate().is_ok());
+}
+
+#[tokio::test]
+async fn limits() {
+    let server = myust::test_server::TestServer::start();
+    let client = myust::Client::new().base_url(server.url());
+
+    // Multi-byte characters count once.
+    let content = "é".repeat(MAX_FILE_CHARACTERS);
+    assert!(File::validate_size("myust.txt", &content).is_ok());
+    let content = "a".repeat(MAX_FILE_CHARACTERS + 1);
+    let err = client
+        .create_paste(|p| p.filename("big.txt").content(&content))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ContentTooLarge { file, size, max }
+            if file == "big.txt" && size == MAX_FILE_CHARACTERS + 1 && max == MAX_FILE_CHARACTERS
+    ));
+
+    let mut builder = PastesBuilder::default();
+    for i in 0..=MAX_FILES {
+        builder.file(|f| f.filename(format!("{i}.txt")).content("hi"));
+    }
+    assert_eq!(
+        builder.validate(),
+        Err(ValidationError::TooManyFiles {
+            count: MAX_FILES + 1,
+            max: MAX_FILES
+        })
+    );
+    let err = client
+        .create_multifile_paste(|p| {
+            for i in 0..=MAX_FILES {
+                p.file(|f| f.filename(format!("{i}.txt")).content("hi"));
+            }
+            p
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::TooManyFiles { .. }));
+    assert_eq!(server.paste_count(), 0);
+}
+
+#[test]
+fn try_file() {
+    let mut pastes = PastesBuilder::default();
+    pastes
+        .try_file(|f| Ok(f.filename("first.txt").content("first")))
+        .unwrap();
+
+    let missing = pastes.try_file(|f| {
+        let content = std::fs::read_to_string("/nonexistent/myust.txt")?;
+        Ok(f.filename("missing.txt").content(content))
+    });
+    assert!(matches!(missing, Err(Error::Io(_))));
+    let empty = pastes.try_file(|f| Ok(f.filename("empty.txt")));
+    assert!(matches!(
+        empty,
+        Err(Error::Invalid(ValidationError::EmptyContent))
+    ));
+    let large = pastes.try_file(|f| {
+        Ok(f.filename("large.txt")
+            .content("x".repeat(MAX_FILE_CHARACTERS + 1)))
+    });
+    assert!(matches!(large, Err(Error::ContentTooLarge { .. })));
+    assert_eq!(pastes.files.len(), 1);
+
+    for i in 1..MAX_FILES {
+        pastes
+            .try_file(|f| Ok(f.filename(format!("{i}.txt")).content("hi")))
+            .unwrap();
+    }
+    let extra = pastes.try_file(|f| Ok(f.filename("extra.txt").content("hi")));
+    assert!(matches!(extra, Err(Error::TooManyFiles { .. })));
+    assert_eq!(pastes.files.len(), MAX_FILES);
+}
+
+#[test]
+fn size_helpers() {
+    let file = File {
+        filename: "notes.txt".to_string(),
+        content: "é".repeat(MAX_FILE_CHARACTERS),
+        ..Default::default()
+    };
+    assert_eq!(file.len_chars(), MAX_FILE_CHARACTERS);
+    assert_eq!(file.len_bytes(), 2 * MAX_FILE_CHARACTERS);
+    assert!(file.fits_limits());
+
+    let mut request = myust::CreatePasteRequest {
+        files: vec![file.clone(); MAX_FILES],
+        ..Default::default()
+    };
+    assert!(request.fits_limits());
+    request.files[0].content.push('é');
+    assert!(!request.files[0].fits_limits());
+    assert!(!request.fits_limits());
+    request.files[0] = file;
+    request.files.push(File::default());
+    assert!(!request.fits_limits());
+}
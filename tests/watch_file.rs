@@ -0,0 +1,95 @@
+use std::{fs, time::Duration};
+
+use myust::{test_server::TestServer, watch::FileWatch, Client, Error, Expiry, ValidationError};
+
+/// Wait for the watch to move on from the paste `from`.
+async fn next_paste(watch: &FileWatch, from: &str) -> String {
+    for _ in 0..50 {
+        let paste_id = watch.paste_id();
+        if paste_id != from {
+            return paste_id;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("the paste wasn't replaced");
+}
+
+#[tokio::test]
+async fn watch_file() {
+    let server = TestServer::start();
+    let client = Client::new()
+        .base_url(server.url())
+        .auth(TestServer::TOKEN)
+        .await;
+    let dir = std::env::temp_dir().join(format!("myust-watch-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("app.log");
+    fs::write(&path, "started\n").unwrap();
+    let first = client
+        .create_multifile_paste(|p| {
+            p.file(|f| {
+                f.filename("app.log")
+                    .content("started\n")
+                    .password("hunter2")
+                    .expires(Expiry::ONE_DAY)
+            })
+            .file(|f| f.filename("README.md").content("live log"))
+        })
+        .await
+        .unwrap();
+
+    let watch = client
+        .watch_file(&path, first.id.as_str(), |w| {
+            w.debounce(Duration::from_millis(100)).password("hunter2")
+        })
+        .await
+        .unwrap();
+    assert_eq!(watch.paste_id(), first.id);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    fs::write(&path, "started\nrequest failed\n").unwrap();
+    let second = next_paste(&watch, &first.id).await;
+    fs::write(&path, "started\nrequest failed\nretrying\n").unwrap();
+    let third = next_paste(&watch, &second).await;
+    assert!(watch.take_error().is_none());
+    watch.stop();
+
+    let paste = client
+        .get_paste(|p| p.id(&third).password("hunter2"))
+        .await
+        .unwrap();
+    assert_eq!(
+        paste.file("app.log").unwrap().content,
+        "started\nrequest failed\nretrying\n"
+    );
+    assert_eq!(paste.file("README.md").unwrap().content, "live log");
+    assert!(paste.expires.is_some());
+    // the shared paste is kept, the intermediate one is deleted
+    let kept = client
+        .get_paste(|p| p.id(&first.id).password("hunter2"))
+        .await
+        .unwrap();
+    assert_eq!(kept.file("app.log").unwrap().content, "started\n");
+    assert!(client.get_paste(|p| p.id(&second)).await.is_err());
+    assert_eq!(server.paste_count(), 2);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn watch_invalid_paste() {
+    let server = TestServer::start();
+    let client = Client::new().base_url(server.url());
+    let err = client
+        .watch_file("app.log", "../users/@me", |w| w)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Invalid(ValidationError::InvalidPasteId(_))
+    ));
+    let err = client
+        .watch_file("app.log", "MissingPaste", |w| w)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(myust::StatusCode::NOT_FOUND));
+}